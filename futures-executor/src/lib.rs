@@ -37,5 +37,6 @@ if_std! {
     pub use enter::{enter, Enter, EnterError};
 
     mod spawn;
-    pub use spawn::{spawn, Spawn, spawn_with_handle, SpawnWithHandle, JoinHandle};
+    pub use spawn::{spawn, Spawn, spawn_with_handle, SpawnWithHandle, JoinHandle,
+                    spawn_read_to_end};
 }
@@ -155,6 +155,42 @@ impl LocalPool {
         })
     }
 
+    /// Runs all tasks in the pool until none of them can make further
+    /// progress, without blocking the calling thread.
+    ///
+    /// The given executor, `exec`, is used as the default executor for any
+    /// *newly*-spawned tasks. You can route these additional tasks back into
+    /// the `LocalPool` by using its executor handle:
+    ///
+    /// ```
+    /// # extern crate futures;
+    /// # use futures::executor::LocalPool;
+    ///
+    /// # fn main() {
+    /// let mut pool = LocalPool::new();
+    /// let mut exec = pool.executor();
+    ///
+    /// // ... spawn some initial tasks using `exec.spawn()` or `exec.spawn_local()`
+    ///
+    /// // run the tasks in the pool until they all wait for outside events
+    /// pool.run_until_stalled(&mut exec);
+    /// # }
+    /// ```
+    ///
+    /// Tasks are polled for as long as any of them has been woken, including
+    /// by one another, so the function returns once every task in the pool has
+    /// either completed or returned `Pending` and is waiting for a wakeup from
+    /// outside the pool. The remaining tasks stay in the pool and continue
+    /// with further use of `run`, `run_until` or `run_until_stalled`. This
+    /// makes it handy for deterministic tests of how tasks interact. A task
+    /// which keeps waking itself prevents the function from returning.
+    pub fn run_until_stalled(&mut self, exec: &mut Executor) {
+        run_executor(|waker| {
+            self.poll_pool(waker, exec);
+            Async::Ready(())
+        })
+    }
+
     // Make maximal progress on the entire pool of spawned task, returning `Ready`
     // if the pool is empty and `Pending` if no further progress can be made.
     fn poll_pool(&mut self, waker: &Waker, exec: &mut Executor) -> Async<()> {
@@ -1,10 +1,16 @@
 use futures_core::{Future, Async, Poll};
+use futures_core::executor::{Executor, SpawnError};
 use futures_core::never::Never;
 use futures_core::task::{self, Context};
 use futures_channel::oneshot::{channel, Sender, Receiver};
 use futures_util::FutureExt;
+use futures_util::future::CatchUnwind;
+use futures_util::io::{AsyncRead, AsyncReadExt};
 
+use std::boxed::Box;
+use std::io;
 use std::thread;
+use std::vec::Vec;
 use std::sync::Arc;
 use std::sync::atomic::Ordering;
 use std::panic::{self, AssertUnwindSafe};
@@ -107,25 +113,50 @@ impl<F> Future for SpawnWithHandle<F>
     type Item = JoinHandle<F::Item, F::Error>;
     type Error = Never;
     fn poll(&mut self, cx: &mut Context) -> Poll<Self::Item, Never> {
-        let (tx, rx) = channel();
-        let keep_running_flag = Arc::new(AtomicBool::new(false));
-        // AssertUnwindSafe is used here because `Send + 'static` is basically
-        // an alias for an implementation of the `UnwindSafe` trait but we can't
-        // express that in the standard library right now.
-        let sender = MySender {
-            fut: AssertUnwindSafe(self.0.take().unwrap()).catch_unwind(),
-            tx: Some(tx),
-            keep_running_flag: keep_running_flag.clone(),
-        };
-
+        let (sender, handle) = with_handle(self.0.take().unwrap());
         cx.spawn(sender);
-        Ok(Async::Ready(JoinHandle {
-            inner: rx ,
-            keep_running_flag: keep_running_flag.clone()
-        }))
+        Ok(Async::Ready(handle))
     }
 }
 
+/// Spawn a task draining `reader` onto `exec`, yielding a
+/// [`JoinHandle`](::JoinHandle) which resolves to all the bytes read.
+///
+/// This is convenient for buffering a body on a background task. The
+/// handle resolves to the error if reading fails.
+pub fn spawn_read_to_end<E, R>(exec: &mut E, reader: R)
+    -> Result<JoinHandle<Vec<u8>, io::Error>, SpawnError>
+    where E: ?Sized + Executor,
+          R: AsyncRead + Send + 'static,
+{
+    let (sender, handle) = with_handle(reader.read_all().map(|(_, buf)| buf));
+    exec.spawn(Box::new(sender))?;
+    Ok(handle)
+}
+
+type Catching<F> = CatchUnwind<AssertUnwindSafe<F>>;
+type Caught<F> = thread::Result<Result<<F as Future>::Item, <F as Future>::Error>>;
+
+fn with_handle<F>(f: F) -> (MySender<Catching<F>, Caught<F>>, JoinHandle<F::Item, F::Error>)
+    where F: Future,
+{
+    let (tx, rx) = channel();
+    let keep_running_flag = Arc::new(AtomicBool::new(false));
+    // AssertUnwindSafe is used here because `Send + 'static` is basically
+    // an alias for an implementation of the `UnwindSafe` trait but we can't
+    // express that in the standard library right now.
+    let sender = MySender {
+        fut: AssertUnwindSafe(f).catch_unwind(),
+        tx: Some(tx),
+        keep_running_flag: keep_running_flag.clone(),
+    };
+    let handle = JoinHandle {
+        inner: rx,
+        keep_running_flag: keep_running_flag,
+    };
+    (sender, handle)
+}
+
 struct MySender<F, T> {
     fut: F,
     tx: Option<Sender<T>>,
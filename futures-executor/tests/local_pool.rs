@@ -181,3 +181,75 @@ fn tasks_are_scheduled_fairly() {
 
     pool.run(&mut exec);
 }
+
+#[test]
+fn run_until_stalled_returns_when_tasks_wait() {
+    struct Shared {
+        turn: usize,
+        exchanges: usize,
+        wakers: [Option<task::Waker>; 2],
+    }
+
+    // Takes turns with its peer, waking the peer after each turn, until
+    // both have had five turns.
+    struct PingPong {
+        shared: Rc<RefCell<Shared>>,
+        idx: usize,
+    }
+
+    impl Future for PingPong {
+        type Item = ();
+        type Error = Never;
+
+        fn poll(&mut self, cx: &mut task::Context) -> Poll<(), Never> {
+            let mut shared = self.shared.borrow_mut();
+            if shared.turn == self.idx && shared.exchanges < 10 {
+                shared.exchanges += 1;
+                shared.turn = 1 - self.idx;
+                if let Some(waker) = shared.wakers[1 - self.idx].take() {
+                    waker.wake();
+                }
+            }
+            shared.wakers[self.idx] = Some(cx.waker().clone());
+            Ok(Async::Pending)
+        }
+    }
+
+    let shared = Rc::new(RefCell::new(Shared {
+        turn: 0,
+        exchanges: 0,
+        wakers: [None, None],
+    }));
+    let mut pool = LocalPool::new();
+    let mut exec = pool.executor();
+    for idx in 0..2 {
+        exec.spawn_local(Box::new(PingPong { shared: shared.clone(), idx })).unwrap();
+    }
+
+    pool.run_until_stalled(&mut exec);
+    let shared = shared.borrow();
+    assert_eq!(shared.exchanges, 10);
+    assert_eq!(shared.turn, 0);
+    assert!(shared.wakers.iter().all(|w| w.is_some()));
+}
+
+#[test]
+fn run_until_stalled_resumes_later() {
+    let (tx, rx) = oneshot::channel::<()>();
+    let done = Rc::new(Cell::new(false));
+    let done2 = done.clone();
+
+    let mut pool = LocalPool::new();
+    let mut exec = pool.executor();
+    exec.spawn_local(Box::new(rx.then(move |_| {
+        done2.set(true);
+        DONE
+    }))).unwrap();
+
+    pool.run_until_stalled(&mut exec);
+    assert!(!done.get());
+
+    tx.send(()).unwrap();
+    pool.run_until_stalled(&mut exec);
+    assert!(done.get());
+}
@@ -0,0 +1,143 @@
+use std::boxed::Box;
+use std::cmp;
+use std::fmt;
+use std::vec::Vec;
+
+use futures_core::{Async, Poll, task};
+
+use {AsyncBufRead, AsyncRead, AsyncReadable, Error, Initializer};
+
+// The default capacity of the buffer, the same as `std::io::BufReader`'s.
+const DEFAULT_CAPACITY: usize = 8 * 1024;
+
+/// Adds buffering to any `AsyncRead`.
+///
+/// This is the asynchronous analog to `std::io::BufReader`. Small reads are
+/// served from an in-memory buffer which is refilled by reading from the
+/// underlying reader in large chunks, so that many small reads don't each
+/// need a read of the underlying reader, which may be a system call. It also
+/// provides `AsyncBufRead` on top of any reader.
+///
+/// Reads at least as large as the buffer bypass it while it is empty.
+pub struct BufReader<R> {
+    inner: R,
+    buf: Box<[u8]>,
+    pos: usize,
+    cap: usize,
+}
+
+impl<R: AsyncRead> BufReader<R> {
+    /// Creates a new `BufReader` with a default buffer capacity of 8 KiB.
+    pub fn new(inner: R) -> BufReader<R> {
+        BufReader::with_capacity(DEFAULT_CAPACITY, inner)
+    }
+
+    /// Creates a new `BufReader` with a buffer of the given capacity.
+    pub fn with_capacity(cap: usize, inner: R) -> BufReader<R> {
+        unsafe {
+            let mut buf = Vec::with_capacity(cap);
+            buf.set_len(cap);
+            // Only readers which don't look at the buffer before writing to
+            // it may be handed uninitialized memory.
+            inner.initializer().initialize(&mut buf);
+            BufReader {
+                inner,
+                buf: buf.into_boxed_slice(),
+                pos: 0,
+                cap: 0,
+            }
+        }
+    }
+}
+
+impl<R> BufReader<R> {
+    /// Returns a reference to the underlying reader.
+    ///
+    /// It is inadvisable to directly read from the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// It is inadvisable to directly read from the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this `BufReader`, returning the underlying reader.
+    ///
+    /// Note that any leftover data in the internal buffer is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for BufReader<R> {
+    unsafe fn initializer(&self) -> Initializer {
+        self.inner.initializer()
+    }
+
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, Error>
+    {
+        // Don't copy large reads through the buffer when there is nothing
+        // buffered anyway.
+        if self.pos == self.cap && buf.len() >= self.buf.len() {
+            return self.inner.poll_read(cx, buf)
+        }
+        let n = {
+            let available = match self.poll_fill_buf(cx)? {
+                Async::Ready(available) => available,
+                Async::Pending => return Ok(Async::Pending),
+            };
+            let n = cmp::min(available.len(), buf.len());
+            buf[..n].copy_from_slice(&available[..n]);
+            n
+        };
+        self.consume(n);
+        Ok(Async::Ready(n))
+    }
+}
+
+impl<R: AsyncRead> AsyncBufRead for BufReader<R> {
+    fn poll_fill_buf(&mut self, cx: &mut task::Context) -> Poll<&[u8], Error> {
+        // The buffer is only touched once the read has completed, so a
+        // `Pending` read leaves it as it was.
+        if self.pos >= self.cap {
+            self.cap = match self.inner.poll_read(cx, &mut self.buf)? {
+                Async::Ready(n) => n,
+                Async::Pending => return Ok(Async::Pending),
+            };
+            self.pos = 0;
+        }
+        Ok(Async::Ready(&self.buf[self.pos..self.cap]))
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos = cmp::min(self.pos + amt, self.cap);
+    }
+}
+
+// Buffered bytes can be read right away; otherwise readiness is found out by
+// filling the buffer, which keeps whatever the read returns.
+impl<R: AsyncRead> AsyncReadable for BufReader<R> {
+    fn poll_readable(&mut self, cx: &mut task::Context) -> Poll<(), Error> {
+        if self.pos < self.cap {
+            return Ok(Async::Ready(()))
+        }
+        match self.poll_fill_buf(cx)? {
+            Async::Ready(_) => Ok(Async::Ready(())),
+            Async::Pending => Ok(Async::Pending),
+        }
+    }
+}
+
+impl<R: fmt::Debug> fmt::Debug for BufReader<R> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("BufReader")
+            .field("reader", &self.inner)
+            .field("buffer", &format_args!("{}/{}", self.cap - self.pos, self.buf.len()))
+            .finish()
+    }
+}
@@ -0,0 +1,165 @@
+use std::fmt;
+use std::vec::Vec;
+
+use futures_core::{Async, Future, Poll, task};
+
+use {AsyncWritable, AsyncWrite, Error, ErrorKind};
+
+// The default capacity of the buffer, the same as `std::io::BufWriter`'s.
+const DEFAULT_CAPACITY: usize = 8 * 1024;
+
+/// Adds buffering to any `AsyncWrite`.
+///
+/// This is the asynchronous analog to `std::io::BufWriter`. Small writes are
+/// collected in an in-memory buffer which is only written to the underlying
+/// writer once it is full, or when the `BufWriter` is flushed or closed, so
+/// that many small writes don't each need a write of the underlying writer,
+/// which may be a system call.
+///
+/// If the underlying writer accepts only part of the buffer, the rest is kept
+/// and written by the next call. Writes at least as large as the buffer
+/// bypass it once it has been emptied.
+///
+/// Dropping a `BufWriter` loses any buffered bytes; flush or close it, or
+/// call `into_inner`, first.
+pub struct BufWriter<W> {
+    inner: W,
+    buf: Vec<u8>,
+    // The number of bytes at the start of `buf` already written.
+    written: usize,
+}
+
+impl<W: AsyncWrite> BufWriter<W> {
+    /// Creates a new `BufWriter` with a default buffer capacity of 8 KiB.
+    pub fn new(inner: W) -> BufWriter<W> {
+        BufWriter::with_capacity(DEFAULT_CAPACITY, inner)
+    }
+
+    /// Creates a new `BufWriter` with a buffer of the given capacity.
+    pub fn with_capacity(cap: usize, inner: W) -> BufWriter<W> {
+        BufWriter {
+            inner,
+            buf: Vec::with_capacity(cap),
+            written: 0,
+        }
+    }
+
+    /// Creates a future which writes out the buffered bytes and then
+    /// resolves to the underlying writer.
+    ///
+    /// The underlying writer is not flushed. If writing the buffered bytes
+    /// fails the error is returned and the `BufWriter` is dropped.
+    pub fn into_inner(self) -> IntoInner<W> {
+        IntoInner { writer: Some(self) }
+    }
+
+    fn poll_flush_buf(&mut self, cx: &mut task::Context) -> Poll<(), Error> {
+        while self.written < self.buf.len() {
+            let n = match self.inner.poll_write(cx, &self.buf[self.written..])? {
+                Async::Ready(n) => n,
+                Async::Pending => return Ok(Async::Pending),
+            };
+            if n == 0 {
+                return Err(Error::new(ErrorKind::WriteZero,
+                                      "failed to write the buffered data"))
+            }
+            self.written += n;
+        }
+        self.buf.clear();
+        self.written = 0;
+        Ok(Async::Ready(()))
+    }
+}
+
+impl<W> BufWriter<W> {
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// It is inadvisable to directly write to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+}
+
+impl<W: AsyncWrite> AsyncWrite for BufWriter<W> {
+    fn poll_write(&mut self, cx: &mut task::Context, buf: &[u8])
+        -> Poll<usize, Error>
+    {
+        if self.buf.len() + buf.len() > self.buf.capacity() {
+            if let Async::Pending = self.poll_flush_buf(cx)? {
+                return Ok(Async::Pending)
+            }
+        }
+        if buf.len() >= self.buf.capacity() {
+            self.inner.poll_write(cx, buf)
+        } else {
+            self.buf.extend_from_slice(buf);
+            Ok(Async::Ready(buf.len()))
+        }
+    }
+
+    fn poll_flush(&mut self, cx: &mut task::Context) -> Poll<(), Error> {
+        if let Async::Pending = self.poll_flush_buf(cx)? {
+            return Ok(Async::Pending)
+        }
+        self.inner.poll_flush(cx)
+    }
+
+    fn poll_close(&mut self, cx: &mut task::Context) -> Poll<(), Error> {
+        if let Async::Pending = self.poll_flush_buf(cx)? {
+            return Ok(Async::Pending)
+        }
+        self.inner.poll_close(cx)
+    }
+}
+
+// A write fits while the buffer has room; once it is full, it has to be
+// written out first.
+impl<W: AsyncWrite> AsyncWritable for BufWriter<W> {
+    fn poll_writable(&mut self, cx: &mut task::Context) -> Poll<(), Error> {
+        if self.buf.len() < self.buf.capacity() {
+            return Ok(Async::Ready(()))
+        }
+        self.poll_flush_buf(cx)
+    }
+}
+
+impl<W: fmt::Debug> fmt::Debug for BufWriter<W> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("BufWriter")
+            .field("writer", &self.inner)
+            .field("buffer", &format_args!("{}/{}", self.buf.len() - self.written,
+                                           self.buf.capacity()))
+            .finish()
+    }
+}
+
+/// A future which writes out the bytes buffered by a `BufWriter` and
+/// resolves to its underlying writer.
+///
+/// This is created by the [`BufWriter::into_inner`] method.
+///
+/// [`BufWriter::into_inner`]: struct.BufWriter.html#method.into_inner
+#[derive(Debug)]
+pub struct IntoInner<W> {
+    writer: Option<BufWriter<W>>,
+}
+
+impl<W: AsyncWrite> Future for IntoInner<W> {
+    type Item = W;
+    type Error = Error;
+
+    fn poll(&mut self, cx: &mut task::Context) -> Poll<W, Error> {
+        {
+            let writer = self.writer.as_mut().expect("cannot poll IntoInner twice");
+            if let Async::Pending = writer.poll_flush_buf(cx)? {
+                return Ok(Async::Pending)
+            }
+        }
+        Ok(Async::Ready(self.writer.take().unwrap().inner))
+    }
+}
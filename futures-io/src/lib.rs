@@ -1,8 +1,10 @@
 //! Asynchronous I/O
 //!
-//! This crate contains the `AsyncRead` and `AsyncWrite` traits, the
-//! asynchronous analogs to `std::io::{Read, Write}`. The primary difference is
-//! that these traits integrate with the asynchronous task system.
+//! This crate contains the `AsyncRead`, `AsyncBufRead`, `AsyncWrite` and
+//! `AsyncSeek` traits, the asynchronous analogs to
+//! `std::io::{Read, BufRead, Write, Seek}`. The
+//! primary difference is that these traits integrate with the asynchronous
+//! task system.
 
 #![no_std]
 #![deny(missing_docs, missing_debug_implementations)]
@@ -35,6 +37,11 @@ if_std! {
     pub use StdIo::ErrorKind as ErrorKind;
     pub use StdIo::Result as Result;
 
+    mod buf_reader;
+    mod buf_writer;
+    pub use buf_reader::BufReader;
+    pub use buf_writer::{BufWriter, IntoInner};
+
     /// A type used to conditionally initialize buffers passed to `AsyncRead`
     /// methods, modeled after `std`.
     #[derive(Debug)]
@@ -129,9 +136,13 @@ if_std! {
         /// `Ok(Async::Pending)` and arranges for the current task (via
         /// `cx.waker()`) to receive a notification when the object becomes
         /// readable or is closed.
-        /// By default, this method delegates to using `poll_read` on the first
-        /// buffer in `vec`. Objects which support vectored IO should override
-        /// this method.
+        /// By default, this method calls `poll_read` on each non-empty buffer
+        /// in `vec` in turn, moving on to the next one only while the previous
+        /// one was filled completely. It returns the total number of bytes
+        /// read once a read comes up short or pends, or an error if the first
+        /// read fails; an error or `Pending` after some bytes have been read
+        /// is left for the next call to run into. Objects which support
+        /// vectored IO should override this method.
         ///
         /// # Implementation
         ///
@@ -142,15 +153,89 @@ if_std! {
         fn poll_vectored_read(&mut self, cx: &mut task::Context, vec: &mut [&mut IoVec])
             -> Poll<usize, Error>
         {
-            if let Some(ref mut first_iovec) = vec.get_mut(0) {
-                self.poll_read(cx, first_iovec)
-            } else {
-                // `vec` is empty.
-                return Ok(Async::Ready(0));
+            let mut total = 0;
+            for iovec in vec.iter_mut().filter(|iovec| !iovec.is_empty()) {
+                match self.poll_read(cx, iovec) {
+                    Ok(Async::Ready(n)) => {
+                        total += n;
+                        if n < iovec.len() {
+                            break
+                        }
+                    }
+                    Ok(Async::Pending) if total == 0 => return Ok(Async::Pending),
+                    Err(e) if total == 0 => return Err(e),
+                    Ok(Async::Pending) | Err(_) => break,
+                }
             }
+            Ok(Async::Ready(total))
         }
     }
 
+    /// Determine whether an `AsyncRead` has data available.
+    ///
+    /// This trait is implemented by readers which can tell whether a read
+    /// would make progress without consuming any data, such as buffered
+    /// readers checking for buffered bytes. It allows consumers to put off
+    /// expensive setup until there is something to read.
+    pub trait AsyncReadable: AsyncRead {
+        /// Attempt to wait until the next call to `poll_read` can return
+        /// without blocking, either because data is available or because the
+        /// end of the stream has been reached.
+        ///
+        /// On success, returns `Ok(Async::Ready(()))`. No data is consumed.
+        ///
+        /// If no data is available, the method returns `Ok(Async::Pending)`
+        /// and arranges for the current task (via `cx.waker()`) to receive a
+        /// notification when the object becomes readable or is closed.
+        ///
+        /// # Implementation
+        ///
+        /// This function may not return errors of kind `WouldBlock` or
+        /// `Interrupted`.  Implementations must convert `WouldBlock` into
+        /// `Async::Pending` and either internally retry or convert
+        /// `Interrupted` into another error kind.
+        fn poll_readable(&mut self, cx: &mut task::Context) -> Poll<(), Error>;
+    }
+
+    /// Read bytes asynchronously through an internal buffer.
+    ///
+    /// This trait is analogous to the `std::io::BufRead` trait, but
+    /// integrates with the asynchronous task system. It lets consumers look
+    /// at buffered bytes before deciding how many of them to consume, which
+    /// parsers of delimited data need.
+    pub trait AsyncBufRead: AsyncRead {
+        /// Attempt to return the contents of the internal buffer, filling it
+        /// with more data from the inner reader if it is empty.
+        ///
+        /// On success, returns `Ok(Async::Ready(buf))`. An empty `buf` means
+        /// that the end of the stream has been reached. The bytes are not
+        /// consumed: they are returned again by the next call unless
+        /// `consume` is called in between.
+        ///
+        /// If no data is available for reading, the method returns
+        /// `Ok(Async::Pending)` and arranges for the current task (via
+        /// `cx.waker()`) to receive a notification when the object becomes
+        /// readable or is closed. The borrow of `self` ends with the returned
+        /// value, so the call can simply be retried later.
+        ///
+        /// # Implementation
+        ///
+        /// This function may not return errors of kind `WouldBlock` or
+        /// `Interrupted`.  Implementations must convert `WouldBlock` into
+        /// `Async::Pending` and either internally retry or convert
+        /// `Interrupted` into another error kind.
+        fn poll_fill_buf(&mut self, cx: &mut task::Context) -> Poll<&[u8], Error>;
+
+        /// Tells this buffer that `amt` bytes have been consumed from the
+        /// buffer, so they should no longer be returned by `poll_fill_buf` or
+        /// `poll_read`.
+        ///
+        /// This function is a lower-level call. It needs to be paired with
+        /// `poll_fill_buf` to work properly, and `amt` must not be larger
+        /// than the length of the buffer it last returned.
+        fn consume(&mut self, amt: usize);
+    }
+
     /// Write bytes asynchronously.
     ///
     /// This trait is analogous to the `std::io::Write` trait, but integrates
@@ -247,6 +332,63 @@ if_std! {
         fn poll_close(&mut self, cx: &mut task::Context) -> Poll<(), Error>;
     }
 
+    /// Determine whether an `AsyncWrite` is ready to accept more data.
+    ///
+    /// This trait is implemented by writers which can cheaply tell whether a
+    /// write would make progress, such as buffered writers checking their
+    /// remaining capacity. It allows producers to wait for capacity before
+    /// generating expensive data.
+    pub trait AsyncWritable: AsyncWrite {
+        /// Attempt to wait until the next call to `poll_write` is likely to
+        /// accept at least one byte.
+        ///
+        /// On success, returns `Ok(Async::Ready(()))`.
+        ///
+        /// If the object cannot currently accept data, the method returns
+        /// `Ok(Async::Pending)` and arranges for the current task (via
+        /// `cx.waker()`) to receive a notification when it can. This method
+        /// may make progress on writing out previously buffered data.
+        ///
+        /// # Implementation
+        ///
+        /// This function may not return errors of kind `WouldBlock` or
+        /// `Interrupted`.  Implementations must convert `WouldBlock` into
+        /// `Async::Pending` and either internally retry or convert
+        /// `Interrupted` into another error kind.
+        fn poll_writable(&mut self, cx: &mut task::Context) -> Poll<(), Error>;
+    }
+
+    /// Seek to an offset, in bytes, in a stream asynchronously.
+    ///
+    /// This trait is analogous to the `std::io::Seek` trait, but integrates
+    /// with the asynchronous task system.
+    pub trait AsyncSeek {
+        /// Attempt to seek to an offset, in bytes, in a stream.
+        ///
+        /// A seek beyond the end of a stream is allowed, but behavior is
+        /// defined by the implementation; for in-memory sources like
+        /// `std::io::Cursor`, reads from there yield EOF.
+        ///
+        /// On success, returns `Ok(Async::Ready(pos))` with the new position
+        /// from the start of the stream.
+        ///
+        /// If the seek cannot complete immediately, the method returns
+        /// `Ok(Async::Pending)` and arranges for the current task (via
+        /// `cx.waker()`) to receive a notification when it can make progress.
+        /// The seek is then resumed by calling `poll_seek` again with the
+        /// same `pos`; until it completes, the position of the stream is
+        /// unspecified and it should not be read from or written to.
+        ///
+        /// # Implementation
+        ///
+        /// This function may not return errors of kind `WouldBlock` or
+        /// `Interrupted`.  Implementations must convert `WouldBlock` into
+        /// `Async::Pending` and either internally retry or convert
+        /// `Interrupted` into another error kind.
+        fn poll_seek(&mut self, cx: &mut task::Context, pos: StdIo::SeekFrom)
+            -> Poll<u64, Error>;
+    }
+
     macro_rules! deref_async_read {
         () => {
             unsafe fn initializer(&self) -> Initializer {
@@ -291,6 +433,62 @@ if_std! {
         }
     }
 
+    macro_rules! deref_async_buf_read {
+        () => {
+            fn poll_fill_buf(&mut self, cx: &mut task::Context) -> Poll<&[u8], Error> {
+                (**self).poll_fill_buf(cx)
+            }
+
+            fn consume(&mut self, amt: usize) {
+                (**self).consume(amt)
+            }
+        }
+    }
+
+    impl<T: ?Sized + AsyncBufRead> AsyncBufRead for Box<T> {
+        deref_async_buf_read!();
+    }
+
+    impl<'a, T: ?Sized + AsyncBufRead> AsyncBufRead for &'a mut T {
+        deref_async_buf_read!();
+    }
+
+    macro_rules! delegate_async_buf_read_to_stdio {
+        () => {
+            fn poll_fill_buf(&mut self, _: &mut task::Context) -> Poll<&[u8], Error> {
+                Ok(Async::Ready(StdIo::BufRead::fill_buf(self)?))
+            }
+
+            fn consume(&mut self, amt: usize) {
+                StdIo::BufRead::consume(self, amt)
+            }
+        }
+    }
+
+    impl<'a> AsyncBufRead for &'a [u8] {
+        delegate_async_buf_read_to_stdio!();
+    }
+
+    impl<T: AsRef<[u8]>> AsyncBufRead for StdIo::Cursor<T> {
+        delegate_async_buf_read_to_stdio!();
+    }
+
+    impl AsyncBufRead for StdIo::Empty {
+        delegate_async_buf_read_to_stdio!();
+    }
+
+    impl<T: ?Sized + AsyncReadable> AsyncReadable for Box<T> {
+        fn poll_readable(&mut self, cx: &mut task::Context) -> Poll<(), Error> {
+            (**self).poll_readable(cx)
+        }
+    }
+
+    impl<'a, T: ?Sized + AsyncReadable> AsyncReadable for &'a mut T {
+        fn poll_readable(&mut self, cx: &mut task::Context) -> Poll<(), Error> {
+            (**self).poll_readable(cx)
+        }
+    }
+
     impl<'a> AsyncRead for &'a [u8] {
         unsafe_delegate_async_read_to_stdio!();
     }
@@ -299,10 +497,27 @@ if_std! {
         unsafe_delegate_async_read_to_stdio!();
     }
 
+    impl AsyncRead for StdIo::Empty {
+        unsafe_delegate_async_read_to_stdio!();
+    }
+
     impl<T: AsRef<[u8]>> AsyncRead for StdIo::Cursor<T> {
         unsafe_delegate_async_read_to_stdio!();
     }
 
+    // In-memory readers never block.
+    impl<'a> AsyncReadable for &'a [u8] {
+        fn poll_readable(&mut self, _: &mut task::Context) -> Poll<(), Error> {
+            Ok(Async::Ready(()))
+        }
+    }
+
+    impl<T: AsRef<[u8]>> AsyncReadable for StdIo::Cursor<T> {
+        fn poll_readable(&mut self, _: &mut task::Context) -> Poll<(), Error> {
+            Ok(Async::Ready(()))
+        }
+    }
+
     macro_rules! deref_async_write {
         () => {
             fn poll_write(&mut self, cx: &mut task::Context, buf: &[u8])
@@ -353,6 +568,18 @@ if_std! {
         }
     }
 
+    impl<T: ?Sized + AsyncWritable> AsyncWritable for Box<T> {
+        fn poll_writable(&mut self, cx: &mut task::Context) -> Poll<(), Error> {
+            (**self).poll_writable(cx)
+        }
+    }
+
+    impl<'a, T: ?Sized + AsyncWritable> AsyncWritable for &'a mut T {
+        fn poll_writable(&mut self, cx: &mut task::Context) -> Poll<(), Error> {
+            (**self).poll_writable(cx)
+        }
+    }
+
     impl<'a> AsyncWrite for StdIo::Cursor<&'a mut [u8]> {
         delegate_async_write_to_stdio!();
     }
@@ -365,7 +592,52 @@ if_std! {
         delegate_async_write_to_stdio!();
     }
 
+    macro_rules! deref_async_seek {
+        () => {
+            fn poll_seek(&mut self, cx: &mut task::Context, pos: StdIo::SeekFrom)
+                -> Poll<u64, Error>
+            {
+                (**self).poll_seek(cx, pos)
+            }
+        }
+    }
+
+    impl<T: ?Sized + AsyncSeek> AsyncSeek for Box<T> {
+        deref_async_seek!();
+    }
+
+    impl<'a, T: ?Sized + AsyncSeek> AsyncSeek for &'a mut T {
+        deref_async_seek!();
+    }
+
+    // Seeking a cursor just moves its position, so it never pends.
+    impl<T: AsRef<[u8]>> AsyncSeek for StdIo::Cursor<T> {
+        fn poll_seek(&mut self, _: &mut task::Context, pos: StdIo::SeekFrom)
+            -> Poll<u64, Error>
+        {
+            Ok(Async::Ready(StdIo::Seek::seek(self, pos)?))
+        }
+    }
+
     impl AsyncWrite for StdIo::Sink {
         delegate_async_write_to_stdio!();
     }
+
+    // Written out by hand: `std::io::Write` is only implemented for `Empty`
+    // by newer versions of std.
+    impl AsyncWrite for StdIo::Empty {
+        fn poll_write(&mut self, _: &mut task::Context, buf: &[u8])
+            -> Poll<usize, Error>
+        {
+            Ok(Async::Ready(buf.len()))
+        }
+
+        fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), Error> {
+            Ok(Async::Ready(()))
+        }
+
+        fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), Error> {
+            Ok(Async::Ready(()))
+        }
+    }
 }
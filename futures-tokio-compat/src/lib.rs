@@ -0,0 +1,239 @@
+//! Compatibility with the IO traits of `tokio-io` 0.1.
+//!
+//! The IO traits of `tokio-io` are built on futures 0.1, where a poll finds
+//! the task to wake through the thread-local `task::current()` and signals
+//! that it isn't ready with a `WouldBlock` error. The traits of futures-rs
+//! are passed the task explicitly, as a `task::Context`.
+//!
+//! - [`Compat`] wraps one of the IO objects of futures-rs and implements the
+//!   `tokio-io` traits, along with `std::io::Read` and `std::io::Write`.
+//! - [`Compat01As02`] wraps a `tokio-io` object and implements the
+//!   `AsyncRead` and `AsyncWrite` traits of futures-rs.
+//!
+//! This is a crate of its own, outside of the `futures` facade, because it
+//! depends on futures 0.1.
+//!
+//! [`Compat`]: struct.Compat.html
+//! [`Compat01As02`]: struct.Compat01As02.html
+
+#![deny(missing_docs, missing_debug_implementations)]
+#![doc(html_root_url = "https://docs.rs/futures-tokio-compat/0.2.2")]
+
+extern crate futures as futures01;
+extern crate futures_core;
+extern crate futures_io;
+extern crate tokio_io;
+
+use std::boxed::Box;
+use std::io;
+use std::sync::Arc;
+
+use futures_core::{Async, Future, Never, Poll, task};
+use futures_core::executor::{Executor, SpawnError};
+use futures_core::task::{LocalMap, Wake, Waker};
+use futures01::executor::{Notify, Spawn};
+
+use futures_io::{AsyncRead, AsyncWrite, Initializer};
+
+/// Wraps one of the IO objects of futures-rs so that it implements the IO
+/// traits of `tokio-io`.
+///
+/// Polls wake the futures 0.1 task which is current when they are called, so
+/// the wrapper must only be used from within a futures 0.1 task; otherwise
+/// a poll panics. A poll which isn't ready yet is reported as a `WouldBlock`
+/// error by `Read` and `Write`, as `tokio-io` expects.
+///
+/// The wrapped object runs without a default executor, so spawning from
+/// within one of its polls fails.
+#[derive(Debug)]
+pub struct Compat<T> {
+    inner: T,
+}
+
+impl<T> Compat<T> {
+    /// Wraps `inner`.
+    pub fn new(inner: T) -> Compat<T> {
+        Compat { inner }
+    }
+
+    /// Returns a reference to the wrapped object.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the wrapped object.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consumes this `Compat`, returning the wrapped object.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+// Wakes the current futures 0.1 task.
+struct TaskWake(futures01::task::Task);
+
+impl Wake for TaskWake {
+    fn wake(arc_self: &Arc<Self>) {
+        arc_self.0.notify();
+    }
+}
+
+// The executor of tasks polled through `Compat`, which have no executor of
+// their own.
+struct NoExecutor;
+
+impl Executor for NoExecutor {
+    fn spawn(&mut self, _: Box<Future<Item = (), Error = Never> + Send>)
+        -> Result<(), SpawnError>
+    {
+        Err(SpawnError::shutdown())
+    }
+}
+
+// Calls `f` with a context which wakes the current futures 0.1 task.
+fn with_context<F, R>(f: F) -> R
+    where F: FnOnce(&mut task::Context) -> R,
+{
+    let waker = Waker::from(Arc::new(TaskWake(futures01::task::current())));
+    let mut map = LocalMap::new();
+    let mut exec = NoExecutor;
+    let mut cx = task::Context::new(&mut map, &waker, &mut exec);
+    f(&mut cx)
+}
+
+fn into_io<T>(res: Poll<T, io::Error>) -> io::Result<T> {
+    match res? {
+        Async::Ready(t) => Ok(t),
+        Async::Pending => Err(io::ErrorKind::WouldBlock.into()),
+    }
+}
+
+fn into_poll01<T>(res: Poll<T, io::Error>) -> futures01::Poll<T, io::Error> {
+    match res? {
+        Async::Ready(t) => Ok(futures01::Async::Ready(t)),
+        Async::Pending => Ok(futures01::Async::NotReady),
+    }
+}
+
+impl<T: AsyncRead> io::Read for Compat<T> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let inner = &mut self.inner;
+        into_io(with_context(|cx| inner.poll_read(cx, buf)))
+    }
+}
+
+impl<T: AsyncRead> tokio_io::AsyncRead for Compat<T> {
+    unsafe fn prepare_uninitialized_buffer(&self, buf: &mut [u8]) -> bool {
+        let initializer = self.inner.initializer();
+        initializer.initialize(buf);
+        initializer.should_initialize()
+    }
+}
+
+impl<T: AsyncWrite> io::Write for Compat<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let inner = &mut self.inner;
+        into_io(with_context(|cx| inner.poll_write(cx, buf)))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let inner = &mut self.inner;
+        into_io(with_context(|cx| inner.poll_flush(cx)))
+    }
+}
+
+impl<T: AsyncWrite> tokio_io::AsyncWrite for Compat<T> {
+    fn shutdown(&mut self) -> futures01::Poll<(), io::Error> {
+        let inner = &mut self.inner;
+        into_poll01(with_context(|cx| inner.poll_close(cx)))
+    }
+}
+
+/// Wraps an IO object implementing the traits of `tokio-io` so that it
+/// implements the `AsyncRead` and `AsyncWrite` traits of futures-rs.
+///
+/// On every poll, the wrapped object is polled within a futures 0.1 task
+/// whose `task::current()` wakes the task of the `task::Context` the poll
+/// was given.
+#[derive(Debug)]
+pub struct Compat01As02<T> {
+    inner: Spawn<T>,
+}
+
+impl<T> Compat01As02<T> {
+    /// Wraps `inner`.
+    pub fn new(inner: T) -> Compat01As02<T> {
+        Compat01As02 { inner: futures01::executor::spawn(inner) }
+    }
+
+    /// Returns a reference to the wrapped object.
+    pub fn get_ref(&self) -> &T {
+        self.inner.get_ref()
+    }
+
+    /// Returns a mutable reference to the wrapped object.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.inner.get_mut()
+    }
+
+    /// Consumes this `Compat01As02`, returning the wrapped object.
+    pub fn into_inner(self) -> T {
+        self.inner.into_inner()
+    }
+
+    // Calls `f` on the wrapped object within a futures 0.1 task which wakes
+    // the task of `cx`.
+    fn poll_01<F, R>(&mut self, cx: &mut task::Context, f: F) -> Poll<R, io::Error>
+        where F: FnOnce(&mut T) -> futures01::Poll<R, io::Error>,
+    {
+        let notify = Arc::new(WakerNotify(cx.waker().clone()));
+        match self.inner.poll_fn_notify(&notify, 0, f)? {
+            futures01::Async::Ready(t) => Ok(Async::Ready(t)),
+            futures01::Async::NotReady => Ok(Async::Pending),
+        }
+    }
+}
+
+// Wakes a task of futures-rs when a futures 0.1 task is notified.
+struct WakerNotify(Waker);
+
+impl Notify for WakerNotify {
+    fn notify(&self, _: usize) {
+        self.0.wake();
+    }
+}
+
+impl<T: tokio_io::AsyncRead> AsyncRead for Compat01As02<T> {
+    unsafe fn initializer(&self) -> Initializer {
+        if self.get_ref().prepare_uninitialized_buffer(&mut []) {
+            Initializer::zeroing()
+        } else {
+            Initializer::nop()
+        }
+    }
+
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        self.poll_01(cx, |inner| tokio_io::AsyncRead::poll_read(inner, buf))
+    }
+}
+
+impl<T: tokio_io::AsyncWrite> AsyncWrite for Compat01As02<T> {
+    fn poll_write(&mut self, cx: &mut task::Context, buf: &[u8])
+        -> Poll<usize, io::Error>
+    {
+        self.poll_01(cx, |inner| tokio_io::AsyncWrite::poll_write(inner, buf))
+    }
+
+    fn poll_flush(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        self.poll_01(cx, |inner| tokio_io::AsyncWrite::poll_flush(inner))
+    }
+
+    fn poll_close(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        self.poll_01(cx, |inner| tokio_io::AsyncWrite::shutdown(inner))
+    }
+}
@@ -0,0 +1,56 @@
+extern crate futures;
+extern crate futures_executor;
+extern crate futures_tokio_compat;
+extern crate futures_util;
+extern crate tokio_io;
+
+use std::io::Cursor;
+use std::thread;
+use std::time::Duration;
+
+use futures::Future as Future01;
+use futures_executor::block_on;
+use futures_tokio_compat::{Compat, Compat01As02};
+use futures_util::io::{pipe, AsyncReadExt, AsyncWriteExt};
+
+#[test]
+fn round_trip_read() {
+    let mut reader = Compat01As02::new(Compat::new(Cursor::new(b"through both".to_vec())));
+    let mut data = Vec::new();
+    block_on(reader.read_to_end(&mut data)).unwrap();
+    assert_eq!(data, b"through both");
+    assert_eq!(reader.into_inner().into_inner().position(), 12);
+}
+
+#[test]
+fn round_trip_write() {
+    let mut writer = Compat01As02::new(Compat::new(Cursor::new(Vec::new())));
+    block_on(writer.write_all(b"through both")).unwrap();
+    block_on(writer.close()).unwrap();
+    assert_eq!(writer.into_inner().into_inner().into_inner(), b"through both");
+}
+
+#[test]
+fn tokio_combinators() {
+    let reader = Compat::new(Cursor::new(b"tokio".to_vec()));
+    let (_, data) = tokio_io::io::read_to_end(reader, Vec::new()).wait().unwrap();
+    assert_eq!(data, b"tokio");
+
+    let writer = Compat::new(Cursor::new(Vec::new()));
+    let (writer, _) = tokio_io::io::write_all(writer, b"tokio").wait().unwrap();
+    assert_eq!(writer.into_inner().into_inner(), b"tokio");
+}
+
+#[test]
+fn wakeups_cross_both_models() {
+    let (reader, mut writer) = pipe();
+    let mut reader = Compat01As02::new(Compat::new(reader));
+    let t = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        block_on(writer.write_all(b"late")).unwrap();
+    });
+    let mut data = Vec::new();
+    block_on(reader.read_to_end(&mut data)).unwrap();
+    assert_eq!(data, b"late");
+    t.join().unwrap();
+}
@@ -0,0 +1,184 @@
+use std::io;
+use std::vec::Vec;
+
+use super::{Decoder, Encoder};
+
+/// A codec for frames prefixed with their length as a big-endian integer.
+///
+/// By default the length field is 4 bytes wide, holds the length of the
+/// payload which follows it, and frames longer than 8 MiB are rejected.
+/// Other framings are configured with [`LengthDelimitedCodec::builder`].
+///
+/// A frame whose length exceeds the maximum is an `InvalidData` error when
+/// decoding, before any of its payload is buffered, so a peer can't make the
+/// decoder allocate more than the maximum by announcing a huge frame. When
+/// encoding, it is an `InvalidInput` error, as is a frame whose length
+/// doesn't fit the length field.
+///
+/// [`LengthDelimitedCodec::builder`]: #method.builder
+#[derive(Debug, Clone)]
+pub struct LengthDelimitedCodec {
+    builder: LengthDelimitedBuilder,
+}
+
+/// A builder for a [`LengthDelimitedCodec`] with a custom framing.
+///
+/// [`LengthDelimitedCodec`]: struct.LengthDelimitedCodec.html
+#[derive(Debug, Clone)]
+pub struct LengthDelimitedBuilder {
+    length_field_length: usize,
+    max_frame_length: usize,
+    length_adjustment: i64,
+}
+
+impl LengthDelimitedCodec {
+    /// Creates a codec with the default framing.
+    pub fn new() -> LengthDelimitedCodec {
+        LengthDelimitedBuilder::new().create()
+    }
+
+    /// Creates a builder for a codec with a custom framing.
+    pub fn builder() -> LengthDelimitedBuilder {
+        LengthDelimitedBuilder::new()
+    }
+
+    /// Returns the maximum length of a frame's payload.
+    pub fn max_frame_length(&self) -> usize {
+        self.builder.max_frame_length
+    }
+
+    /// Sets the maximum length of a frame's payload.
+    pub fn set_max_frame_length(&mut self, max: usize) {
+        self.builder.max_frame_length = max;
+    }
+}
+
+impl Default for LengthDelimitedCodec {
+    fn default() -> LengthDelimitedCodec {
+        LengthDelimitedCodec::new()
+    }
+}
+
+impl LengthDelimitedBuilder {
+    /// Creates a builder for the default framing.
+    pub fn new() -> LengthDelimitedBuilder {
+        LengthDelimitedBuilder {
+            length_field_length: 4,
+            max_frame_length: 8 * 1024 * 1024,
+            length_adjustment: 0,
+        }
+    }
+
+    /// Sets the width of the length field in bytes.
+    ///
+    /// The default is 4.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` isn't 1, 2, 4 or 8.
+    pub fn length_field_length(&mut self, len: usize) -> &mut Self {
+        assert!(len == 1 || len == 2 || len == 4 || len == 8,
+                "the length field must be 1, 2, 4 or 8 bytes wide");
+        self.length_field_length = len;
+        self
+    }
+
+    /// Sets the maximum length of a frame's payload.
+    ///
+    /// The default is 8 MiB.
+    pub fn max_frame_length(&mut self, max: usize) -> &mut Self {
+        self.max_frame_length = max;
+        self
+    }
+
+    /// Sets the amount added to the value of the length field to get the
+    /// length of the payload.
+    ///
+    /// This is for protocols whose length field doesn't hold the length of
+    /// the payload alone; for example, if it includes the length field
+    /// itself, the adjustment is minus the width of the field. The default is
+    /// 0.
+    pub fn length_adjustment(&mut self, adjustment: i64) -> &mut Self {
+        self.length_adjustment = adjustment;
+        self
+    }
+
+    /// Creates a codec with the framing configured by this builder.
+    pub fn create(&self) -> LengthDelimitedCodec {
+        LengthDelimitedCodec { builder: self.clone() }
+    }
+}
+
+impl Default for LengthDelimitedBuilder {
+    fn default() -> LengthDelimitedBuilder {
+        LengthDelimitedBuilder::new()
+    }
+}
+
+impl Decoder for LengthDelimitedCodec {
+    type Item = Vec<u8>;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut Vec<u8>) -> Result<Option<Vec<u8>>, io::Error> {
+        let width = self.builder.length_field_length;
+        if src.len() < width {
+            return Ok(None)
+        }
+        let field = src[..width].iter().fold(0, |n, &b| (n << 8) | b as u64);
+        let adjustment = self.builder.length_adjustment;
+        let len = if adjustment >= 0 {
+            field.checked_add(adjustment as u64)
+        } else {
+            field.checked_sub(adjustment.wrapping_neg() as u64)
+        };
+        let len = match len {
+            Some(len) if len <= self.builder.max_frame_length as u64 => len as usize,
+            Some(_) => {
+                return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                          "frame exceeds the maximum length"))
+            }
+            None => {
+                return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                          "invalid frame length"))
+            }
+        };
+        if src.len() - width < len {
+            return Ok(None)
+        }
+        let frame = src[width..width + len].to_vec();
+        src.drain(..width + len);
+        Ok(Some(frame))
+    }
+}
+
+impl Encoder for LengthDelimitedCodec {
+    type SinkItem = Vec<u8>;
+    type SinkError = io::Error;
+
+    fn encode(&mut self, item: Vec<u8>, dst: &mut Vec<u8>) -> Result<(), io::Error> {
+        if item.len() > self.builder.max_frame_length {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                      "frame exceeds the maximum length"))
+        }
+        let width = self.builder.length_field_length;
+        let adjustment = self.builder.length_adjustment;
+        let len = item.len() as u64;
+        let field = if adjustment >= 0 {
+            len.checked_sub(adjustment as u64)
+        } else {
+            len.checked_add(adjustment.wrapping_neg() as u64)
+        };
+        let field = match field {
+            Some(field) if width == 8 || field >> (8 * width) == 0 => field,
+            _ => {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                          "frame length doesn't fit the length field"))
+            }
+        };
+        for i in (0..width).rev() {
+            dst.push((field >> (8 * i)) as u8);
+        }
+        dst.extend_from_slice(&item);
+        Ok(())
+    }
+}
@@ -0,0 +1,224 @@
+//! Framing of byte streams into streams and sinks of messages.
+//!
+//! A codec splits the bytes read from an `AsyncRead` into frames by
+//! implementing [`Decoder`], and turns frames into bytes to write to an
+//! `AsyncWrite` by implementing [`Encoder`]. [`Framed`] does the reading,
+//! writing and buffering around a codec, so a protocol only has to describe
+//! its framing. [`LengthDelimitedCodec`] implements the common framing of
+//! frames prefixed with their length.
+//!
+//! [`Decoder`]: trait.Decoder.html
+//! [`Encoder`]: trait.Encoder.html
+//! [`Framed`]: struct.Framed.html
+//! [`LengthDelimitedCodec`]: struct.LengthDelimitedCodec.html
+
+use std::io;
+use std::vec::Vec;
+
+use {Async, Poll, task};
+use futures_core::Stream;
+use futures_sink::Sink;
+
+use futures_io::{AsyncRead, AsyncWrite};
+
+pub use self::length_delimited::{LengthDelimitedBuilder, LengthDelimitedCodec};
+
+mod length_delimited;
+
+// The number of bytes read from the underlying reader at a time.
+const READ_CHUNK: usize = 8 * 1024;
+
+// The number of encoded bytes buffered before `poll_ready` waits for them to
+// be written.
+const WRITE_HIGH_WATER: usize = 8 * 1024;
+
+/// Decodes frames from a buffer of bytes read from a stream.
+pub trait Decoder {
+    /// The type of the decoded frames.
+    type Item;
+
+    /// The type of decoding errors, which IO errors of the underlying reader
+    /// are converted into.
+    type Error: From<io::Error>;
+
+    /// Attempts to decode a frame from the start of `src`.
+    ///
+    /// If `src` holds a complete frame, its bytes are removed from the front
+    /// of `src` and the frame is returned. Otherwise `Ok(None)` is returned,
+    /// and the call is repeated once more bytes have been read. Bytes which
+    /// aren't part of a returned frame must be left in `src`.
+    fn decode(&mut self, src: &mut Vec<u8>) -> Result<Option<Self::Item>, Self::Error>;
+
+    /// Attempts to decode a frame once the underlying reader is at EOF,
+    /// so no more bytes will be added to `src`.
+    ///
+    /// This is called until it returns `Ok(None)`. If bytes are left in
+    /// `src` then, the stream fails with an `UnexpectedEof` error. The
+    /// default implementation calls `decode`; codecs whose last frame is
+    /// terminated by the end of the stream override it.
+    fn decode_eof(&mut self, src: &mut Vec<u8>) -> Result<Option<Self::Item>, Self::Error> {
+        self.decode(src)
+    }
+}
+
+/// Encodes frames into bytes to write to a stream.
+pub trait Encoder {
+    /// The type of the frames to encode.
+    type SinkItem;
+
+    /// The type of encoding errors, which IO errors of the underlying writer
+    /// are converted into.
+    type SinkError: From<io::Error>;
+
+    /// Encodes `item`, appending its bytes to `dst`.
+    fn encode(&mut self, item: Self::SinkItem, dst: &mut Vec<u8>)
+        -> Result<(), Self::SinkError>;
+}
+
+/// A stream of the frames decoded from an IO object, which is also a sink of
+/// frames encoded to it.
+///
+/// `Framed` is a `Stream` when `T` is an `AsyncRead` and `C` a `Decoder`,
+/// and a `Sink` when `T` is an `AsyncWrite` and `C` an `Encoder`. An object
+/// which is both can be split into its halves with `StreamExt::split`.
+///
+/// Encoded bytes are buffered, and sending waits for the buffer to be
+/// written once it holds more than 8 KiB; flushing the sink writes out the
+/// buffer and then flushes the underlying writer.
+#[derive(Debug)]
+pub struct Framed<T, C> {
+    inner: T,
+    codec: C,
+    read_buf: Vec<u8>,
+    eof: bool,
+    write_buf: Vec<u8>,
+}
+
+impl<T, C> Framed<T, C> {
+    /// Creates a new `Framed` which decodes and encodes the frames of `inner`
+    /// with `codec`.
+    pub fn new(inner: T, codec: C) -> Framed<T, C> {
+        Framed {
+            inner,
+            codec,
+            read_buf: Vec::new(),
+            eof: false,
+            write_buf: Vec::new(),
+        }
+    }
+
+    /// Returns a reference to the underlying IO object.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying IO object.
+    ///
+    /// Note that reading from or writing to it directly corrupts the framing.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Returns a reference to the codec.
+    pub fn codec(&self) -> &C {
+        &self.codec
+    }
+
+    /// Returns a mutable reference to the codec.
+    pub fn codec_mut(&mut self) -> &mut C {
+        &mut self.codec
+    }
+
+    /// Consumes this `Framed`, returning the underlying IO object.
+    ///
+    /// Any buffered bytes which haven't been decoded or written yet are lost.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T, C> Stream for Framed<T, C>
+    where T: AsyncRead,
+          C: Decoder,
+{
+    type Item = C::Item;
+    type Error = C::Error;
+
+    fn poll_next(&mut self, cx: &mut task::Context) -> Poll<Option<C::Item>, C::Error> {
+        loop {
+            if self.eof {
+                if let Some(item) = self.codec.decode_eof(&mut self.read_buf)? {
+                    return Ok(Async::Ready(Some(item)))
+                }
+                if self.read_buf.is_empty() {
+                    return Ok(Async::Ready(None))
+                }
+                self.read_buf.clear();
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof,
+                                          "stream ended in the middle of a frame").into())
+            }
+
+            if let Some(item) = self.codec.decode(&mut self.read_buf)? {
+                return Ok(Async::Ready(Some(item)))
+            }
+
+            let len = self.read_buf.len();
+            self.read_buf.resize(len + READ_CHUNK, 0);
+            let res = self.inner.poll_read(cx, &mut self.read_buf[len..]);
+            let n = match res {
+                Ok(Async::Ready(n)) => n,
+                _ => 0,
+            };
+            self.read_buf.truncate(len + n);
+            match res {
+                Ok(Async::Ready(0)) => self.eof = true,
+                Ok(Async::Ready(_)) => {}
+                Ok(Async::Pending) => return Ok(Async::Pending),
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+}
+
+impl<T: AsyncWrite, C> Framed<T, C> {
+    fn poll_write_buf(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        while !self.write_buf.is_empty() {
+            let n = try_ready!(self.inner.poll_write(cx, &self.write_buf));
+            if n == 0 {
+                return Err(io::Error::new(io::ErrorKind::WriteZero,
+                                          "failed to write encoded frames"))
+            }
+            self.write_buf.drain(..n);
+        }
+        Ok(Async::Ready(()))
+    }
+}
+
+impl<T, C> Sink for Framed<T, C>
+    where T: AsyncWrite,
+          C: Encoder,
+{
+    type SinkItem = C::SinkItem;
+    type SinkError = C::SinkError;
+
+    fn poll_ready(&mut self, cx: &mut task::Context) -> Poll<(), C::SinkError> {
+        if self.write_buf.len() >= WRITE_HIGH_WATER {
+            try_ready!(self.poll_write_buf(cx));
+        }
+        Ok(Async::Ready(()))
+    }
+
+    fn start_send(&mut self, item: C::SinkItem) -> Result<(), C::SinkError> {
+        self.codec.encode(item, &mut self.write_buf)
+    }
+
+    fn poll_flush(&mut self, cx: &mut task::Context) -> Poll<(), C::SinkError> {
+        try_ready!(self.poll_write_buf(cx));
+        Ok(self.inner.poll_flush(cx)?)
+    }
+
+    fn poll_close(&mut self, cx: &mut task::Context) -> Poll<(), C::SinkError> {
+        try_ready!(self.poll_write_buf(cx));
+        Ok(self.inner.poll_close(cx)?)
+    }
+}
@@ -0,0 +1,78 @@
+use std::io;
+
+use {Async, Poll, task};
+
+use futures_io::{AsyncWrite, IoVec};
+
+/// A writer which reports how many bytes have been flushed every time a
+/// flush of the underlying writer completes.
+///
+/// Created by the [`ack_on_flush`] method.
+///
+/// [`ack_on_flush`]: trait.AsyncWriteExt.html#method.ack_on_flush
+#[derive(Debug)]
+pub struct AckOnFlush<W, A> {
+    inner: W,
+    ack: A,
+    written: u64,
+}
+
+pub fn ack_on_flush<W, A>(inner: W, ack: A) -> AckOnFlush<W, A>
+    where W: AsyncWrite,
+          A: FnMut(u64),
+{
+    AckOnFlush { inner, ack, written: 0 }
+}
+
+impl<W, A> AckOnFlush<W, A> {
+    /// Returns the number of bytes written so far, flushed or not.
+    pub fn written(&self) -> u64 {
+        self.written
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes this `AckOnFlush`, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W, A> AsyncWrite for AckOnFlush<W, A>
+    where W: AsyncWrite,
+          A: FnMut(u64),
+{
+    fn poll_write(&mut self, cx: &mut task::Context, buf: &[u8])
+        -> Poll<usize, io::Error>
+    {
+        let n = try_ready!(self.inner.poll_write(cx, buf));
+        self.written += n as u64;
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_vectored_write(&mut self, cx: &mut task::Context, vec: &[&IoVec])
+        -> Poll<usize, io::Error>
+    {
+        let n = try_ready!(self.inner.poll_vectored_write(cx, vec));
+        self.written += n as u64;
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_flush(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        try_ready!(self.inner.poll_flush(cx));
+        (self.ack)(self.written);
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        self.inner.poll_close(cx)
+    }
+}
@@ -9,16 +9,19 @@ use std::vec::Vec;
 /// to be used in contexts which expect an `AsyncRead` or `AsyncWrite`.
 ///
 /// If these types issue an error with the kind `io::ErrorKind::WouldBlock`,
-/// it is expected that they will notify the current task on readiness.
-/// Synchronous `std` types should not issue errors of this kind and
-/// are safe to use in this context. However, using these types with
-/// `AllowStdIo` will cause the event loop to block, so they should be used
-/// with care.
+/// it is turned into `Async::Pending`, and since a `std` type has no way of
+/// notifying the current task on readiness, the task is woken right away to
+/// try again. Synchronous `std` types such as `std::fs::File` don't issue
+/// errors of this kind and are safe to use in this context. However, every
+/// read or write then blocks the thread which polls it, and with it the
+/// executor running on that thread, until the operation completes, so these
+/// types should be used with care, such as on a thread pool where blocking
+/// is acceptable.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct AllowStdIo<T>(T);
 
 macro_rules! try_with_interrupt {
-    ($e:expr) => {
+    ($cx:expr, $e:expr) => {
         loop {
             match $e {
                 Ok(e) => {
@@ -27,6 +30,10 @@ macro_rules! try_with_interrupt {
                 Err(ref e) if e.kind() == ::std::io::ErrorKind::Interrupted => {
                     continue;
                 }
+                Err(ref e) if e.kind() == ::std::io::ErrorKind::WouldBlock => {
+                    $cx.waker().wake();
+                    return Ok(Async::Pending);
+                }
                 Err(e) => {
                     return Err(e);
                 }
@@ -73,14 +80,14 @@ impl<T> io::Write for AllowStdIo<T> where T: io::Write {
 }
 
 impl<T> AsyncWrite for AllowStdIo<T> where T: io::Write {
-    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8])
+    fn poll_write(&mut self, cx: &mut task::Context, buf: &[u8])
         -> Poll<usize, io::Error>
     {
-        Ok(Async::Ready(try_with_interrupt!(io::Write::write(&mut self.0, buf))))
+        Ok(Async::Ready(try_with_interrupt!(cx, io::Write::write(&mut self.0, buf))))
     }
 
-    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
-        Ok(Async::Ready(try_with_interrupt!(io::Write::flush(self))))
+    fn poll_flush(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(try_with_interrupt!(cx, io::Write::flush(self))))
     }
 
     fn poll_close(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
@@ -106,9 +113,9 @@ impl<T> io::Read for AllowStdIo<T> where T: io::Read {
 }
 
 impl<T> AsyncRead for AllowStdIo<T> where T: io::Read {
-    fn poll_read(&mut self, _: &mut task::Context, buf: &mut [u8])
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
         -> Poll<usize, io::Error>
     {
-        Ok(Async::Ready(try_with_interrupt!(io::Read::read(&mut self.0, buf))))
+        Ok(Async::Ready(try_with_interrupt!(cx, io::Read::read(&mut self.0, buf))))
     }
 }
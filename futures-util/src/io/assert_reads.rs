@@ -0,0 +1,64 @@
+use std::io;
+
+use {Async, Poll, task};
+
+use futures_io::{AsyncRead, Initializer, IoVec};
+
+/// A reader which checks, in debug builds, that the underlying reader never
+/// reports more bytes than fit in the buffer it was given.
+///
+/// Created by the [`assert_nondecreasing_reads`] method.
+///
+/// [`assert_nondecreasing_reads`]: trait.AsyncReadExt.html#method.assert_nondecreasing_reads
+#[derive(Debug)]
+pub struct AssertNondecreasingReads<R> {
+    inner: R,
+}
+
+pub fn assert_nondecreasing_reads<R: AsyncRead>(inner: R) -> AssertNondecreasingReads<R> {
+    AssertNondecreasingReads { inner }
+}
+
+impl<R> AssertNondecreasingReads<R> {
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this `AssertNondecreasingReads`, returning the underlying
+    /// reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for AssertNondecreasingReads<R> {
+    unsafe fn initializer(&self) -> Initializer {
+        self.inner.initializer()
+    }
+
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        let n = try_ready!(self.inner.poll_read(cx, buf));
+        debug_assert!(n <= buf.len(),
+                      "reader reported {} bytes read into a buffer of {}", n, buf.len());
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_vectored_read(&mut self, cx: &mut task::Context, vec: &mut [&mut IoVec])
+        -> Poll<usize, io::Error>
+    {
+        let n = try_ready!(self.inner.poll_vectored_read(cx, vec));
+        if cfg!(debug_assertions) {
+            let len: usize = vec.iter().map(|v| v.len()).sum();
+            assert!(n <= len, "reader reported {} bytes read into buffers of {}", n, len);
+        }
+        Ok(Async::Ready(n))
+    }
+}
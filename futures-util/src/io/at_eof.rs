@@ -0,0 +1,32 @@
+use std::io;
+
+use {Async, Future, Poll, task};
+
+use futures_io::AsyncBufRead;
+
+/// A future which resolves to whether a buffered reader is at the end of its
+/// stream.
+///
+/// This is created by the [`at_eof`] method.
+///
+/// [`at_eof`]: trait.AsyncBufReadExt.html#method.at_eof
+#[derive(Debug)]
+pub struct AtEof<'a, R: ?Sized + 'a> {
+    reader: &'a mut R,
+}
+
+pub fn at_eof<'a, R>(reader: &'a mut R) -> AtEof<'a, R>
+    where R: ?Sized + AsyncBufRead,
+{
+    AtEof { reader }
+}
+
+impl<'a, R: ?Sized + AsyncBufRead> Future for AtEof<'a, R> {
+    type Item = bool;
+    type Error = io::Error;
+
+    fn poll(&mut self, cx: &mut task::Context) -> Poll<bool, io::Error> {
+        let buf = try_ready!(self.reader.poll_fill_buf(cx));
+        Ok(Async::Ready(buf.is_empty()))
+    }
+}
@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+use std::io;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::vec::Vec;
+
+use {Async, Future, Poll, task};
+use futures_core::task::Waker;
+
+use futures_io::AsyncWrite;
+
+/// A handle to a writer shared between tasks which writes whole frames
+/// without interleaving them.
+///
+/// Each frame sent with [`send_frame`] is written while holding a lock on the
+/// writer which is only released once the entire frame has been accepted,
+/// even if the underlying writer needs many partial writes to do so. Frames
+/// from different handles may therefore be written in any order, but the
+/// bytes of two frames are never mixed.
+///
+/// The shared writer is flushed or closed between frames with [`flush`] and
+/// [`close`].
+///
+/// Handles are created with `AtomicFrameWriter::new` and `clone`.
+///
+/// [`send_frame`]: #method.send_frame
+/// [`flush`]: #method.flush
+/// [`close`]: #method.close
+#[derive(Debug)]
+pub struct AtomicFrameWriter<W> {
+    shared: Arc<Mutex<Shared<W>>>,
+}
+
+#[derive(Debug)]
+struct Shared<W> {
+    writer: W,
+    locked: bool,
+    // One slot per future waiting for the lock, keyed by its id, so that a
+    // future polled again while it waits replaces its waker.
+    waiters: HashMap<usize, Waker>,
+    next_id: usize,
+}
+
+impl<W> Clone for AtomicFrameWriter<W> {
+    fn clone(&self) -> AtomicFrameWriter<W> {
+        AtomicFrameWriter { shared: self.shared.clone() }
+    }
+}
+
+impl<W: AsyncWrite> AtomicFrameWriter<W> {
+    /// Creates a new handle to `writer`.
+    pub fn new(writer: W) -> AtomicFrameWriter<W> {
+        AtomicFrameWriter {
+            shared: Arc::new(Mutex::new(Shared {
+                writer,
+                locked: false,
+                waiters: HashMap::new(),
+                next_id: 0,
+            })),
+        }
+    }
+
+    /// Creates a future which writes all of `frame` to the shared writer
+    /// before any other frame may be started.
+    ///
+    /// If the future is dropped after it has started writing, the frame may
+    /// be left partially written and the lock is released.
+    pub fn send_frame(&self, frame: Vec<u8>) -> SendFrame<W> {
+        SendFrame {
+            lock: FrameLock::new(&self.shared),
+            frame,
+            pos: 0,
+        }
+    }
+
+    /// Creates a future which flushes the shared writer between two frames.
+    pub fn flush(&self) -> FlushFrames<W> {
+        FlushFrames { lock: FrameLock::new(&self.shared) }
+    }
+
+    /// Creates a future which closes the shared writer between two frames.
+    ///
+    /// Frames sent after the writer has been closed fail as the writer
+    /// does.
+    pub fn close(&self) -> CloseFrames<W> {
+        CloseFrames { lock: FrameLock::new(&self.shared) }
+    }
+
+    /// Consumes this handle, returning the underlying writer if no other
+    /// handles or pending frames remain.
+    pub fn into_inner(self) -> Result<W, AtomicFrameWriter<W>> {
+        match Arc::try_unwrap(self.shared) {
+            Ok(shared) => Ok(shared.into_inner().unwrap_or_else(|e| e.into_inner()).writer),
+            Err(shared) => Err(AtomicFrameWriter { shared }),
+        }
+    }
+}
+
+fn lock<'a, W>(shared: &'a Mutex<Shared<W>>) -> MutexGuard<'a, Shared<W>> {
+    shared.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+// The frame lock as held by one of the futures of this module, which is
+// released when it completes or is dropped.
+#[derive(Debug)]
+struct FrameLock<W> {
+    shared: Arc<Mutex<Shared<W>>>,
+    id: usize,
+    locked: bool,
+}
+
+impl<W> FrameLock<W> {
+    fn new(shared: &Arc<Mutex<Shared<W>>>) -> FrameLock<W> {
+        let id = {
+            let mut guard = lock(shared);
+            let id = guard.next_id;
+            guard.next_id = id.wrapping_add(1);
+            id
+        };
+        FrameLock { shared: shared.clone(), id, locked: false }
+    }
+
+    fn poll_lock(&mut self, cx: &mut task::Context) -> Async<()> {
+        if !self.locked {
+            let mut shared = lock(&self.shared);
+            if shared.locked {
+                shared.waiters.insert(self.id, cx.waker().clone());
+                return Async::Pending
+            }
+            shared.waiters.remove(&self.id);
+            shared.locked = true;
+            self.locked = true;
+        }
+        Async::Ready(())
+    }
+
+    fn unlock(&mut self) {
+        if !self.locked {
+            return
+        }
+        self.locked = false;
+        let mut shared = lock(&self.shared);
+        shared.locked = false;
+        // Every waiter retries, as only one of them gets the lock anyway.
+        for (_, waker) in shared.waiters.drain() {
+            waker.wake();
+        }
+    }
+}
+
+impl<W> Drop for FrameLock<W> {
+    fn drop(&mut self) {
+        lock(&self.shared).waiters.remove(&self.id);
+        self.unlock();
+    }
+}
+
+/// A future which writes one frame through an `AtomicFrameWriter`.
+///
+/// Created by the [`send_frame`] method.
+///
+/// [`send_frame`]: struct.AtomicFrameWriter.html#method.send_frame
+#[derive(Debug)]
+pub struct SendFrame<W> {
+    lock: FrameLock<W>,
+    frame: Vec<u8>,
+    pos: usize,
+}
+
+impl<W: AsyncWrite> Future for SendFrame<W> {
+    type Item = ();
+    type Error = io::Error;
+
+    fn poll(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        if let Async::Pending = self.lock.poll_lock(cx) {
+            return Ok(Async::Pending)
+        }
+
+        while self.pos < self.frame.len() {
+            let res = lock(&self.lock.shared).writer.poll_write(cx, &self.frame[self.pos..]);
+            match res {
+                Ok(Async::Ready(0)) => {
+                    self.lock.unlock();
+                    return Err(io::Error::new(io::ErrorKind::WriteZero,
+                                              "failed to write whole frame"))
+                }
+                Ok(Async::Ready(n)) => self.pos += n,
+                Ok(Async::Pending) => return Ok(Async::Pending),
+                Err(e) => {
+                    self.lock.unlock();
+                    return Err(e)
+                }
+            }
+        }
+        self.lock.unlock();
+        Ok(Async::Ready(()))
+    }
+}
+
+/// A future which flushes the writer shared by an `AtomicFrameWriter`.
+///
+/// Created by the [`flush`] method.
+///
+/// [`flush`]: struct.AtomicFrameWriter.html#method.flush
+#[derive(Debug)]
+pub struct FlushFrames<W> {
+    lock: FrameLock<W>,
+}
+
+impl<W: AsyncWrite> Future for FlushFrames<W> {
+    type Item = ();
+    type Error = io::Error;
+
+    fn poll(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        if let Async::Pending = self.lock.poll_lock(cx) {
+            return Ok(Async::Pending)
+        }
+        let res = lock(&self.lock.shared).writer.poll_flush(cx);
+        if let Ok(Async::Pending) = res {
+            return res
+        }
+        self.lock.unlock();
+        res
+    }
+}
+
+/// A future which closes the writer shared by an `AtomicFrameWriter`.
+///
+/// Created by the [`close`] method.
+///
+/// [`close`]: struct.AtomicFrameWriter.html#method.close
+#[derive(Debug)]
+pub struct CloseFrames<W> {
+    lock: FrameLock<W>,
+}
+
+impl<W: AsyncWrite> Future for CloseFrames<W> {
+    type Item = ();
+    type Error = io::Error;
+
+    fn poll(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        if let Async::Pending = self.lock.poll_lock(cx) {
+            return Ok(Async::Pending)
+        }
+        let res = lock(&self.lock.shared).writer.poll_close(cx);
+        if let Ok(Async::Pending) = res {
+            return res
+        }
+        self.lock.unlock();
+        res
+    }
+}
@@ -0,0 +1,61 @@
+use std::cmp;
+use std::io;
+use std::vec::{self, Vec};
+
+use {Async, Poll, task};
+
+use futures_io::AsyncRead;
+
+/// An `AsyncRead` which reads a bounded number of bytes from each of a list
+/// of sources in turn.
+///
+/// Created by the [`bounded_concat`] function.
+///
+/// [`bounded_concat`]: fn.bounded_concat.html
+#[derive(Debug)]
+pub struct BoundedConcat<R> {
+    current: Option<(R, u64)>,
+    rest: vec::IntoIter<(R, u64)>,
+}
+
+/// Creates an `AsyncRead` which reads each source in `sources` up to its
+/// paired byte limit before moving on to the next one.
+///
+/// A source which reaches EOF before its limit is left early. The returned
+/// reader reports EOF once every source is done, and sources are dropped as
+/// soon as they have been read.
+pub fn bounded_concat<R>(sources: Vec<(R, u64)>) -> BoundedConcat<R>
+    where R: AsyncRead,
+{
+    let mut rest = sources.into_iter();
+    BoundedConcat {
+        current: rest.next(),
+        rest,
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for BoundedConcat<R> {
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        if buf.is_empty() {
+            return Ok(Async::Ready(0))
+        }
+        loop {
+            let n = match self.current {
+                Some((ref mut reader, ref mut left)) if *left > 0 => {
+                    let len = cmp::min(buf.len() as u64, *left) as usize;
+                    let n = try_ready!(reader.poll_read(cx, &mut buf[..len]));
+                    *left -= n as u64;
+                    n
+                }
+                Some(_) => 0,
+                None => return Ok(Async::Ready(0)),
+            };
+            if n > 0 {
+                return Ok(Async::Ready(n))
+            }
+            self.current = self.rest.next();
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use std::cmp;
+use std::io;
+
+use {Async, Poll, task};
+
+use futures_io::AsyncWrite;
+
+/// A writer which flushes the underlying writer before more than a maximum
+/// number of bytes have been written to it since the last flush.
+///
+/// Created by the [`bounded_unflushed`] method.
+///
+/// [`bounded_unflushed`]: trait.AsyncWriteExt.html#method.bounded_unflushed
+#[derive(Debug)]
+pub struct BoundedUnflushed<W> {
+    inner: W,
+    max: usize,
+    unflushed: usize,
+}
+
+pub fn bounded_unflushed<W: AsyncWrite>(inner: W, max: usize) -> BoundedUnflushed<W> {
+    assert!(max > 0, "bounded_unflushed needs a limit of at least one byte");
+    BoundedUnflushed { inner, max, unflushed: 0 }
+}
+
+impl<W> BoundedUnflushed<W> {
+    /// Returns the number of bytes written since the last completed flush.
+    pub fn unflushed(&self) -> usize {
+        self.unflushed
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// Note that bytes written to the underlying writer directly are not
+    /// counted.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes this `BoundedUnflushed`, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: AsyncWrite> AsyncWrite for BoundedUnflushed<W> {
+    fn poll_write(&mut self, cx: &mut task::Context, buf: &[u8])
+        -> Poll<usize, io::Error>
+    {
+        if buf.is_empty() {
+            return Ok(Async::Ready(0))
+        }
+        if self.unflushed >= self.max {
+            try_ready!(self.poll_flush(cx));
+        }
+        let len = cmp::min(buf.len(), self.max - self.unflushed);
+        let n = try_ready!(self.inner.poll_write(cx, &buf[..len]));
+        self.unflushed += n;
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_flush(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        try_ready!(self.inner.poll_flush(cx));
+        self.unflushed = 0;
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        self.inner.poll_close(cx)
+    }
+}
@@ -0,0 +1,100 @@
+use std::io;
+
+use {Async, Poll, task};
+
+use futures_io::{AsyncWrite, IoVec};
+
+/// A writer which forces its task to yield after a fixed number of writes.
+///
+/// Created by the [`budget_write`] method.
+///
+/// [`budget_write`]: trait.AsyncWriteExt.html#method.budget_write
+#[derive(Debug)]
+pub struct BudgetWrite<W> {
+    inner: W,
+    budget: usize,
+    remaining: usize,
+    yields: u64,
+}
+
+pub fn budget_write<W: AsyncWrite>(inner: W, budget: usize) -> BudgetWrite<W> {
+    assert!(budget > 0, "the write budget must be at least one");
+    BudgetWrite {
+        inner,
+        budget,
+        remaining: budget,
+        yields: 0,
+    }
+}
+
+impl<W> BudgetWrite<W> {
+    /// Returns the number of times a write has yielded because the budget
+    /// was used up.
+    pub fn yields(&self) -> u64 {
+        self.yields
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes this `BudgetWrite`, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Returns whether the budget is used up, in which case it is reset and
+    /// the current task is woken up again right away.
+    fn exhausted(&mut self, cx: &mut task::Context) -> bool {
+        if self.remaining > 0 {
+            return false
+        }
+        self.remaining = self.budget;
+        self.yields += 1;
+        cx.waker().wake();
+        true
+    }
+
+    fn spend<T>(&mut self, res: Poll<usize, T>) -> Poll<usize, T> {
+        if let Ok(Async::Ready(n)) = res {
+            if n > 0 {
+                self.remaining -= 1;
+            }
+        }
+        res
+    }
+}
+
+impl<W: AsyncWrite> AsyncWrite for BudgetWrite<W> {
+    fn poll_write(&mut self, cx: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        if self.exhausted(cx) {
+            return Ok(Async::Pending)
+        }
+        let res = self.inner.poll_write(cx, buf);
+        self.spend(res)
+    }
+
+    fn poll_vectored_write(&mut self, cx: &mut task::Context, vec: &[&IoVec])
+        -> Poll<usize, io::Error>
+    {
+        if self.exhausted(cx) {
+            return Ok(Async::Pending)
+        }
+        let res = self.inner.poll_vectored_write(cx, vec);
+        self.spend(res)
+    }
+
+    fn poll_flush(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        self.inner.poll_flush(cx)
+    }
+
+    fn poll_close(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        self.inner.poll_close(cx)
+    }
+}
@@ -0,0 +1,52 @@
+use std::io;
+
+use {Async, Poll, task};
+use bytes::{Buf, BufMut};
+
+use futures_io::{AsyncRead, AsyncWrite, IoVec};
+
+// The maximum number of segments of a `Buf` written by one vectored write.
+const MAX_SEGMENTS: usize = 64;
+
+pub fn poll_read_buf<R, B>(reader: &mut R, cx: &mut task::Context, buf: &mut B)
+    -> Poll<usize, io::Error>
+    where R: AsyncRead + ?Sized,
+          B: BufMut,
+{
+    if !buf.has_remaining_mut() {
+        return Ok(Async::Ready(0))
+    }
+    let n = unsafe {
+        let dst = buf.bytes_mut();
+        reader.initializer().initialize(dst);
+        let n = try_ready!(reader.poll_read(cx, dst));
+        assert!(n <= dst.len(), "read more bytes than the buffer holds");
+        n
+    };
+    unsafe {
+        buf.advance_mut(n);
+    }
+    Ok(Async::Ready(n))
+}
+
+pub fn poll_write_buf<W, B>(writer: &mut W, cx: &mut task::Context, buf: &mut B)
+    -> Poll<usize, io::Error>
+    where W: AsyncWrite + ?Sized,
+          B: Buf,
+{
+    if !buf.has_remaining() {
+        return Ok(Async::Ready(0))
+    }
+    let n = {
+        static DUMMY: &[u8] = &[0];
+        let mut vec = [<&IoVec>::from(DUMMY); MAX_SEGMENTS];
+        let segments = buf.bytes_vec(&mut vec);
+        if segments > 1 {
+            try_ready!(writer.poll_vectored_write(cx, &vec[..segments]))
+        } else {
+            try_ready!(writer.poll_write(cx, buf.bytes()))
+        }
+    };
+    buf.advance(n);
+    Ok(Async::Ready(n))
+}
@@ -0,0 +1,114 @@
+use std::io;
+
+use {Async, Poll, task};
+
+use futures_io::{AsyncBufRead, AsyncRead, Initializer, IoVec};
+
+/// A reader which yields everything from one reader and then everything
+/// from another.
+///
+/// Created by the [`chain`] method.
+///
+/// [`chain`]: trait.AsyncReadExt.html#method.chain
+#[derive(Debug)]
+pub struct Chain<T, U> {
+    first: T,
+    second: U,
+    done_first: bool,
+}
+
+pub fn chain<T, U>(first: T, second: U) -> Chain<T, U>
+    where T: AsyncRead,
+          U: AsyncRead,
+{
+    Chain {
+        first,
+        second,
+        done_first: false,
+    }
+}
+
+impl<T, U> Chain<T, U> {
+    /// Returns references to the underlying readers.
+    pub fn get_ref(&self) -> (&T, &U) {
+        (&self.first, &self.second)
+    }
+
+    /// Returns mutable references to the underlying readers.
+    ///
+    /// Care should be taken to avoid modifying the internal I/O state of the
+    /// underlying readers as doing so may corrupt the internal state of this
+    /// `Chain`.
+    pub fn get_mut(&mut self) -> (&mut T, &mut U) {
+        (&mut self.first, &mut self.second)
+    }
+
+    /// Consumes this `Chain`, returning the underlying readers.
+    pub fn into_inner(self) -> (T, U) {
+        (self.first, self.second)
+    }
+}
+
+impl<T, U> AsyncRead for Chain<T, U>
+    where T: AsyncRead,
+          U: AsyncRead,
+{
+    unsafe fn initializer(&self) -> Initializer {
+        let first = self.first.initializer();
+        if first.should_initialize() {
+            first
+        } else {
+            self.second.initializer()
+        }
+    }
+
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        if !self.done_first {
+            // A zero-length read into an empty buffer isn't the end of the
+            // first reader.
+            match try_ready!(self.first.poll_read(cx, buf)) {
+                0 if !buf.is_empty() => self.done_first = true,
+                n => return Ok(Async::Ready(n)),
+            }
+        }
+        self.second.poll_read(cx, buf)
+    }
+
+    fn poll_vectored_read(&mut self, cx: &mut task::Context, vec: &mut [&mut IoVec])
+        -> Poll<usize, io::Error>
+    {
+        if !self.done_first {
+            let n = try_ready!(self.first.poll_vectored_read(cx, vec));
+            if n > 0 || vec.iter().all(|v| v.is_empty()) {
+                return Ok(Async::Ready(n))
+            }
+            self.done_first = true;
+        }
+        self.second.poll_vectored_read(cx, vec)
+    }
+}
+
+impl<T, U> AsyncBufRead for Chain<T, U>
+    where T: AsyncBufRead,
+          U: AsyncBufRead,
+{
+    fn poll_fill_buf(&mut self, cx: &mut task::Context) -> Poll<&[u8], io::Error> {
+        if !self.done_first {
+            match try_ready!(self.first.poll_fill_buf(cx)) {
+                buf if buf.is_empty() => self.done_first = true,
+                buf => return Ok(Async::Ready(buf)),
+            }
+        }
+        self.second.poll_fill_buf(cx)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if self.done_first {
+            self.second.consume(amt)
+        } else {
+            self.first.consume(amt)
+        }
+    }
+}
@@ -1,38 +1,30 @@
 use std::io;
 
-use {Poll, Future, Async, task};
+use {Poll, Future, task};
 
 use futures_io::AsyncWrite;
 
 /// A future used to fully close an I/O object.
 ///
-/// Resolves to the underlying I/O object once the close operation is
-/// complete.
+/// Created by the [`close`] method.
 ///
-/// Created by the [`close`] function.
-///
-/// [`close`]: fn.close.html
+/// [`close`]: trait.AsyncWriteExt.html#method.close
 #[derive(Debug)]
-pub struct Close<A> {
-    a: Option<A>,
+pub struct Close<'a, A: ?Sized + 'a> {
+    writer: &'a mut A,
 }
 
-pub fn close<A>(a: A) -> Close<A>
-    where A: AsyncWrite,
+pub fn close<'a, A>(writer: &'a mut A) -> Close<'a, A>
+    where A: ?Sized + AsyncWrite,
 {
-    Close {
-        a: Some(a),
-    }
+    Close { writer }
 }
 
-impl<A> Future for Close<A>
-    where A: AsyncWrite,
-{
-    type Item = A;
+impl<'a, A: ?Sized + AsyncWrite> Future for Close<'a, A> {
+    type Item = ();
     type Error = io::Error;
 
-    fn poll(&mut self, cx: &mut task::Context) -> Poll<A, io::Error> {
-        try_ready!(self.a.as_mut().unwrap().poll_close(cx));
-        Ok(Async::Ready(self.a.take().unwrap()))
+    fn poll(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        self.writer.poll_close(cx)
     }
 }
@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::io;
+use std::mem;
+use std::vec::Vec;
+
+use {Async, Future, Poll, task};
+
+use futures_io::AsyncRead;
+
+/// What [`collect_pairs`] does with a key which occurs more than once.
+///
+/// [`collect_pairs`]: trait.AsyncReadExt.html#method.collect_pairs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateKeys {
+    /// The value of the last occurrence replaces the earlier ones.
+    LastWins,
+    /// A duplicate key fails the future with an `InvalidData` error.
+    Error,
+}
+
+/// A future which decodes key-value frames from a reader into a map.
+///
+/// This is created by the [`collect_pairs`] method.
+///
+/// [`collect_pairs`]: trait.AsyncReadExt.html#method.collect_pairs
+#[derive(Debug)]
+pub struct CollectPairs<R, D> {
+    inner: Option<R>,
+    decode: D,
+    duplicates: DuplicateKeys,
+    buf: Vec<u8>,
+    map: HashMap<Vec<u8>, Vec<u8>>,
+    eof: bool,
+}
+
+pub fn collect_pairs<R, D>(inner: R, decode: D, duplicates: DuplicateKeys) -> CollectPairs<R, D>
+    where R: AsyncRead,
+          D: FnMut(&mut Vec<u8>) -> Option<(Vec<u8>, Vec<u8>)>,
+{
+    CollectPairs {
+        inner: Some(inner),
+        decode,
+        duplicates,
+        buf: Vec::new(),
+        map: HashMap::new(),
+        eof: false,
+    }
+}
+
+impl<R, D> Future for CollectPairs<R, D>
+    where R: AsyncRead,
+          D: FnMut(&mut Vec<u8>) -> Option<(Vec<u8>, Vec<u8>)>,
+{
+    type Item = (R, HashMap<Vec<u8>, Vec<u8>>);
+    type Error = io::Error;
+
+    fn poll(&mut self, cx: &mut task::Context) -> Poll<Self::Item, io::Error> {
+        loop {
+            while let Some((key, value)) = (self.decode)(&mut self.buf) {
+                match self.map.entry(key) {
+                    Entry::Occupied(mut entry) => {
+                        if self.duplicates == DuplicateKeys::Error {
+                            return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                                      "duplicate key"))
+                        }
+                        entry.insert(value);
+                    }
+                    Entry::Vacant(entry) => {
+                        entry.insert(value);
+                    }
+                }
+            }
+
+            if self.eof {
+                if !self.buf.is_empty() {
+                    return Err(io::Error::new(io::ErrorKind::UnexpectedEof,
+                                              "stream ended in the middle of a frame"))
+                }
+                let inner = self.inner.take().expect("cannot poll CollectPairs twice");
+                return Ok(Async::Ready((inner, mem::replace(&mut self.map, HashMap::new()))))
+            }
+
+            let mut chunk = [0; 1024];
+            let inner = self.inner.as_mut().expect("cannot poll CollectPairs twice");
+            let n = try_ready!(inner.poll_read(cx, &mut chunk));
+            if n == 0 {
+                self.eof = true;
+            }
+            self.buf.extend_from_slice(&chunk[..n]);
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use std::cmp;
+use std::io;
+
+use {Async, Poll, task};
+
+use futures_io::{AsyncRead, Initializer};
+
+/// A reader which requires the underlying stream to be exactly a given
+/// number of bytes long.
+///
+/// Created by the [`content_length`] method.
+///
+/// [`content_length`]: trait.AsyncReadExt.html#method.content_length
+#[derive(Debug)]
+pub struct ContentLength<R> {
+    inner: R,
+    expected: u64,
+    read: u64,
+    done: bool,
+}
+
+pub fn content_length<R: AsyncRead>(inner: R, expected: u64) -> ContentLength<R> {
+    ContentLength {
+        inner,
+        expected,
+        read: 0,
+        done: false,
+    }
+}
+
+impl<R> ContentLength<R> {
+    /// Returns the number of bytes still expected.
+    pub fn remaining(&self) -> u64 {
+        self.expected - self.read
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this `ContentLength`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for ContentLength<R> {
+    unsafe fn initializer(&self) -> Initializer {
+        self.inner.initializer()
+    }
+
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        if self.done || buf.is_empty() {
+            return Ok(Async::Ready(0))
+        }
+
+        if self.read < self.expected {
+            let max = cmp::min(buf.len() as u64, self.expected - self.read) as usize;
+            let n = try_ready!(self.inner.poll_read(cx, &mut buf[..max]));
+            if n == 0 {
+                return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                          "stream is shorter than its content length"))
+            }
+            self.read += n as u64;
+            return Ok(Async::Ready(n))
+        }
+
+        // Make sure the stream really ends here.
+        let mut probe = [0];
+        if try_ready!(self.inner.poll_read(cx, &mut probe)) > 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                      "stream is longer than its content length"))
+        }
+        self.done = true;
+        Ok(Async::Ready(0))
+    }
+}
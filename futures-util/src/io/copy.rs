@@ -0,0 +1,48 @@
+use std::io;
+
+use {Async, Future, Poll, task};
+
+use futures_io::{AsyncRead, AsyncWrite};
+
+use super::copy_into::{copy_into_uninit, CopyInto};
+
+/// A future which copies all the bytes of a reader into a writer.
+///
+/// Created by the [`copy`] function.
+///
+/// [`copy`]: fn.copy.html
+#[derive(Debug)]
+pub struct Copy<R, W> {
+    inner: CopyInto<R, W>,
+}
+
+/// Creates a future which copies all the bytes read from `reader` into
+/// `writer`, and then flushes `writer`, resolving to the number of bytes
+/// copied.
+///
+/// This is the asynchronous analog to `std::io::copy`. The bytes go through
+/// an internal buffer, which is only zeroed if `reader` requires it. If
+/// `writer` accepts only part of what has been read, the rest is written
+/// before reading any more. Errors of either side fail the future.
+///
+/// Use `AsyncReadExt::copy_into` to get the reader and writer back, or pass
+/// them by mutable reference.
+pub fn copy<R, W>(reader: R, writer: W) -> Copy<R, W>
+    where R: AsyncRead,
+          W: AsyncWrite,
+{
+    Copy { inner: copy_into_uninit(reader, writer) }
+}
+
+impl<R, W> Future for Copy<R, W>
+    where R: AsyncRead,
+          W: AsyncWrite,
+{
+    type Item = u64;
+    type Error = io::Error;
+
+    fn poll(&mut self, cx: &mut task::Context) -> Poll<u64, io::Error> {
+        let (amt, _, _) = try_ready!(self.inner.poll(cx));
+        Ok(Async::Ready(amt))
+    }
+}
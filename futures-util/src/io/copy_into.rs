@@ -1,5 +1,6 @@
 use std::io;
 use std::boxed::Box;
+use std::vec::Vec;
 
 use {Future, Poll, task};
 
@@ -23,6 +24,22 @@ pub struct CopyInto<R, W> {
 }
 
 pub fn copy_into<R, W>(reader: R, writer: W) -> CopyInto<R, W> {
+    with_buf(reader, writer, Box::new([0; 2048]))
+}
+
+/// Like `copy_into`, but only zeroes the buffer if the reader's initializer
+/// asks for it.
+pub fn copy_into_uninit<R: AsyncRead, W>(reader: R, writer: W) -> CopyInto<R, W> {
+    let buf = unsafe {
+        let mut buf = Vec::with_capacity(2048);
+        buf.set_len(2048);
+        reader.initializer().initialize(&mut buf);
+        buf.into_boxed_slice()
+    };
+    with_buf(reader, writer, buf)
+}
+
+fn with_buf<R, W>(reader: R, writer: W, buf: Box<[u8]>) -> CopyInto<R, W> {
     CopyInto {
         reader: Some(reader),
         read_done: false,
@@ -30,7 +47,7 @@ pub fn copy_into<R, W>(reader: R, writer: W) -> CopyInto<R, W> {
         amt: 0,
         pos: 0,
         cap: 0,
-        buf: Box::new([0; 2048]),
+        buf,
     }
 }
 
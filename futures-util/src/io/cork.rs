@@ -0,0 +1,127 @@
+use std::io;
+use std::vec::Vec;
+
+use {Async, Poll, task};
+
+use futures_io::AsyncWrite;
+
+/// A writer which can be corked to hold back writes until it is uncorked,
+/// like `TCP_CORK`.
+pub trait AsyncCork: AsyncWrite {
+    /// Corks this writer: written bytes are held back until `uncork` is
+    /// called.
+    fn cork(&mut self);
+
+    /// Uncorks this writer, so that the bytes held back are written out, in
+    /// one go, by the next write or flush.
+    fn uncork(&mut self);
+
+    /// Returns whether this writer is corked.
+    fn is_corked(&self) -> bool;
+}
+
+/// A writer which holds back writes while it is corked.
+///
+/// While corked, writes are buffered without touching the underlying writer
+/// and `poll_flush` only flushes what was written before. Once uncorked, the
+/// buffered bytes are handed to the underlying writer in a single write,
+/// continuing with the rest if it accepts only part of them, by the next
+/// `poll_write` or `poll_flush`. `poll_close` uncorks the writer first.
+///
+/// This lets a burst of small writes be batched explicitly into one write of
+/// the underlying writer.
+#[derive(Debug)]
+pub struct CorkWriter<W> {
+    inner: W,
+    buf: Vec<u8>,
+    pos: usize,
+    corked: bool,
+}
+
+impl<W: AsyncWrite> CorkWriter<W> {
+    /// Creates a new, uncorked `CorkWriter` writing to `inner`.
+    pub fn new(inner: W) -> CorkWriter<W> {
+        CorkWriter {
+            inner,
+            buf: Vec::new(),
+            pos: 0,
+            corked: false,
+        }
+    }
+
+    fn poll_buf(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        while self.pos < self.buf.len() {
+            let n = try_ready!(self.inner.poll_write(cx, &self.buf[self.pos..]));
+            if n == 0 {
+                return Err(io::Error::new(io::ErrorKind::WriteZero,
+                                          "failed to write corked data"))
+            }
+            self.pos += n;
+        }
+        self.buf.clear();
+        self.pos = 0;
+        Ok(Async::Ready(()))
+    }
+}
+
+impl<W> CorkWriter<W> {
+    /// Returns the number of bytes held back.
+    pub fn buffered(&self) -> usize {
+        self.buf.len() - self.pos
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes this `CorkWriter`, returning the underlying writer.
+    ///
+    /// Any bytes held back are lost.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: AsyncWrite> AsyncCork for CorkWriter<W> {
+    fn cork(&mut self) {
+        self.corked = true;
+    }
+
+    fn uncork(&mut self) {
+        self.corked = false;
+    }
+
+    fn is_corked(&self) -> bool {
+        self.corked
+    }
+}
+
+impl<W: AsyncWrite> AsyncWrite for CorkWriter<W> {
+    fn poll_write(&mut self, cx: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        if self.corked {
+            self.buf.extend_from_slice(buf);
+            return Ok(Async::Ready(buf.len()))
+        }
+        try_ready!(self.poll_buf(cx));
+        self.inner.poll_write(cx, buf)
+    }
+
+    fn poll_flush(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        if !self.corked {
+            try_ready!(self.poll_buf(cx));
+        }
+        self.inner.poll_flush(cx)
+    }
+
+    fn poll_close(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        self.corked = false;
+        try_ready!(self.poll_buf(cx));
+        self.inner.poll_close(cx)
+    }
+}
@@ -0,0 +1,153 @@
+use std::io;
+
+use {Async, Poll, task};
+
+use futures_io::{AsyncBufRead, AsyncRead, AsyncWrite, Initializer, IoVec};
+
+/// A reader which counts the bytes read through it.
+///
+/// Its accessor is `byte_count` rather than `count`. Method lookup tries
+/// the by-value [`count`] before an inherent `count(&self)`, so on a
+/// `CountRead` with the prelude imported `reader.count()` would wrap the
+/// reader a second time instead of returning the number.
+///
+/// Created by the [`count`] method.
+///
+/// [`count`]: trait.AsyncReadExt.html#method.count
+#[derive(Debug)]
+pub struct CountRead<R> {
+    inner: R,
+    count: u64,
+}
+
+pub fn count_read<R: AsyncRead>(inner: R) -> CountRead<R> {
+    CountRead { inner, count: 0 }
+}
+
+impl<R> CountRead<R> {
+    /// Returns the number of bytes read so far.
+    pub fn byte_count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// Note that bytes read from the underlying reader directly are not
+    /// counted.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this `CountRead`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for CountRead<R> {
+    unsafe fn initializer(&self) -> Initializer {
+        self.inner.initializer()
+    }
+
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        let n = try_ready!(self.inner.poll_read(cx, buf));
+        self.count += n as u64;
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_vectored_read(&mut self, cx: &mut task::Context, vec: &mut [&mut IoVec])
+        -> Poll<usize, io::Error>
+    {
+        let n = try_ready!(self.inner.poll_vectored_read(cx, vec));
+        self.count += n as u64;
+        Ok(Async::Ready(n))
+    }
+}
+
+impl<R: AsyncBufRead> AsyncBufRead for CountRead<R> {
+    fn poll_fill_buf(&mut self, cx: &mut task::Context) -> Poll<&[u8], io::Error> {
+        self.inner.poll_fill_buf(cx)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt);
+        self.count += amt as u64;
+    }
+}
+
+/// A writer which counts the bytes accepted by the underlying writer.
+///
+/// Its accessor is `byte_count` to match [`CountRead`], whose `count`
+/// would be shadowed by `AsyncReadExt::count`.
+///
+/// Created by the [`count_write`] method.
+///
+/// [`CountRead`]: struct.CountRead.html
+/// [`count_write`]: trait.AsyncWriteExt.html#method.count_write
+#[derive(Debug)]
+pub struct CountWrite<W> {
+    inner: W,
+    count: u64,
+}
+
+pub fn count_write<W: AsyncWrite>(inner: W) -> CountWrite<W> {
+    CountWrite { inner, count: 0 }
+}
+
+impl<W> CountWrite<W> {
+    /// Returns the number of bytes the underlying writer has accepted so far.
+    pub fn byte_count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// Note that bytes written to the underlying writer directly are not
+    /// counted.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes this `CountWrite`, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: AsyncWrite> AsyncWrite for CountWrite<W> {
+    fn poll_write(&mut self, cx: &mut task::Context, buf: &[u8])
+        -> Poll<usize, io::Error>
+    {
+        let n = try_ready!(self.inner.poll_write(cx, buf));
+        self.count += n as u64;
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_vectored_write(&mut self, cx: &mut task::Context, vec: &[&IoVec])
+        -> Poll<usize, io::Error>
+    {
+        let n = try_ready!(self.inner.poll_vectored_write(cx, vec));
+        self.count += n as u64;
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_flush(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        self.inner.poll_flush(cx)
+    }
+
+    fn poll_close(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        self.inner.poll_close(cx)
+    }
+}
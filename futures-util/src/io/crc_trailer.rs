@@ -0,0 +1,101 @@
+use std::io;
+
+use {Async, Poll, task};
+
+use futures_io::AsyncWrite;
+
+/// A writer which appends the CRC-32 of everything written through it to the
+/// stream when closed.
+///
+/// Created by the [`crc_trailer_write`] method.
+///
+/// [`crc_trailer_write`]: trait.AsyncWriteExt.html#method.crc_trailer_write
+#[derive(Debug)]
+pub struct CrcTrailerWriter<W> {
+    inner: W,
+    // The running CRC register, which is inverted at the end.
+    crc: u32,
+    trailer: Option<[u8; 4]>,
+    pos: usize,
+}
+
+pub fn crc_trailer_write<W: AsyncWrite>(inner: W) -> CrcTrailerWriter<W> {
+    CrcTrailerWriter {
+        inner,
+        crc: !0,
+        trailer: None,
+        pos: 0,
+    }
+}
+
+// The bit-reflected polynomial of the CRC-32 used by zlib, gzip and PNG.
+const POLY: u32 = 0xedb8_8320;
+
+fn update(mut crc: u32, buf: &[u8]) -> u32 {
+    for &byte in buf {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ POLY } else { crc >> 1 };
+        }
+    }
+    crc
+}
+
+impl<W> CrcTrailerWriter<W> {
+    /// Returns the CRC-32 of the bytes written so far.
+    pub fn crc(&self) -> u32 {
+        !self.crc
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// Note that bytes written to the underlying writer directly aren't
+    /// covered by the CRC.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes this `CrcTrailerWriter`, returning the underlying writer.
+    ///
+    /// The trailer is only written by `poll_close`.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: AsyncWrite> AsyncWrite for CrcTrailerWriter<W> {
+    fn poll_write(&mut self, cx: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        if self.trailer.is_some() {
+            return Err(io::Error::new(io::ErrorKind::Other,
+                                      "write after the CRC trailer was started"))
+        }
+        let n = try_ready!(self.inner.poll_write(cx, buf));
+        self.crc = update(self.crc, &buf[..n]);
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_flush(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        self.inner.poll_flush(cx)
+    }
+
+    fn poll_close(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        let crc = self.crc();
+        let trailer = *self.trailer.get_or_insert_with(|| {
+            [(crc >> 24) as u8, (crc >> 16) as u8, (crc >> 8) as u8, crc as u8]
+        });
+        while self.pos < trailer.len() {
+            let n = try_ready!(self.inner.poll_write(cx, &trailer[self.pos..]));
+            if n == 0 {
+                return Err(io::Error::new(io::ErrorKind::WriteZero,
+                                          "failed to write the CRC trailer"))
+            }
+            self.pos += n;
+        }
+        self.inner.poll_close(cx)
+    }
+}
@@ -0,0 +1,66 @@
+use std::io;
+
+use {Async, Poll, task};
+
+use futures_io::AsyncWrite;
+
+/// A writer which passes each write to the underlying writer as one whole
+/// message.
+///
+/// Created by the [`datagram_write`] method.
+///
+/// [`datagram_write`]: trait.AsyncWriteExt.html#method.datagram_write
+#[derive(Debug)]
+pub struct DatagramWriter<W> {
+    inner: W,
+    max_datagram: usize,
+}
+
+pub fn datagram_write<W: AsyncWrite>(inner: W, max_datagram: usize) -> DatagramWriter<W> {
+    DatagramWriter { inner, max_datagram }
+}
+
+impl<W> DatagramWriter<W> {
+    /// Returns the size of the largest message which may be written.
+    pub fn max_datagram(&self) -> usize {
+        self.max_datagram
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes this `DatagramWriter`, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: AsyncWrite> AsyncWrite for DatagramWriter<W> {
+    fn poll_write(&mut self, cx: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        if buf.len() > self.max_datagram {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                      "message is larger than the maximum datagram size"))
+        }
+        let n = try_ready!(self.inner.poll_write(cx, buf));
+        if n != buf.len() {
+            return Err(io::Error::new(io::ErrorKind::WriteZero,
+                                      "datagram was only partially written"))
+        }
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_flush(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        self.inner.poll_flush(cx)
+    }
+
+    fn poll_close(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        self.inner.poll_close(cx)
+    }
+}
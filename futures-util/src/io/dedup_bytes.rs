@@ -0,0 +1,71 @@
+use std::io;
+
+use {Async, Poll, task};
+
+use futures_io::{AsyncRead, Initializer};
+
+/// A reader which collapses runs of identical consecutive bytes into one.
+///
+/// Created by the [`dedup_bytes`] method.
+///
+/// [`dedup_bytes`]: trait.AsyncReadExt.html#method.dedup_bytes
+#[derive(Debug)]
+pub struct DedupBytes<R> {
+    inner: R,
+    last: Option<u8>,
+}
+
+pub fn dedup_bytes<R: AsyncRead>(inner: R) -> DedupBytes<R> {
+    DedupBytes { inner, last: None }
+}
+
+impl<R> DedupBytes<R> {
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this `DedupBytes`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for DedupBytes<R> {
+    unsafe fn initializer(&self) -> Initializer {
+        self.inner.initializer()
+    }
+
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        if buf.is_empty() {
+            return Ok(Async::Ready(0))
+        }
+        loop {
+            let n = try_ready!(self.inner.poll_read(cx, buf));
+            if n == 0 {
+                return Ok(Async::Ready(0))
+            }
+            let mut out = 0;
+            for i in 0..n {
+                let byte = buf[i];
+                if self.last != Some(byte) {
+                    buf[out] = byte;
+                    out += 1;
+                    self.last = Some(byte);
+                }
+            }
+            // A read which only continued the previous run yields nothing,
+            // which mustn't be mistaken for EOF.
+            if out > 0 {
+                return Ok(Async::Ready(out))
+            }
+        }
+    }
+}
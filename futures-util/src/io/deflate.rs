@@ -0,0 +1,284 @@
+use std::io;
+use std::vec::Vec;
+
+use {Async, Poll, task};
+use flate2::{Compress, Compression, Decompress, FlushCompress, FlushDecompress};
+use flate2::Status;
+
+use futures_io::{AsyncRead, AsyncWrite};
+
+// The size of the chunks compressed output is produced in.
+const CHUNK: usize = 8 * 1024;
+
+/// A writer which compresses the bytes written to it into a raw deflate
+/// stream written to the underlying writer.
+///
+/// Created by the [`deflate_write`] function.
+///
+/// [`deflate_write`]: fn.deflate_write.html
+#[derive(Debug)]
+pub struct DeflateWriter<W> {
+    inner: W,
+    compress: Compress,
+    out: Vec<u8>,
+    pos: usize,
+    finished: bool,
+}
+
+/// Creates a writer which deflate-compresses the bytes written to it at the
+/// given `level` and writes the compressed stream to `inner`.
+///
+/// Writes report the number of uncompressed bytes consumed. Compressed
+/// output which the underlying writer doesn't accept right away is buffered
+/// and written out by later calls. `poll_flush` flushes the compressor so
+/// that everything written so far can be decompressed, and `poll_close`
+/// finishes the deflate stream before closing the underlying writer.
+///
+/// This function is only available with the `deflate` feature.
+pub fn deflate_write<W: AsyncWrite>(inner: W, level: Compression) -> DeflateWriter<W> {
+    DeflateWriter {
+        inner,
+        compress: Compress::new(level, false),
+        out: Vec::new(),
+        pos: 0,
+        finished: false,
+    }
+}
+
+fn compress_error<E>(_: E) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, "deflate compression failed")
+}
+
+impl<W> DeflateWriter<W> {
+    /// Returns the number of uncompressed bytes written so far.
+    pub fn total_in(&self) -> u64 {
+        self.compress.total_in()
+    }
+
+    /// Returns the number of compressed bytes produced so far.
+    pub fn total_out(&self) -> u64 {
+        self.compress.total_out()
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// Note that writing to the underlying writer directly corrupts the
+    /// compressed stream.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes this `DeflateWriter`, returning the underlying writer.
+    ///
+    /// Any compressed output which hasn't been written yet is lost.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Runs the compressor over `input`, appending all the output it
+    /// produces to `out`. Returns the number of input bytes consumed.
+    fn run(&mut self, input: &[u8], flush: FlushCompress) -> Result<usize, io::Error> {
+        let start = self.compress.total_in();
+        let mut chunk = [0; CHUNK];
+        loop {
+            let consumed = (self.compress.total_in() - start) as usize;
+            let before = self.compress.total_out();
+            let status = self.compress.compress(&input[consumed..], &mut chunk, flush)
+                .map_err(compress_error)?;
+            let produced = (self.compress.total_out() - before) as usize;
+            self.out.extend_from_slice(&chunk[..produced]);
+
+            let consumed = (self.compress.total_in() - start) as usize;
+            let done = match status {
+                Status::StreamEnd => true,
+                Status::BufError => produced == 0,
+                Status::Ok => consumed == input.len() && produced < chunk.len(),
+            };
+            if done {
+                return Ok(consumed)
+            }
+        }
+    }
+}
+
+impl<W: AsyncWrite> DeflateWriter<W> {
+    fn poll_out(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        while self.pos < self.out.len() {
+            let n = try_ready!(self.inner.poll_write(cx, &self.out[self.pos..]));
+            if n == 0 {
+                return Err(io::Error::new(io::ErrorKind::WriteZero,
+                                          "failed to write compressed data"))
+            }
+            self.pos += n;
+        }
+        self.out.clear();
+        self.pos = 0;
+        Ok(Async::Ready(()))
+    }
+}
+
+impl<W: AsyncWrite> AsyncWrite for DeflateWriter<W> {
+    fn poll_write(&mut self, cx: &mut task::Context, buf: &[u8])
+        -> Poll<usize, io::Error>
+    {
+        if self.finished {
+            return Err(io::Error::new(io::ErrorKind::Other,
+                                      "write after the deflate stream was finished"))
+        }
+        try_ready!(self.poll_out(cx));
+        if buf.is_empty() {
+            return Ok(Async::Ready(0))
+        }
+        let n = self.run(buf, FlushCompress::None)?;
+
+        // The input is ours now; try to get the output going, but any error
+        // or `Pending` is reported by the next call instead.
+        let _ = self.poll_out(cx);
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_flush(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        try_ready!(self.poll_out(cx));
+        if !self.finished {
+            self.run(&[], FlushCompress::Sync)?;
+            try_ready!(self.poll_out(cx));
+        }
+        self.inner.poll_flush(cx)
+    }
+
+    fn poll_close(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        try_ready!(self.poll_out(cx));
+        if !self.finished {
+            self.run(&[], FlushCompress::Finish)?;
+            self.finished = true;
+            try_ready!(self.poll_out(cx));
+        }
+        self.inner.poll_close(cx)
+    }
+}
+
+/// A reader which decompresses a raw deflate stream read from the
+/// underlying reader.
+///
+/// Created by the [`inflate_read`] function.
+///
+/// [`inflate_read`]: fn.inflate_read.html
+#[derive(Debug)]
+pub struct InflateReader<R> {
+    inner: R,
+    decompress: Decompress,
+    buf: Vec<u8>,
+    pos: usize,
+    eof: bool,
+    done: bool,
+}
+
+/// Creates a reader which decompresses the raw deflate stream read from
+/// `inner`, such as one written by `deflate_write`.
+///
+/// Compressed input may arrive in chunks of any size. A corrupt stream is an
+/// `InvalidData` error, and a stream which ends before the end of the
+/// compressed data is an `UnexpectedEof` error. Anything after the end of
+/// the compressed data is left unread in the internal buffer.
+///
+/// This function is only available with the `deflate` feature.
+pub fn inflate_read<R: AsyncRead>(inner: R) -> InflateReader<R> {
+    InflateReader {
+        inner,
+        decompress: Decompress::new(false),
+        buf: Vec::new(),
+        pos: 0,
+        eof: false,
+        done: false,
+    }
+}
+
+impl<R> InflateReader<R> {
+    /// Returns the number of compressed bytes consumed so far.
+    pub fn total_in(&self) -> u64 {
+        self.decompress.total_in()
+    }
+
+    /// Returns the number of decompressed bytes produced so far.
+    pub fn total_out(&self) -> u64 {
+        self.decompress.total_out()
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this `InflateReader`, returning the underlying reader.
+    ///
+    /// Any buffered compressed input is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for InflateReader<R> {
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        if self.done || buf.is_empty() {
+            return Ok(Async::Ready(0))
+        }
+        loop {
+            let flush = if self.eof {
+                FlushDecompress::Finish
+            } else {
+                FlushDecompress::None
+            };
+            let before_in = self.decompress.total_in();
+            let before_out = self.decompress.total_out();
+            let status = self.decompress.decompress(&self.buf[self.pos..], buf, flush)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            let consumed = (self.decompress.total_in() - before_in) as usize;
+            let produced = (self.decompress.total_out() - before_out) as usize;
+            self.pos += consumed;
+
+            if let Status::StreamEnd = status {
+                self.done = true;
+            }
+            if produced > 0 || self.done {
+                return Ok(Async::Ready(produced))
+            }
+            if consumed > 0 && self.pos < self.buf.len() {
+                continue
+            }
+            if self.eof {
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof,
+                                          "deflate stream ended unexpectedly"))
+            }
+
+            // More input is needed.
+            self.buf.drain(..self.pos);
+            self.pos = 0;
+            let len = self.buf.len();
+            self.buf.resize(len + CHUNK, 0);
+            let res = self.inner.poll_read(cx, &mut self.buf[len..]);
+            let n = match res {
+                Ok(Async::Ready(n)) => n,
+                _ => 0,
+            };
+            self.buf.truncate(len + n);
+            match res {
+                Ok(Async::Ready(0)) => self.eof = true,
+                Ok(Async::Ready(_)) => {}
+                Ok(Async::Pending) => return Ok(Async::Pending),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
@@ -0,0 +1,216 @@
+use std::cmp;
+use std::collections::{HashMap, VecDeque};
+use std::io;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use {Async, Poll, task};
+use futures_core::task::Waker;
+
+use futures_io::AsyncRead;
+
+// Tag byte and big-endian `u32` payload length.
+const HEADER_LEN: usize = 1 + 4;
+
+// The number of bytes buffered for a tag before demuxing pauses.
+const CAPACITY: usize = 8 * 1024;
+
+/// One of the logical streams read from a multiplexed reader.
+///
+/// Created by the [`demux`] method.
+///
+/// [`demux`]: trait.AsyncReadExt.html#method.demux
+#[derive(Debug)]
+pub struct DemuxReader<R> {
+    tag: u8,
+    shared: Arc<Mutex<Shared<R>>>,
+}
+
+#[derive(Debug)]
+struct Shared<R> {
+    inner: R,
+    state: State,
+    endpoints: HashMap<u8, Endpoint>,
+    eof: bool,
+}
+
+#[derive(Debug)]
+enum State {
+    Header([u8; HEADER_LEN], usize),
+    Payload(u8, usize),
+}
+
+#[derive(Debug, Default)]
+struct Endpoint {
+    buf: VecDeque<u8>,
+    waker: Option<Waker>,
+    // Set once the reader has been dropped; its frames are discarded.
+    closed: bool,
+}
+
+pub fn demux<R>(inner: R, tags: &[u8]) -> HashMap<u8, DemuxReader<R>>
+    where R: AsyncRead,
+{
+    let shared = Arc::new(Mutex::new(Shared {
+        inner,
+        state: State::Header([0; HEADER_LEN], 0),
+        endpoints: tags.iter().map(|&tag| (tag, Endpoint::default())).collect(),
+        eof: false,
+    }));
+    // A tag listed twice gets a single reader. Collecting the pairs into a
+    // map would instead drop the first reader, which closes the endpoint
+    // the second one reads from.
+    let mut readers = HashMap::new();
+    for &tag in tags {
+        readers.entry(tag).or_insert_with(|| DemuxReader { tag, shared: shared.clone() });
+    }
+    readers
+}
+
+fn lock<'a, R>(shared: &'a Mutex<Shared<R>>) -> MutexGuard<'a, Shared<R>> {
+    shared.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl<R> DemuxReader<R> {
+    /// Returns the tag of the frames this reader yields.
+    pub fn tag(&self) -> u8 {
+        self.tag
+    }
+}
+
+impl<R> Shared<R> {
+    fn wake_all(&mut self) {
+        for endpoint in self.endpoints.values_mut() {
+            if let Some(waker) = endpoint.waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl<R: AsyncRead> Shared<R> {
+    /// Reads from the underlying reader until `tag` has data buffered, the
+    /// buffer of the tag currently being read is full, or the reader pends.
+    fn pump(&mut self, cx: &mut task::Context, tag: u8) -> Poll<(), io::Error> {
+        while self.endpoints[&tag].buf.is_empty() && !self.eof {
+            match self.state {
+                State::Header(ref mut header, ref mut filled) => {
+                    let n = try_ready!(self.inner.poll_read(cx, &mut header[*filled..]));
+                    if n == 0 {
+                        self.eof = true;
+                        if *filled > 0 {
+                            return Err(io::Error::new(io::ErrorKind::UnexpectedEof,
+                                                      "stream ended in the middle of a frame"))
+                        }
+                        break
+                    }
+                    *filled += n;
+                    if *filled < HEADER_LEN {
+                        continue
+                    }
+                }
+                State::Payload(to, remaining) => {
+                    if remaining == 0 {
+                        self.state = State::Header([0; HEADER_LEN], 0);
+                        continue
+                    }
+                    let closed = self.endpoints[&to].closed;
+                    let space = if closed {
+                        remaining
+                    } else {
+                        CAPACITY - self.endpoints[&to].buf.len()
+                    };
+                    if space == 0 {
+                        return Ok(Async::Pending)
+                    }
+                    let mut chunk = [0; 1024];
+                    let max = cmp::min(cmp::min(remaining, space), chunk.len());
+                    let n = try_ready!(self.inner.poll_read(cx, &mut chunk[..max]));
+                    if n == 0 {
+                        self.eof = true;
+                        return Err(io::Error::new(io::ErrorKind::UnexpectedEof,
+                                                  "stream ended in the middle of a frame"))
+                    }
+                    if !closed {
+                        let endpoint = self.endpoints.get_mut(&to).unwrap();
+                        endpoint.buf.extend(&chunk[..n]);
+                        if let Some(waker) = endpoint.waker.take() {
+                            waker.wake();
+                        }
+                    }
+                    self.state = State::Payload(to, remaining - n);
+                    continue
+                }
+            }
+
+            // A complete header has been read.
+            let (to, len) = match self.state {
+                State::Header(ref header, _) => {
+                    let len = header[1..].iter().fold(0, |n, &b| (n << 8) | b as usize);
+                    (header[0], len)
+                }
+                State::Payload(..) => unreachable!(),
+            };
+            if !self.endpoints.contains_key(&to) {
+                return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                          "frame with an unknown tag"))
+            }
+            self.state = State::Payload(to, len);
+        }
+        Ok(Async::Ready(()))
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for DemuxReader<R> {
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        let mut shared = lock(&self.shared);
+        match shared.pump(cx, self.tag) {
+            Ok(Async::Ready(())) => {}
+            Ok(Async::Pending) => {
+                let endpoint = shared.endpoints.get_mut(&self.tag).unwrap();
+                if endpoint.buf.is_empty() {
+                    endpoint.waker = Some(cx.waker().clone());
+                    return Ok(Async::Pending)
+                }
+            }
+            Err(e) => {
+                shared.wake_all();
+                return Err(e)
+            }
+        }
+
+        if shared.eof && shared.endpoints[&self.tag].buf.is_empty() {
+            shared.wake_all();
+            return Ok(Async::Ready(0))
+        }
+
+        let n = {
+            let endpoint = shared.endpoints.get_mut(&self.tag).unwrap();
+            let n = cmp::min(buf.len(), endpoint.buf.len());
+            for (dst, src) in buf.iter_mut().zip(endpoint.buf.drain(..n)) {
+                *dst = src;
+            }
+            n
+        };
+        // Another reader may be waiting for this buffer to drain.
+        if n > 0 {
+            shared.wake_all();
+        }
+        Ok(Async::Ready(n))
+    }
+}
+
+impl<R> Drop for DemuxReader<R> {
+    fn drop(&mut self) {
+        let mut shared = lock(&self.shared);
+        {
+            let endpoint = shared.endpoints.get_mut(&self.tag).unwrap();
+            endpoint.closed = true;
+            endpoint.buf = VecDeque::new();
+            endpoint.waker = None;
+        }
+        // The others may be waiting for this buffer to drain.
+        shared.wake_all();
+    }
+}
@@ -0,0 +1,93 @@
+use std::cmp;
+use std::io::{self, Cursor};
+use std::mem;
+use std::vec::Vec;
+
+use {Async, Future, Poll, task};
+
+use futures_io::AsyncRead;
+
+use super::chain::{chain, Chain};
+
+/// The result of [`detect_format`].
+///
+/// [`detect_format`]: trait.AsyncReadExt.html#method.detect_format
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Detected<F> {
+    /// The stream starts with the magic bytes of this format.
+    Known(F),
+    /// The stream doesn't start with any of the given magic bytes.
+    Unknown,
+}
+
+/// A future which looks at the start of a stream to detect its format.
+///
+/// This is created by the [`detect_format`] method.
+///
+/// [`detect_format`]: trait.AsyncReadExt.html#method.detect_format
+#[derive(Debug)]
+pub struct DetectFormat<R, F> {
+    inner: Option<R>,
+    detectors: Vec<(Vec<u8>, F)>,
+    buf: Vec<u8>,
+    eof: bool,
+}
+
+pub fn detect_format<R, F>(inner: R, detectors: Vec<(&[u8], F)>) -> DetectFormat<R, F>
+    where R: AsyncRead,
+{
+    DetectFormat {
+        inner: Some(inner),
+        detectors: detectors.into_iter().map(|(magic, f)| (magic.to_vec(), f)).collect(),
+        buf: Vec::new(),
+        eof: false,
+    }
+}
+
+impl<R, F> DetectFormat<R, F> {
+    /// Returns the index of the first detector which matches, `Ok(None)` if
+    /// none of them can, or `Err(())` if more bytes are needed to tell.
+    fn decide(&self) -> Result<Option<usize>, ()> {
+        for (i, &(ref magic, _)) in self.detectors.iter().enumerate() {
+            let len = cmp::min(magic.len(), self.buf.len());
+            if magic[..len] != self.buf[..len] {
+                continue
+            }
+            if len == magic.len() {
+                return Ok(Some(i))
+            }
+            if !self.eof {
+                // An earlier detector takes precedence over any later one.
+                return Err(())
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl<R: AsyncRead, F> Future for DetectFormat<R, F> {
+    type Item = (Detected<F>, Chain<Cursor<Vec<u8>>, R>);
+    type Error = io::Error;
+
+    fn poll(&mut self, cx: &mut task::Context) -> Poll<Self::Item, io::Error> {
+        loop {
+            if let Ok(found) = self.decide() {
+                let inner = self.inner.take().expect("cannot poll DetectFormat twice");
+                let detected = match found {
+                    Some(i) => Detected::Known(self.detectors.swap_remove(i).1),
+                    None => Detected::Unknown,
+                };
+                let prefix = Cursor::new(mem::replace(&mut self.buf, Vec::new()));
+                return Ok(Async::Ready((detected, chain(prefix, inner))))
+            }
+
+            let mut chunk = [0; 64];
+            let inner = self.inner.as_mut().expect("cannot poll DetectFormat twice");
+            let n = try_ready!(inner.poll_read(cx, &mut chunk));
+            if n == 0 {
+                self.eof = true;
+            }
+            self.buf.extend_from_slice(&chunk[..n]);
+        }
+    }
+}
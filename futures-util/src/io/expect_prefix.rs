@@ -0,0 +1,87 @@
+use std::cmp;
+use std::io;
+use std::vec::Vec;
+
+use {Async, Poll, task};
+
+use futures_io::{AsyncRead, Initializer};
+
+/// An `AsyncRead` which verifies that the underlying reader starts with an
+/// expected sequence of bytes.
+///
+/// Created by the [`expect_prefix`] method.
+///
+/// [`expect_prefix`]: trait.AsyncReadExt.html#method.expect_prefix
+#[derive(Debug)]
+pub struct ExpectPrefix<R> {
+    inner: R,
+    prefix: Vec<u8>,
+    matched: usize,
+}
+
+pub fn expect_prefix<R>(inner: R, prefix: &[u8]) -> ExpectPrefix<R>
+    where R: AsyncRead,
+{
+    ExpectPrefix {
+        inner,
+        prefix: prefix.to_vec(),
+        matched: 0,
+    }
+}
+
+impl<R> ExpectPrefix<R> {
+    /// Returns whether the whole prefix has been read and verified.
+    pub fn is_verified(&self) -> bool {
+        self.matched == self.prefix.len()
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// Note that reading from the underlying reader directly may confuse the
+    /// verification of the prefix.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this `ExpectPrefix`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for ExpectPrefix<R> {
+    unsafe fn initializer(&self) -> Initializer {
+        self.inner.initializer()
+    }
+
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        if buf.is_empty() {
+            return Ok(Async::Ready(0))
+        }
+
+        // The caller's buffer doubles as scratch space for the prefix, so
+        // never read past its end.
+        while self.matched < self.prefix.len() {
+            let n = cmp::min(buf.len(), self.prefix.len() - self.matched);
+            let n = try_ready!(self.inner.poll_read(cx, &mut buf[..n]));
+            if n == 0 {
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof,
+                                          "stream ended before the expected prefix"))
+            }
+            if buf[..n] != self.prefix[self.matched..self.matched + n] {
+                return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                          "stream does not start with the expected prefix"))
+            }
+            self.matched += n;
+        }
+
+        self.inner.poll_read(cx, buf)
+    }
+}
@@ -0,0 +1,133 @@
+use std::io;
+use std::mem;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use {Poll, task};
+
+use futures_io::{AsyncWrite, IoVec};
+
+/// A handle to a writer shared between tasks which discards writes made
+/// through handles from before a reconnect.
+///
+/// The writer carries a generation counter, and every handle remembers the
+/// generation it was created in. [`bump_generation`] and [`reconnect`]
+/// start a new generation, after which writing, flushing or closing through
+/// a handle from an earlier one fails with a `NotConnected` error instead of
+/// reaching the writer. This way writes which were still in flight for a
+/// previous connection can't end up on the new one.
+///
+/// Handles are created with `FencedWriter::new` and `clone`; a clone belongs
+/// to the same generation as the handle it was cloned from.
+///
+/// [`bump_generation`]: #method.bump_generation
+/// [`reconnect`]: #method.reconnect
+#[derive(Debug)]
+pub struct FencedWriter<W> {
+    shared: Arc<Mutex<Shared<W>>>,
+    generation: u64,
+}
+
+#[derive(Debug)]
+struct Shared<W> {
+    writer: W,
+    generation: u64,
+}
+
+impl<W> Clone for FencedWriter<W> {
+    fn clone(&self) -> FencedWriter<W> {
+        FencedWriter {
+            shared: self.shared.clone(),
+            generation: self.generation,
+        }
+    }
+}
+
+fn lock<'a, W>(shared: &'a Mutex<Shared<W>>) -> MutexGuard<'a, Shared<W>> {
+    shared.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl<W: AsyncWrite> FencedWriter<W> {
+    /// Creates a new handle to `writer`, in generation zero.
+    pub fn new(writer: W) -> FencedWriter<W> {
+        FencedWriter {
+            shared: Arc::new(Mutex::new(Shared { writer, generation: 0 })),
+            generation: 0,
+        }
+    }
+}
+
+impl<W> FencedWriter<W> {
+    /// Returns the generation this handle belongs to.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Returns whether a new generation has been started since this handle
+    /// was created, so that it can no longer be used.
+    pub fn is_stale(&self) -> bool {
+        lock(&self.shared).generation != self.generation
+    }
+
+    /// Starts a new generation, which this handle moves to, and returns it.
+    ///
+    /// All other handles of earlier generations become stale.
+    pub fn bump_generation(&mut self) -> u64 {
+        let mut shared = lock(&self.shared);
+        shared.generation += 1;
+        self.generation = shared.generation;
+        self.generation
+    }
+
+    /// Replaces the underlying writer, such as with a new connection, and
+    /// starts a new generation as `bump_generation` does. Returns the
+    /// previous writer.
+    pub fn reconnect(&mut self, writer: W) -> W {
+        let old = mem::replace(&mut lock(&self.shared).writer, writer);
+        self.bump_generation();
+        old
+    }
+
+    /// Consumes this handle, returning the underlying writer if no other
+    /// handles remain.
+    pub fn into_inner(self) -> Result<W, FencedWriter<W>> {
+        let generation = self.generation;
+        match Arc::try_unwrap(self.shared) {
+            Ok(shared) => Ok(shared.into_inner().unwrap_or_else(|e| e.into_inner()).writer),
+            Err(shared) => Err(FencedWriter { shared, generation }),
+        }
+    }
+
+    /// Runs `f` with the writer if this handle isn't stale.
+    fn with_writer<T, F>(&self, f: F) -> Poll<T, io::Error>
+        where F: FnOnce(&mut W) -> Poll<T, io::Error>,
+    {
+        let mut shared = lock(&self.shared);
+        if shared.generation != self.generation {
+            return Err(io::Error::new(io::ErrorKind::NotConnected,
+                                      "write from a previous generation"))
+        }
+        f(&mut shared.writer)
+    }
+}
+
+impl<W: AsyncWrite> AsyncWrite for FencedWriter<W> {
+    fn poll_write(&mut self, cx: &mut task::Context, buf: &[u8])
+        -> Poll<usize, io::Error>
+    {
+        self.with_writer(|w| w.poll_write(cx, buf))
+    }
+
+    fn poll_vectored_write(&mut self, cx: &mut task::Context, vec: &[&IoVec])
+        -> Poll<usize, io::Error>
+    {
+        self.with_writer(|w| w.poll_vectored_write(cx, vec))
+    }
+
+    fn poll_flush(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        self.with_writer(|w| w.poll_flush(cx))
+    }
+
+    fn poll_close(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        self.with_writer(|w| w.poll_close(cx))
+    }
+}
@@ -0,0 +1,82 @@
+use std::io;
+
+use {Async, Poll, task};
+
+use futures_io::{AsyncWrite, IoVec};
+
+/// A writer which flushes and closes the underlying writer at most once.
+///
+/// Created by the [`finalize_once`] method.
+///
+/// [`finalize_once`]: trait.AsyncWriteExt.html#method.finalize_once
+#[derive(Debug)]
+pub struct FinalizeOnce<W> {
+    inner: W,
+    state: State,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Open,
+    Flushed,
+    Closed,
+}
+
+pub fn finalize_once<W: AsyncWrite>(inner: W) -> FinalizeOnce<W> {
+    FinalizeOnce { inner, state: State::Open }
+}
+
+impl<W> FinalizeOnce<W> {
+    /// Returns whether the underlying writer has been closed.
+    pub fn is_closed(&self) -> bool {
+        self.state == State::Closed
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes this `FinalizeOnce`, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: AsyncWrite> AsyncWrite for FinalizeOnce<W> {
+    fn poll_write(&mut self, cx: &mut task::Context, buf: &[u8])
+        -> Poll<usize, io::Error>
+    {
+        self.inner.poll_write(cx, buf)
+    }
+
+    fn poll_vectored_write(&mut self, cx: &mut task::Context, vec: &[&IoVec])
+        -> Poll<usize, io::Error>
+    {
+        self.inner.poll_vectored_write(cx, vec)
+    }
+
+    fn poll_flush(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        if self.state == State::Closed {
+            return Ok(Async::Ready(()))
+        }
+        self.inner.poll_flush(cx)
+    }
+
+    fn poll_close(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        if self.state == State::Open {
+            try_ready!(self.inner.poll_flush(cx));
+            self.state = State::Flushed;
+        }
+        if self.state == State::Flushed {
+            try_ready!(self.inner.poll_close(cx));
+            self.state = State::Closed;
+        }
+        Ok(Async::Ready(()))
+    }
+}
@@ -1,38 +1,30 @@
 use std::io;
 
-use {Poll, Future, Async, task};
+use {Poll, Future, task};
 
 use futures_io::AsyncWrite;
 
 /// A future used to fully flush an I/O object.
 ///
-/// Resolves to the underlying I/O object once the flush operation is complete.
+/// Created by the [`flush`] method.
 ///
-/// Created by the [`flush`] function.
-///
-/// [`flush`]: fn.flush.html
+/// [`flush`]: trait.AsyncWriteExt.html#method.flush
 #[derive(Debug)]
-pub struct Flush<A> {
-    a: Option<A>,
+pub struct Flush<'a, A: ?Sized + 'a> {
+    writer: &'a mut A,
 }
 
-pub fn flush<A>(a: A) -> Flush<A>
-    where A: AsyncWrite,
+pub fn flush<'a, A>(writer: &'a mut A) -> Flush<'a, A>
+    where A: ?Sized + AsyncWrite,
 {
-    Flush {
-        a: Some(a),
-    }
+    Flush { writer }
 }
 
-impl<A> Future for Flush<A>
-    where A: AsyncWrite,
-{
-    type Item = A;
+impl<'a, A: ?Sized + AsyncWrite> Future for Flush<'a, A> {
+    type Item = ();
     type Error = io::Error;
 
-    fn poll(&mut self, cx: &mut task::Context) -> Poll<A, io::Error> {
-        try_ready!(self.a.as_mut().unwrap().poll_flush(cx));
-        Ok(Async::Ready(self.a.take().unwrap()))
+    fn poll(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        self.writer.poll_flush(cx)
     }
 }
-
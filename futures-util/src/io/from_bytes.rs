@@ -0,0 +1,16 @@
+use std::io::Cursor;
+use std::vec::Vec;
+
+/// Creates a reader which owns a copy of `bytes`.
+///
+/// This is the owned counterpart of reading from `&[u8]` directly, for when
+/// the reader needs to be stored, such as in a struct or a task, where the
+/// borrow would get in the way. The reader is a `Cursor`, so it is also an
+/// `AsyncBufRead` and `AsyncSeek`. Its initializer doesn't zero buffers, and
+/// once all bytes have been read every further read returns zero bytes.
+///
+/// Any other owned byte buffer, such as a `[u8; N]` array, can be read by
+/// wrapping it in a `Cursor` instead of copying it.
+pub fn from_bytes(bytes: &[u8]) -> Cursor<Vec<u8>> {
+    Cursor::new(bytes.to_vec())
+}
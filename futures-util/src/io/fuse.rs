@@ -0,0 +1,68 @@
+use std::io;
+
+use {Async, Poll, task};
+
+use futures_io::{AsyncRead, Initializer};
+
+/// An `AsyncRead` which never polls the underlying reader again once it has
+/// reached EOF or failed.
+///
+/// Created by the [`fuse`] method.
+///
+/// [`fuse`]: trait.AsyncReadExt.html#method.fuse
+#[derive(Debug)]
+pub struct Fuse<R> {
+    inner: R,
+    done: bool,
+}
+
+pub fn fuse<R: AsyncRead>(inner: R) -> Fuse<R> {
+    Fuse { inner, done: false }
+}
+
+impl<R> Fuse<R> {
+    /// Returns whether the underlying reader has reached EOF or failed.
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this `Fuse`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for Fuse<R> {
+    unsafe fn initializer(&self) -> Initializer {
+        self.inner.initializer()
+    }
+
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        if self.done {
+            return Ok(Async::Ready(0))
+        }
+        match self.inner.poll_read(cx, buf) {
+            Ok(Async::Ready(0)) if !buf.is_empty() => {
+                self.done = true;
+                Ok(Async::Ready(0))
+            }
+            Err(e) => {
+                self.done = true;
+                Err(e)
+            }
+            res => res,
+        }
+    }
+}
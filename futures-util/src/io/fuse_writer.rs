@@ -0,0 +1,117 @@
+use std::io;
+
+use {Async, Poll, task};
+
+use futures_io::{AsyncWrite, IoVec};
+
+/// A writer which never calls the underlying writer again once it has been
+/// closed or has failed.
+///
+/// Created by the [`fuse_write`] method.
+///
+/// [`fuse_write`]: trait.AsyncWriteExt.html#method.fuse_write
+#[derive(Debug)]
+pub struct FuseWriter<W> {
+    inner: W,
+    state: State,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Open,
+    Closed,
+    Failed(io::ErrorKind),
+}
+
+pub fn fuse_write<W: AsyncWrite>(inner: W) -> FuseWriter<W> {
+    FuseWriter { inner, state: State::Open }
+}
+
+impl<W> FuseWriter<W> {
+    /// Returns whether the underlying writer has been closed or has failed.
+    pub fn is_done(&self) -> bool {
+        self.state != State::Open
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes this `FuseWriter`, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    fn check<T>(&mut self, res: Poll<T, io::Error>) -> Poll<T, io::Error> {
+        if let Err(ref e) = res {
+            match e.kind() {
+                io::ErrorKind::WouldBlock | io::ErrorKind::Interrupted => {}
+                kind => self.state = State::Failed(kind),
+            }
+        }
+        res
+    }
+}
+
+fn failed(kind: io::ErrorKind) -> io::Error {
+    io::Error::new(kind, "writer failed previously")
+}
+
+impl<W: AsyncWrite> AsyncWrite for FuseWriter<W> {
+    fn poll_write(&mut self, cx: &mut task::Context, buf: &[u8])
+        -> Poll<usize, io::Error>
+    {
+        match self.state {
+            State::Open => {
+                let res = self.inner.poll_write(cx, buf);
+                self.check(res)
+            }
+            State::Closed => Ok(Async::Ready(0)),
+            State::Failed(kind) => Err(failed(kind)),
+        }
+    }
+
+    fn poll_vectored_write(&mut self, cx: &mut task::Context, vec: &[&IoVec])
+        -> Poll<usize, io::Error>
+    {
+        match self.state {
+            State::Open => {
+                let res = self.inner.poll_vectored_write(cx, vec);
+                self.check(res)
+            }
+            State::Closed => Ok(Async::Ready(0)),
+            State::Failed(kind) => Err(failed(kind)),
+        }
+    }
+
+    fn poll_flush(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        match self.state {
+            State::Open => {
+                let res = self.inner.poll_flush(cx);
+                self.check(res)
+            }
+            State::Closed => Ok(Async::Ready(())),
+            State::Failed(kind) => Err(failed(kind)),
+        }
+    }
+
+    fn poll_close(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        match self.state {
+            State::Open => {
+                let res = self.inner.poll_close(cx);
+                if let Ok(Async::Ready(())) = res {
+                    self.state = State::Closed;
+                }
+                self.check(res)
+            }
+            State::Closed => Ok(Async::Ready(())),
+            State::Failed(kind) => Err(failed(kind)),
+        }
+    }
+}
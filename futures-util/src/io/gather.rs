@@ -0,0 +1,106 @@
+use std::io;
+use std::vec::Vec;
+
+use {Async, Poll, task};
+
+use futures_io::{AsyncWrite, IoVec};
+
+/// A writer which keeps every write as a separate buffer and hands them all
+/// to the underlying writer in vectored writes when flushed or closed.
+///
+/// Created by the [`gather_until_close`] method.
+///
+/// [`gather_until_close`]: trait.AsyncWriteExt.html#method.gather_until_close
+#[derive(Debug)]
+pub struct GatherUntilClose<W> {
+    inner: W,
+    bufs: Vec<Vec<u8>>,
+    // The number of bytes of `bufs[0]` which have already been written.
+    pos: usize,
+}
+
+pub fn gather_until_close<W: AsyncWrite>(inner: W) -> GatherUntilClose<W> {
+    GatherUntilClose {
+        inner,
+        bufs: Vec::new(),
+        pos: 0,
+    }
+}
+
+impl<W> GatherUntilClose<W> {
+    /// Returns the number of buffered bytes not yet written to the underlying
+    /// writer.
+    pub fn buffered(&self) -> usize {
+        self.bufs.iter().map(|buf| buf.len()).sum::<usize>() - self.pos
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes this `GatherUntilClose`, returning the underlying writer.
+    ///
+    /// Any buffered bytes are lost.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: AsyncWrite> GatherUntilClose<W> {
+    fn poll_emit(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        while !self.bufs.is_empty() {
+            let n = {
+                let first = IoVec::from_bytes(&self.bufs[0][self.pos..]);
+                let vecs: Vec<&IoVec> = first.into_iter()
+                    .chain(self.bufs[1..].iter().filter_map(|buf| IoVec::from_bytes(buf)))
+                    .collect();
+                try_ready!(self.inner.poll_vectored_write(cx, &vecs))
+            };
+            if n == 0 {
+                return Err(io::Error::new(io::ErrorKind::WriteZero,
+                                          "failed to write gathered buffers"))
+            }
+
+            // Drop the buffers which have been written completely.
+            let mut n = self.pos + n;
+            let done = self.bufs.iter()
+                .take_while(|buf| {
+                    if n < buf.len() {
+                        return false
+                    }
+                    n -= buf.len();
+                    true
+                })
+                .count();
+            self.bufs.drain(..done);
+            self.pos = n;
+        }
+        self.pos = 0;
+        Ok(Async::Ready(()))
+    }
+}
+
+impl<W: AsyncWrite> AsyncWrite for GatherUntilClose<W> {
+    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        if !buf.is_empty() {
+            self.bufs.push(buf.to_vec());
+        }
+        Ok(Async::Ready(buf.len()))
+    }
+
+    fn poll_flush(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        try_ready!(self.poll_emit(cx));
+        self.inner.poll_flush(cx)
+    }
+
+    fn poll_close(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        try_ready!(self.poll_emit(cx));
+        self.inner.poll_close(cx)
+    }
+}
@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+use std::io;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::vec::Vec;
+
+use {Async, Future, Poll, task};
+use futures_core::task::Waker;
+
+use futures_io::AsyncWrite;
+
+/// A handle to a writer shared between tasks which lets a single flush of
+/// the writer serve every task whose bytes it covers.
+///
+/// Each [`write_and_flush`] future writes its bytes and then waits for a
+/// flush which was started after they were written. Before starting a flush
+/// it yields once, so that other tasks which are ready to write can add
+/// their bytes first; all of them are then served by one flush of the
+/// underlying writer instead of flushing it once each.
+///
+/// The bytes of one `write_and_flush` are never interleaved with those of
+/// another. Handles are created with `GroupFlushWriter::new` and `clone`.
+///
+/// [`write_and_flush`]: #method.write_and_flush
+#[derive(Debug)]
+pub struct GroupFlushWriter<W> {
+    shared: Arc<Mutex<Shared<W>>>,
+}
+
+#[derive(Debug)]
+struct Shared<W> {
+    writer: W,
+    // Whether a `write_and_flush` is in the middle of writing its bytes.
+    writing: bool,
+    // The total number of bytes written.
+    written: u64,
+    // The number of bytes covered by the last completed flush.
+    flushed: u64,
+    // The number of bytes the flush in progress, if any, covers.
+    flushing: Option<u64>,
+    flushes: u64,
+    // One slot per waiting `write_and_flush`, keyed by its id, so that a
+    // future polled again while it waits replaces its waker.
+    waiters: HashMap<usize, Waker>,
+    next_id: usize,
+}
+
+impl<W> Clone for GroupFlushWriter<W> {
+    fn clone(&self) -> GroupFlushWriter<W> {
+        GroupFlushWriter { shared: self.shared.clone() }
+    }
+}
+
+fn lock<'a, W>(shared: &'a Mutex<Shared<W>>) -> MutexGuard<'a, Shared<W>> {
+    shared.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl<W> Shared<W> {
+    fn wake_all(&mut self) {
+        for (_, waker) in self.waiters.drain() {
+            waker.wake();
+        }
+    }
+}
+
+impl<W: AsyncWrite> GroupFlushWriter<W> {
+    /// Creates a new handle to `writer`.
+    pub fn new(writer: W) -> GroupFlushWriter<W> {
+        GroupFlushWriter {
+            shared: Arc::new(Mutex::new(Shared {
+                writer,
+                writing: false,
+                written: 0,
+                flushed: 0,
+                flushing: None,
+                flushes: 0,
+                waiters: HashMap::new(),
+                next_id: 0,
+            })),
+        }
+    }
+
+    /// Creates a future which writes all of `buf` and resolves once a flush
+    /// of the writer covering it has completed.
+    pub fn write_and_flush(&self, buf: Vec<u8>) -> WriteAndFlush<W> {
+        let id = {
+            let mut shared = lock(&self.shared);
+            let id = shared.next_id;
+            shared.next_id = id.wrapping_add(1);
+            id
+        };
+        WriteAndFlush {
+            shared: self.shared.clone(),
+            id,
+            buf,
+            pos: 0,
+            state: State::Idle,
+        }
+    }
+
+    /// Returns the number of times the underlying writer has been flushed.
+    pub fn flushes(&self) -> u64 {
+        lock(&self.shared).flushes
+    }
+
+    /// Consumes this handle, returning the underlying writer if no other
+    /// handles or pending writes remain.
+    pub fn into_inner(self) -> Result<W, GroupFlushWriter<W>> {
+        match Arc::try_unwrap(self.shared) {
+            Ok(shared) => Ok(shared.into_inner().unwrap_or_else(|e| e.into_inner()).writer),
+            Err(shared) => Err(GroupFlushWriter { shared }),
+        }
+    }
+}
+
+/// A future which writes bytes through a `GroupFlushWriter` and waits for
+/// them to be flushed.
+///
+/// Created by the [`write_and_flush`] method.
+///
+/// [`write_and_flush`]: struct.GroupFlushWriter.html#method.write_and_flush
+#[derive(Debug)]
+pub struct WriteAndFlush<W> {
+    shared: Arc<Mutex<Shared<W>>>,
+    id: usize,
+    buf: Vec<u8>,
+    pos: usize,
+    state: State,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum State {
+    Idle,
+    Writing,
+    // Written up to the given mark, giving other tasks a chance to write
+    // before a flush is started.
+    Yielding(u64),
+    // Waiting for `flushed` to reach the given mark.
+    Flushing(u64),
+    Done,
+}
+
+impl<W: AsyncWrite> WriteAndFlush<W> {
+    fn poll_write(&mut self, shared: &mut Shared<W>, cx: &mut task::Context)
+        -> Poll<u64, io::Error>
+    {
+        while self.pos < self.buf.len() {
+            match shared.writer.poll_write(cx, &self.buf[self.pos..])? {
+                Async::Ready(0) => {
+                    return Err(io::Error::new(io::ErrorKind::WriteZero,
+                                              "failed to write whole buffer"))
+                }
+                Async::Ready(n) => {
+                    self.pos += n;
+                    shared.written += n as u64;
+                }
+                Async::Pending => return Ok(Async::Pending),
+            }
+        }
+        Ok(Async::Ready(shared.written))
+    }
+
+    fn poll_flushed(&mut self, shared: &mut Shared<W>, cx: &mut task::Context, mark: u64)
+        -> Poll<(), io::Error>
+    {
+        while shared.flushed < mark {
+            let target = *shared.flushing.get_or_insert(shared.written);
+            match shared.writer.poll_flush(cx) {
+                Ok(Async::Ready(())) => {
+                    shared.flushes += 1;
+                    shared.flushing = None;
+                    shared.flushed = target;
+                    shared.wake_all();
+                }
+                Ok(Async::Pending) => {
+                    shared.waiters.insert(self.id, cx.waker().clone());
+                    return Ok(Async::Pending)
+                }
+                Err(e) => {
+                    shared.flushing = None;
+                    shared.wake_all();
+                    return Err(e)
+                }
+            }
+        }
+        Ok(Async::Ready(()))
+    }
+}
+
+impl<W: AsyncWrite> Future for WriteAndFlush<W> {
+    type Item = ();
+    type Error = io::Error;
+
+    fn poll(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        let shared = self.shared.clone();
+        let mut shared = lock(&shared);
+        loop {
+            match self.state {
+                State::Idle => {
+                    if shared.writing {
+                        shared.waiters.insert(self.id, cx.waker().clone());
+                        return Ok(Async::Pending)
+                    }
+                    shared.waiters.remove(&self.id);
+                    shared.writing = true;
+                    self.state = State::Writing;
+                }
+                State::Writing => {
+                    let res = self.poll_write(&mut shared, cx);
+                    if let Ok(Async::Pending) = res {
+                        return Ok(Async::Pending)
+                    }
+                    shared.writing = false;
+                    shared.wake_all();
+                    self.state = match res {
+                        Ok(Async::Ready(mark)) => State::Yielding(mark),
+                        _ => State::Done,
+                    };
+                    res?;
+                }
+                State::Yielding(mark) => {
+                    self.state = State::Flushing(mark);
+                    if shared.flushing.is_none() {
+                        cx.waker().wake();
+                        return Ok(Async::Pending)
+                    }
+                }
+                State::Flushing(mark) => {
+                    try_ready!(self.poll_flushed(&mut shared, cx, mark));
+                    shared.waiters.remove(&self.id);
+                    self.state = State::Done;
+                    return Ok(Async::Ready(()))
+                }
+                State::Done => panic!("cannot poll WriteAndFlush twice"),
+            }
+        }
+    }
+}
+
+impl<W> Drop for WriteAndFlush<W> {
+    fn drop(&mut self) {
+        let mut shared = lock(&self.shared);
+        shared.waiters.remove(&self.id);
+        if let State::Writing = self.state {
+            shared.writing = false;
+            shared.wake_all();
+        }
+    }
+}
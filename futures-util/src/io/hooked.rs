@@ -0,0 +1,80 @@
+use std::io;
+
+use {Async, Poll, task};
+
+use futures_io::{AsyncRead, Initializer};
+
+/// The outcome of a `poll_read` call, as seen by the `after` hook of
+/// [`hooked`].
+///
+/// [`hooked`]: trait.AsyncReadExt.html#method.hooked
+#[derive(Debug)]
+pub enum ReadOutcome<'a> {
+    /// The read completed with this many bytes; zero means end of file.
+    Ready(usize),
+    /// The read is not ready yet.
+    Pending,
+    /// The read failed.
+    Err(&'a io::Error),
+}
+
+/// An `AsyncRead` which calls hooks around every read of the underlying
+/// reader.
+///
+/// Created by the [`hooked`] method.
+///
+/// [`hooked`]: trait.AsyncReadExt.html#method.hooked
+#[derive(Debug)]
+pub struct Hooked<R, B, A> {
+    inner: R,
+    before: B,
+    after: A,
+}
+
+pub fn hooked<R, B, A>(inner: R, before: B, after: A) -> Hooked<R, B, A>
+    where R: AsyncRead,
+          B: FnMut(&mut task::Context),
+          A: FnMut(ReadOutcome),
+{
+    Hooked { inner, before, after }
+}
+
+impl<R, B, A> Hooked<R, B, A> {
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this `Hooked`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R, B, A> AsyncRead for Hooked<R, B, A>
+    where R: AsyncRead,
+          B: FnMut(&mut task::Context),
+          A: FnMut(ReadOutcome),
+{
+    unsafe fn initializer(&self) -> Initializer {
+        self.inner.initializer()
+    }
+
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        (self.before)(cx);
+        let res = self.inner.poll_read(cx, buf);
+        match res {
+            Ok(Async::Ready(n)) => (self.after)(ReadOutcome::Ready(n)),
+            Ok(Async::Pending) => (self.after)(ReadOutcome::Pending),
+            Err(ref e) => (self.after)(ReadOutcome::Err(e)),
+        }
+        res
+    }
+}
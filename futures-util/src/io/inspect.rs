@@ -0,0 +1,115 @@
+use std::io;
+
+use {Async, Poll, task};
+
+use futures_io::{AsyncRead, AsyncWrite, Initializer};
+
+/// A reader which passes every chunk read from the underlying reader to a
+/// closure.
+///
+/// Created by the [`inspect`] method.
+///
+/// [`inspect`]: trait.AsyncReadExt.html#method.inspect
+#[derive(Debug)]
+pub struct Inspect<R, F> {
+    inner: R,
+    f: F,
+}
+
+pub fn inspect<R, F>(inner: R, f: F) -> Inspect<R, F>
+    where R: AsyncRead,
+          F: FnMut(&[u8]),
+{
+    Inspect { inner, f }
+}
+
+impl<R, F> Inspect<R, F> {
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this `Inspect`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R, F> AsyncRead for Inspect<R, F>
+    where R: AsyncRead,
+          F: FnMut(&[u8]),
+{
+    unsafe fn initializer(&self) -> Initializer {
+        self.inner.initializer()
+    }
+
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        let n = try_ready!(self.inner.poll_read(cx, buf));
+        (self.f)(&buf[..n]);
+        Ok(Async::Ready(n))
+    }
+}
+
+/// A writer which passes every chunk accepted by the underlying writer to a
+/// closure.
+///
+/// Created by the [`inspect_write`] method.
+///
+/// [`inspect_write`]: trait.AsyncWriteExt.html#method.inspect_write
+#[derive(Debug)]
+pub struct InspectWrite<W, F> {
+    inner: W,
+    f: F,
+}
+
+pub fn inspect_write<W, F>(inner: W, f: F) -> InspectWrite<W, F>
+    where W: AsyncWrite,
+          F: FnMut(&[u8]),
+{
+    InspectWrite { inner, f }
+}
+
+impl<W, F> InspectWrite<W, F> {
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes this `InspectWrite`, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W, F> AsyncWrite for InspectWrite<W, F>
+    where W: AsyncWrite,
+          F: FnMut(&[u8]),
+{
+    fn poll_write(&mut self, cx: &mut task::Context, buf: &[u8])
+        -> Poll<usize, io::Error>
+    {
+        let n = try_ready!(self.inner.poll_write(cx, buf));
+        (self.f)(&buf[..n]);
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_flush(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        self.inner.poll_flush(cx)
+    }
+
+    fn poll_close(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        self.inner.poll_close(cx)
+    }
+}
@@ -0,0 +1,86 @@
+use std::io;
+
+use {Async, Poll, task};
+
+use futures_io::{AsyncRead, Initializer, IoVec};
+
+/// A reader which records the position in the stream at which EOF was
+/// observed.
+///
+/// Created by the [`inspect_eof_position`] method.
+///
+/// [`inspect_eof_position`]: trait.AsyncReadExt.html#method.inspect_eof_position
+#[derive(Debug)]
+pub struct InspectEofPosition<R> {
+    inner: R,
+    pos: u64,
+    eof: Option<u64>,
+}
+
+pub fn inspect_eof_position<R: AsyncRead>(inner: R) -> InspectEofPosition<R> {
+    InspectEofPosition {
+        inner,
+        pos: 0,
+        eof: None,
+    }
+}
+
+impl<R> InspectEofPosition<R> {
+    /// Returns the number of bytes read at which the first EOF was observed,
+    /// or `None` if the underlying reader hasn't reported EOF yet.
+    pub fn eof_position(&self) -> Option<u64> {
+        self.eof
+    }
+
+    /// Returns the number of bytes read so far.
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this `InspectEofPosition`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn record(&mut self, n: usize, requested: bool) {
+        if n > 0 {
+            self.pos += n as u64;
+        } else if requested && self.eof.is_none() {
+            self.eof = Some(self.pos);
+        }
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for InspectEofPosition<R> {
+    unsafe fn initializer(&self) -> Initializer {
+        self.inner.initializer()
+    }
+
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        let n = try_ready!(self.inner.poll_read(cx, buf));
+        // A zero-length read into an empty buffer says nothing about EOF.
+        self.record(n, !buf.is_empty());
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_vectored_read(&mut self, cx: &mut task::Context, vec: &mut [&mut IoVec])
+        -> Poll<usize, io::Error>
+    {
+        let n = try_ready!(self.inner.poll_vectored_read(cx, vec));
+        let requested = vec.iter().any(|v| !v.is_empty());
+        self.record(n, requested);
+        Ok(Async::Ready(n))
+    }
+}
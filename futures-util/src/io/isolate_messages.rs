@@ -0,0 +1,101 @@
+use std::io;
+use std::vec::Vec;
+
+use {Async, Future, Poll, task};
+
+use futures_io::AsyncWrite;
+
+/// A writer which isolates each message sent through it between two
+/// flushes of the underlying writer.
+///
+/// Created by the [`isolate_messages`] method.
+///
+/// [`isolate_messages`]: trait.AsyncWriteExt.html#method.isolate_messages
+#[derive(Debug)]
+pub struct IsolateMessages<W> {
+    inner: W,
+}
+
+pub fn isolate_messages<W: AsyncWrite>(inner: W) -> IsolateMessages<W> {
+    IsolateMessages { inner }
+}
+
+impl<W: AsyncWrite> IsolateMessages<W> {
+    /// Creates a future which flushes the underlying writer, writes all of
+    /// `message` to it and flushes it again.
+    pub fn send_message<'a>(&'a mut self, message: Vec<u8>) -> SendMessage<'a, W> {
+        SendMessage {
+            writer: &mut self.inner,
+            message,
+            state: State::FlushBefore,
+        }
+    }
+}
+
+impl<W> IsolateMessages<W> {
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes this `IsolateMessages`, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+/// A future which sends one message through an `IsolateMessages` writer.
+///
+/// Created by the [`send_message`] method.
+///
+/// [`send_message`]: struct.IsolateMessages.html#method.send_message
+#[derive(Debug)]
+pub struct SendMessage<'a, W: 'a> {
+    writer: &'a mut W,
+    message: Vec<u8>,
+    state: State,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum State {
+    FlushBefore,
+    Writing(usize),
+    FlushAfter,
+    Done,
+}
+
+impl<'a, W: AsyncWrite> Future for SendMessage<'a, W> {
+    type Item = ();
+    type Error = io::Error;
+
+    fn poll(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        loop {
+            match self.state {
+                State::FlushBefore => {
+                    try_ready!(self.writer.poll_flush(cx));
+                    self.state = State::Writing(0);
+                }
+                State::Writing(pos) if pos < self.message.len() => {
+                    let n = try_ready!(self.writer.poll_write(cx, &self.message[pos..]));
+                    if n == 0 {
+                        return Err(io::Error::new(io::ErrorKind::WriteZero,
+                                                  "failed to write whole message"))
+                    }
+                    self.state = State::Writing(pos + n);
+                }
+                State::Writing(_) => self.state = State::FlushAfter,
+                State::FlushAfter => {
+                    try_ready!(self.writer.poll_flush(cx));
+                    self.state = State::Done;
+                    return Ok(Async::Ready(()))
+                }
+                State::Done => panic!("cannot poll SendMessage twice"),
+            }
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use std::io;
+use std::marker::PhantomData;
+use std::vec::Vec;
+
+use {Async, Poll, task};
+use futures_core::Stream;
+use futures_sink::Sink;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json;
+
+use futures_io::{AsyncRead, AsyncWrite};
+
+/// A stream of the values in a newline-delimited JSON document.
+///
+/// Created by the [`json_lines`] method.
+///
+/// [`json_lines`]: trait.AsyncReadExt.html#method.json_lines
+#[derive(Debug)]
+pub struct JsonLines<R, T> {
+    inner: R,
+    buf: Vec<u8>,
+    // The number of bytes at the start of `buf` known not to contain `\n`.
+    searched: usize,
+    skip_blank: bool,
+    eof: bool,
+    _marker: PhantomData<fn() -> T>,
+}
+
+pub fn json_lines<R, T>(inner: R) -> JsonLines<R, T>
+    where R: AsyncRead,
+          T: DeserializeOwned,
+{
+    JsonLines {
+        inner,
+        buf: Vec::new(),
+        searched: 0,
+        skip_blank: true,
+        eof: false,
+        _marker: PhantomData,
+    }
+}
+
+impl<R, T> JsonLines<R, T> {
+    /// Configures whether lines containing only whitespace are skipped, which
+    /// is the default, or yield an `InvalidData` error.
+    pub fn skip_blank_lines(&mut self, skip: bool) -> &mut JsonLines<R, T> {
+        self.skip_blank = skip;
+        self
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this `JsonLines`, returning the underlying reader.
+    ///
+    /// Any buffered data which has not been decoded yet is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn next_line(&mut self) -> Option<Vec<u8>> {
+        match self.buf[self.searched..].iter().position(|&b| b == b'\n') {
+            Some(i) => {
+                let end = self.searched + i;
+                let mut line = self.buf.drain(..end + 1).collect::<Vec<_>>();
+                line.pop();
+                self.searched = 0;
+                Some(line)
+            }
+            None if self.eof && !self.buf.is_empty() => {
+                self.searched = 0;
+                Some(self.buf.drain(..).collect())
+            }
+            None => {
+                self.searched = self.buf.len();
+                None
+            }
+        }
+    }
+}
+
+impl<R, T> Stream for JsonLines<R, T>
+    where R: AsyncRead,
+          T: DeserializeOwned,
+{
+    type Item = T;
+    type Error = io::Error;
+
+    fn poll_next(&mut self, cx: &mut task::Context) -> Poll<Option<T>, io::Error> {
+        loop {
+            if let Some(line) = self.next_line() {
+                if self.skip_blank && line.iter().all(|b| b.is_ascii_whitespace()) {
+                    continue
+                }
+                return serde_json::from_slice(&line)
+                    .map(|value| Async::Ready(Some(value)))
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            }
+
+            if self.eof {
+                return Ok(Async::Ready(None))
+            }
+
+            let mut chunk = [0; 1024];
+            let n = try_ready!(self.inner.poll_read(cx, &mut chunk));
+            if n == 0 {
+                self.eof = true;
+            }
+            self.buf.extend_from_slice(&chunk[..n]);
+        }
+    }
+}
+
+/// A sink which writes each item to a writer as one line of JSON.
+///
+/// Created by the [`json_lines_sink`] function.
+///
+/// [`json_lines_sink`]: fn.json_lines_sink.html
+#[derive(Debug)]
+pub struct JsonLinesSink<W, T> {
+    inner: W,
+    buf: Vec<u8>,
+    pos: usize,
+    _marker: PhantomData<fn(T)>,
+}
+
+/// Creates a sink which serializes each item as JSON followed by a newline
+/// and writes it to `inner`, producing newline-delimited JSON which can be
+/// read back with `json_lines`.
+///
+/// An item which fails to serialize is rejected by `start_send` with an
+/// `InvalidData` error. Each item is written fully before the next one is
+/// accepted.
+///
+/// This function is only available with the `json` feature.
+pub fn json_lines_sink<W, T>(inner: W) -> JsonLinesSink<W, T>
+    where W: AsyncWrite,
+          T: Serialize,
+{
+    JsonLinesSink {
+        inner,
+        buf: Vec::new(),
+        pos: 0,
+        _marker: PhantomData,
+    }
+}
+
+impl<W, T> JsonLinesSink<W, T> {
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes this `JsonLinesSink`, returning the underlying writer.
+    ///
+    /// Any partially written line is lost.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: AsyncWrite, T> JsonLinesSink<W, T> {
+    fn poll_buf(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        while self.pos < self.buf.len() {
+            let n = try_ready!(self.inner.poll_write(cx, &self.buf[self.pos..]));
+            if n == 0 {
+                return Err(io::Error::new(io::ErrorKind::WriteZero,
+                                          "failed to write JSON line"))
+            }
+            self.pos += n;
+        }
+        self.buf.clear();
+        self.pos = 0;
+        Ok(Async::Ready(()))
+    }
+}
+
+impl<W, T> Sink for JsonLinesSink<W, T>
+    where W: AsyncWrite,
+          T: Serialize,
+{
+    type SinkItem = T;
+    type SinkError = io::Error;
+
+    fn poll_ready(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        self.poll_buf(cx)
+    }
+
+    fn start_send(&mut self, item: T) -> Result<(), io::Error> {
+        debug_assert!(self.buf.is_empty());
+        serde_json::to_writer(&mut self.buf, &item).map_err(|e| {
+            self.buf.clear();
+            io::Error::new(io::ErrorKind::InvalidData, e)
+        })?;
+        self.buf.push(b'\n');
+        Ok(())
+    }
+
+    fn poll_flush(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        try_ready!(self.poll_buf(cx));
+        self.inner.poll_flush(cx)
+    }
+
+    fn poll_close(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        try_ready!(self.poll_buf(cx));
+        self.inner.poll_close(cx)
+    }
+}
@@ -0,0 +1,141 @@
+use std::io;
+use std::time::Duration;
+use std::vec::Vec;
+
+use {Async, Future, Poll, task};
+use futures_core::Never;
+
+use futures_io::AsyncWrite;
+
+/// A writer which writes a keepalive frame to the underlying writer whenever
+/// it has been idle for a while.
+///
+/// Created by the [`keepalive_write`] method.
+///
+/// [`keepalive_write`]: trait.AsyncWriteExt.html#method.keepalive_write
+#[derive(Debug)]
+pub struct KeepaliveWriter<W, F, T> {
+    inner: W,
+    interval: Duration,
+    timer: F,
+    delay: T,
+    frame: Vec<u8>,
+    // How much of a keepalive frame in progress has been written.
+    pos: Option<usize>,
+    keepalives: u64,
+}
+
+pub fn keepalive_write<W, F, T>(inner: W, interval: Duration, mut timer: F, frame: Vec<u8>)
+    -> KeepaliveWriter<W, F, T>
+    where W: AsyncWrite,
+          F: FnMut(Duration) -> T,
+          T: Future<Item = (), Error = Never>,
+{
+    KeepaliveWriter {
+        inner,
+        interval,
+        delay: timer(interval),
+        timer,
+        frame,
+        pos: None,
+        keepalives: 0,
+    }
+}
+
+impl<W, F, T> KeepaliveWriter<W, F, T> {
+    /// Returns the number of keepalive frames written so far.
+    pub fn keepalives(&self) -> u64 {
+        self.keepalives
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes this `KeepaliveWriter`, returning the underlying writer.
+    ///
+    /// A partially written keepalive frame is left as it is.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W, F, T> KeepaliveWriter<W, F, T>
+    where W: AsyncWrite,
+          F: FnMut(Duration) -> T,
+          T: Future<Item = (), Error = Never>,
+{
+    /// Drives the idle timer, writing a keepalive frame to the underlying
+    /// writer each time it fires.
+    ///
+    /// This must be polled while the writer is idle, since nothing else
+    /// polls it then, for example by a connection task alongside waiting
+    /// for the next message to write. It returns `Ready(())` each time a
+    /// keepalive frame has been written completely, and `Pending` while
+    /// waiting for the timer or the underlying writer.
+    pub fn poll_keepalive(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        if self.pos.is_none() {
+            match self.delay.poll(cx) {
+                Ok(Async::Ready(())) => self.pos = Some(0),
+                Ok(Async::Pending) => return Ok(Async::Pending),
+                Err(never) => match never {},
+            }
+        }
+        self.poll_frame(cx)
+    }
+
+    /// Finishes writing a keepalive frame in progress, if any, so that it
+    /// isn't interleaved with the application's bytes.
+    fn poll_frame(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        if let Some(mut pos) = self.pos {
+            while pos < self.frame.len() {
+                let n = try_ready!(self.inner.poll_write(cx, &self.frame[pos..]));
+                if n == 0 {
+                    return Err(io::Error::new(io::ErrorKind::WriteZero,
+                                              "failed to write keepalive frame"))
+                }
+                pos += n;
+                self.pos = Some(pos);
+            }
+            self.pos = None;
+            self.keepalives += 1;
+            self.rearm();
+        }
+        Ok(Async::Ready(()))
+    }
+
+    fn rearm(&mut self) {
+        self.delay = (self.timer)(self.interval);
+    }
+}
+
+impl<W, F, T> AsyncWrite for KeepaliveWriter<W, F, T>
+    where W: AsyncWrite,
+          F: FnMut(Duration) -> T,
+          T: Future<Item = (), Error = Never>,
+{
+    fn poll_write(&mut self, cx: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        try_ready!(self.poll_frame(cx));
+        let n = try_ready!(self.inner.poll_write(cx, buf));
+        if n > 0 {
+            self.rearm();
+        }
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_flush(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        try_ready!(self.poll_frame(cx));
+        self.inner.poll_flush(cx)
+    }
+
+    fn poll_close(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        try_ready!(self.poll_frame(cx));
+        self.inner.poll_close(cx)
+    }
+}
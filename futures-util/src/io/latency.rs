@@ -0,0 +1,113 @@
+use std::io;
+use std::time::{Duration, Instant};
+use std::vec::Vec;
+
+use {Async, Poll, task};
+
+use futures_io::AsyncWrite;
+
+/// A writer which records how long the underlying writer takes to accept
+/// each write.
+///
+/// The clock starts when `poll_write` is first called for a buffer and stops
+/// when the underlying writer accepts it, so time spent returning
+/// `Async::Pending` is included. Only completed writes contribute samples;
+/// writes which fail are discarded. Time is obtained from the `now` closure,
+/// which allows a fake clock to be used in tests.
+#[derive(Debug)]
+pub struct LatencyWriter<W, C> {
+    inner: W,
+    now: C,
+    started: Option<Instant>,
+    samples: Vec<Duration>,
+}
+
+impl<W, C> LatencyWriter<W, C>
+    where W: AsyncWrite,
+          C: FnMut() -> Instant,
+{
+    /// Creates a new `LatencyWriter` measuring time with `now`.
+    pub fn new(inner: W, now: C) -> LatencyWriter<W, C> {
+        LatencyWriter {
+            inner,
+            now,
+            started: None,
+            samples: Vec::new(),
+        }
+    }
+
+    /// Returns the recorded latencies, in the order writes completed.
+    pub fn samples(&self) -> &[Duration] {
+        &self.samples
+    }
+
+    /// Returns the latency at each of the given percentiles (between 0 and
+    /// 100), using the nearest-rank method.
+    ///
+    /// Returns an empty vector if no write has completed yet.
+    pub fn percentiles(&self, percentiles: &[f64]) -> Vec<Duration> {
+        if self.samples.is_empty() {
+            return Vec::new()
+        }
+        let mut sorted = self.samples.clone();
+        sorted.sort();
+        let len = sorted.len();
+        percentiles.iter().map(|p| {
+            let rank = (p / 100.0 * len as f64).ceil() as usize;
+            sorted[rank.max(1).min(len) - 1]
+        }).collect()
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes this `LatencyWriter`, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W, C> AsyncWrite for LatencyWriter<W, C>
+    where W: AsyncWrite,
+          C: FnMut() -> Instant,
+{
+    fn poll_write(&mut self, cx: &mut task::Context, buf: &[u8])
+        -> Poll<usize, io::Error>
+    {
+        let started = match self.started {
+            Some(started) => started,
+            None => (self.now)(),
+        };
+        match self.inner.poll_write(cx, buf) {
+            Ok(Async::Ready(n)) => {
+                self.started = None;
+                let elapsed = (self.now)() - started;
+                self.samples.push(elapsed);
+                Ok(Async::Ready(n))
+            }
+            Ok(Async::Pending) => {
+                self.started = Some(started);
+                Ok(Async::Pending)
+            }
+            Err(e) => {
+                self.started = None;
+                Err(e)
+            }
+        }
+    }
+
+    fn poll_flush(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        self.inner.poll_flush(cx)
+    }
+
+    fn poll_close(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        self.inner.poll_close(cx)
+    }
+}
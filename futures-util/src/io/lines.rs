@@ -1,17 +1,21 @@
-use std::io::{self, BufRead};
+use std::io;
 use std::mem;
 use std::string::String;
+use std::vec::Vec;
 
-use {Poll, Stream};
+use {Async, Poll, task};
+use futures_core::Stream;
 
-use io::AsyncRead;
+use futures_io::AsyncBufRead;
 
-/// Combinator created by the top-level `lines` method which is a stream over
-/// the lines of text on an I/O object.
+/// Combinator created by the [`lines`] method which is a stream over the
+/// lines of text on an I/O object.
+///
+/// [`lines`]: trait.AsyncReadExt.html#method.lines
 #[derive(Debug)]
 pub struct Lines<A> {
     io: A,
-    line: String,
+    buf: Vec<u8>,
 }
 
 /// Creates a new stream from the I/O object given representing the lines of
@@ -21,15 +25,25 @@ pub struct Lines<A> {
 /// lines that the object contains. The returned stream will reach its end once
 /// `a` reaches EOF.
 pub fn lines<A>(a: A) -> Lines<A>
-    where A: AsyncRead + BufRead,
+    where A: AsyncBufRead,
 {
     Lines {
         io: a,
-        line: String::new(),
+        buf: Vec::new(),
     }
 }
 
 impl<A> Lines<A> {
+    /// Returns a reference to the underlying I/O object.
+    pub fn get_ref(&self) -> &A {
+        &self.io
+    }
+
+    /// Returns a mutable reference to the underlying I/O object.
+    pub fn get_mut(&mut self) -> &mut A {
+        &mut self.io
+    }
+
     /// Returns the underlying I/O object.
     ///
     /// Note that this may lose data already read into internal buffers. It's
@@ -40,22 +54,45 @@ impl<A> Lines<A> {
 }
 
 impl<A> Stream for Lines<A>
-    where A: AsyncRead + BufRead,
+    where A: AsyncBufRead,
 {
     type Item = String;
     type Error = io::Error;
 
-    fn poll(&mut self, cx: &mut task::Context) -> Poll<Option<String>, io::Error> {
-        let n = try_ready!(self.io.read_line(&mut self.line));
-        if n == 0 && self.line.len() == 0 {
-            return Ok(None.into())
-        }
-        if self.line.ends_with("\n") {
-            self.line.pop();
-            if self.line.ends_with("\r") {
-                self.line.pop();
+    fn poll_next(&mut self, cx: &mut task::Context) -> Poll<Option<String>, io::Error> {
+        loop {
+            let (done, used) = {
+                let available = try_ready!(self.io.poll_fill_buf(cx));
+                match available.iter().position(|&b| b == b'\n') {
+                    Some(i) => {
+                        self.buf.extend_from_slice(&available[..i + 1]);
+                        (true, i + 1)
+                    }
+                    None => {
+                        self.buf.extend_from_slice(available);
+                        (available.is_empty(), available.len())
+                    }
+                }
+            };
+            self.io.consume(used);
+            if !done {
+                continue
+            }
+
+            if used == 0 && self.buf.is_empty() {
+                return Ok(Async::Ready(None))
+            }
+            if self.buf.ends_with(b"\n") {
+                self.buf.pop();
+                if self.buf.ends_with(b"\r") {
+                    self.buf.pop();
+                }
             }
+            let line = mem::replace(&mut self.buf, Vec::new());
+            let line = String::from_utf8(line).map_err(|_| {
+                io::Error::new(io::ErrorKind::InvalidData, "stream did not contain valid UTF-8")
+            })?;
+            return Ok(Async::Ready(Some(line)))
         }
-        Ok(Some(mem::replace(&mut self.line, String::new())).into())
     }
 }
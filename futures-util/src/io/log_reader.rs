@@ -0,0 +1,131 @@
+use std::io;
+use std::vec::Vec;
+
+use {Async, Poll, task};
+use futures_core::Stream;
+
+use futures_io::AsyncRead;
+
+use super::log_writer::HEADER_LEN;
+
+/// A stream of the `(sequence, timestamp, payload)` events in a log written
+/// by `LogWriter`.
+///
+/// Created by the [`dechunk_log`] method.
+///
+/// [`dechunk_log`]: trait.AsyncReadExt.html#method.dechunk_log
+#[derive(Debug)]
+pub struct LogReader<R> {
+    inner: R,
+    buf: Vec<u8>,
+    expected: Option<u64>,
+    gaps: u64,
+    error_on_gap: bool,
+    eof: bool,
+}
+
+pub fn dechunk_log<R>(inner: R) -> LogReader<R>
+    where R: AsyncRead,
+{
+    LogReader {
+        inner,
+        buf: Vec::new(),
+        expected: None,
+        gaps: 0,
+        error_on_gap: false,
+        eof: false,
+    }
+}
+
+fn get_be(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0, |n, &b| (n << 8) | b as u64)
+}
+
+impl<R> LogReader<R> {
+    /// Configures whether a gap in sequence numbers makes the stream yield an
+    /// `InvalidData` error. By default gaps are only counted.
+    pub fn set_error_on_gap(&mut self, error_on_gap: bool) -> &mut LogReader<R> {
+        self.error_on_gap = error_on_gap;
+        self
+    }
+
+    /// Returns the number of gaps in sequence numbers seen so far.
+    pub fn gaps(&self) -> u64 {
+        self.gaps
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this `LogReader`, returning the underlying reader.
+    ///
+    /// Any buffered data which has not been decoded yet is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn decode(&mut self) -> Option<(u64, u64, Vec<u8>)> {
+        if self.buf.len() < HEADER_LEN {
+            return None
+        }
+        let len = get_be(&self.buf[16..HEADER_LEN]) as usize;
+        if self.buf.len() < HEADER_LEN + len {
+            return None
+        }
+        let seq = get_be(&self.buf[..8]);
+        let ts = get_be(&self.buf[8..16]);
+        let payload = self.buf[HEADER_LEN..HEADER_LEN + len].to_vec();
+        self.buf.drain(..HEADER_LEN + len);
+        Some((seq, ts, payload))
+    }
+}
+
+impl<R: AsyncRead> Stream for LogReader<R> {
+    type Item = (u64, u64, Vec<u8>);
+    type Error = io::Error;
+
+    fn poll_next(&mut self, cx: &mut task::Context)
+        -> Poll<Option<(u64, u64, Vec<u8>)>, io::Error>
+    {
+        loop {
+            if let Some((seq, ts, payload)) = self.decode() {
+                let gap = match self.expected {
+                    Some(expected) => seq != expected,
+                    None => false,
+                };
+                self.expected = Some(seq.wrapping_add(1));
+                if gap {
+                    self.gaps += 1;
+                    if self.error_on_gap {
+                        return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                                  "gap in log sequence numbers"))
+                    }
+                }
+                return Ok(Async::Ready(Some((seq, ts, payload))))
+            }
+
+            if self.eof {
+                if self.buf.is_empty() {
+                    return Ok(Async::Ready(None))
+                }
+                self.buf.clear();
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof,
+                                          "log ended in the middle of a frame"))
+            }
+
+            let mut chunk = [0; 1024];
+            let n = try_ready!(self.inner.poll_read(cx, &mut chunk));
+            if n == 0 {
+                self.eof = true;
+            }
+            self.buf.extend_from_slice(&chunk[..n]);
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use std::cmp;
+use std::io;
+use std::u32;
+use std::vec::Vec;
+
+use {Async, Poll, task};
+
+use futures_io::AsyncWrite;
+
+// Sequence number, timestamp and payload length.
+pub(crate) const HEADER_LEN: usize = 8 + 8 + 4;
+
+/// A writer which turns every write into a self-describing event log frame.
+///
+/// Each call to `poll_write` becomes one frame made of a big-endian `u64`
+/// sequence number, a big-endian `u64` timestamp obtained from the `now`
+/// closure, a big-endian `u32` payload length and the payload itself.
+/// Sequence numbers increase by one for every frame. Frames can be read back
+/// with `LogReader`.
+///
+/// A frame is accepted as a whole and then written out across as many calls
+/// to the underlying writer as needed, before the next frame is started.
+#[derive(Debug)]
+pub struct LogWriter<W, F> {
+    inner: W,
+    now: F,
+    seq: u64,
+    frame: Vec<u8>,
+    pos: usize,
+}
+
+impl<W, F> LogWriter<W, F>
+    where W: AsyncWrite,
+          F: FnMut() -> u64,
+{
+    /// Creates a new `LogWriter` whose first frame has sequence number 0.
+    pub fn new(inner: W, now: F) -> LogWriter<W, F> {
+        LogWriter::with_sequence(inner, 0, now)
+    }
+
+    /// Creates a new `LogWriter` whose first frame has sequence number `seq`,
+    /// for resuming an existing log.
+    pub fn with_sequence(inner: W, seq: u64, now: F) -> LogWriter<W, F> {
+        LogWriter {
+            inner,
+            now,
+            seq,
+            frame: Vec::new(),
+            pos: 0,
+        }
+    }
+
+    /// Returns the sequence number the next frame will be written with.
+    pub fn next_sequence(&self) -> u64 {
+        self.seq
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// Note that writing to the underlying writer directly may corrupt the
+    /// framing of the log.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes this `LogWriter`, returning the underlying writer.
+    ///
+    /// Any partially written frame is lost.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    fn poll_frame(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        while self.pos < self.frame.len() {
+            let n = try_ready!(self.inner.poll_write(cx, &self.frame[self.pos..]));
+            if n == 0 {
+                return Err(io::Error::new(io::ErrorKind::WriteZero,
+                                          "failed to write log frame"))
+            }
+            self.pos += n;
+        }
+        self.frame.clear();
+        self.pos = 0;
+        Ok(Async::Ready(()))
+    }
+}
+
+fn put_u64(buf: &mut Vec<u8>, n: u64) {
+    for i in (0..8).rev() {
+        buf.push((n >> (i * 8)) as u8);
+    }
+}
+
+impl<W, F> AsyncWrite for LogWriter<W, F>
+    where W: AsyncWrite,
+          F: FnMut() -> u64,
+{
+    fn poll_write(&mut self, cx: &mut task::Context, buf: &[u8])
+        -> Poll<usize, io::Error>
+    {
+        try_ready!(self.poll_frame(cx));
+        if buf.is_empty() {
+            return Ok(Async::Ready(0))
+        }
+
+        let len = cmp::min(buf.len(), u32::MAX as usize);
+        self.frame.reserve(HEADER_LEN + len);
+        put_u64(&mut self.frame, self.seq);
+        put_u64(&mut self.frame, (self.now)());
+        for i in (0..4).rev() {
+            self.frame.push((len >> (i * 8)) as u8);
+        }
+        self.frame.extend_from_slice(&buf[..len]);
+        self.seq += 1;
+
+        // The frame is ours now; try to get it going, but any error or
+        // `Pending` is reported by the next call instead.
+        let _ = self.poll_frame(cx);
+        Ok(Async::Ready(len))
+    }
+
+    fn poll_flush(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        try_ready!(self.poll_frame(cx));
+        self.inner.poll_flush(cx)
+    }
+
+    fn poll_close(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        try_ready!(self.poll_frame(cx));
+        self.inner.poll_close(cx)
+    }
+}
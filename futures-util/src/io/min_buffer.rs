@@ -0,0 +1,58 @@
+use std::io;
+
+use {Poll, task};
+
+use futures_io::{AsyncRead, Initializer};
+
+/// A reader which refuses reads into buffers smaller than a minimum size.
+///
+/// Created by the [`require_min_buffer`] method.
+///
+/// [`require_min_buffer`]: trait.AsyncReadExt.html#method.require_min_buffer
+#[derive(Debug)]
+pub struct RequireMinBuffer<R> {
+    inner: R,
+    min: usize,
+}
+
+pub fn require_min_buffer<R: AsyncRead>(inner: R, min: usize) -> RequireMinBuffer<R> {
+    RequireMinBuffer { inner, min }
+}
+
+impl<R> RequireMinBuffer<R> {
+    /// Returns the smallest buffer size accepted.
+    pub fn min(&self) -> usize {
+        self.min
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this `RequireMinBuffer`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for RequireMinBuffer<R> {
+    unsafe fn initializer(&self) -> Initializer {
+        self.inner.initializer()
+    }
+
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        if buf.len() < self.min {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                      "read buffer is smaller than the required minimum"))
+        }
+        self.inner.poll_read(cx, buf)
+    }
+}
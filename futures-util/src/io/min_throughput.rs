@@ -0,0 +1,146 @@
+use std::collections::VecDeque;
+use std::io;
+use std::time::{Duration, Instant};
+
+use {Async, Future, Poll, task};
+use futures_core::Never;
+
+use futures_io::{AsyncRead, Initializer};
+
+/// An `AsyncRead` which fails once the throughput of the underlying reader
+/// drops below a minimum rate.
+///
+/// Created by the [`min_throughput`] method.
+///
+/// [`min_throughput`]: trait.AsyncReadExt.html#method.min_throughput
+#[derive(Debug)]
+pub struct MinThroughput<R, C, F, T> {
+    inner: R,
+    now: C,
+    timer: F,
+    // Started by the first read which has to wait, to detect a reader which
+    // stalls completely and so is never polled again.
+    stall: Option<T>,
+    required: f64,
+    window: Duration,
+    started: Option<Instant>,
+    reads: VecDeque<(Instant, usize)>,
+    eof: bool,
+}
+
+pub fn min_throughput<R, C, F, T>(inner: R, bytes_per_sec: u64, window: Duration, now: C,
+                                  timer: F)
+    -> MinThroughput<R, C, F, T>
+    where R: AsyncRead,
+          C: FnMut() -> Instant,
+          F: FnMut(Duration) -> T,
+          T: Future<Item = (), Error = Never>,
+{
+    let secs = window.as_secs() as f64 + window.subsec_nanos() as f64 / 1e9;
+    MinThroughput {
+        inner,
+        now,
+        timer,
+        stall: None,
+        required: bytes_per_sec as f64 * secs,
+        window,
+        started: None,
+        reads: VecDeque::new(),
+        eof: false,
+    }
+}
+
+impl<R, C, F, T> MinThroughput<R, C, F, T> {
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this `MinThroughput`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn check(&mut self, now: Instant) -> Result<(), io::Error> {
+        let started = *self.started.get_or_insert(now);
+        if now - started < self.window {
+            return Ok(())
+        }
+        while let Some(&(at, _)) = self.reads.front() {
+            if now - at < self.window {
+                break
+            }
+            self.reads.pop_front();
+        }
+        let read = self.reads.iter().map(|&(_, n)| n).sum::<usize>();
+        if (read as f64) < self.required {
+            return Err(too_slow())
+        }
+        Ok(())
+    }
+}
+
+fn too_slow() -> io::Error {
+    io::Error::new(io::ErrorKind::TimedOut, "read throughput dropped below the minimum")
+}
+
+impl<R, C, F, T> AsyncRead for MinThroughput<R, C, F, T>
+    where R: AsyncRead,
+          C: FnMut() -> Instant,
+          F: FnMut(Duration) -> T,
+          T: Future<Item = (), Error = Never>,
+{
+    unsafe fn initializer(&self) -> Initializer {
+        self.inner.initializer()
+    }
+
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        if self.eof {
+            return self.inner.poll_read(cx, buf)
+        }
+        let now = (self.now)();
+        let res = self.inner.poll_read(cx, buf);
+        match res {
+            Ok(Async::Ready(0)) if !buf.is_empty() => {
+                self.eof = true;
+                self.stall = None;
+                return res
+            }
+            Ok(Async::Ready(n)) => {
+                self.reads.push_back((now, n));
+                self.stall = None;
+            }
+            Ok(Async::Pending) => {}
+            Err(e) => {
+                self.stall = None;
+                return Err(e)
+            }
+        }
+        self.check(now)?;
+
+        // Nothing for a whole window is too slow for any positive rate.
+        if let Ok(Async::Pending) = res {
+            if self.required > 0.0 {
+                let window = self.window;
+                let timer = &mut self.timer;
+                let stall = self.stall.get_or_insert_with(|| timer(window));
+                match stall.poll(cx) {
+                    Ok(Async::Ready(())) => {
+                        self.stall = None;
+                        return Err(too_slow())
+                    }
+                    Ok(Async::Pending) => {}
+                    Err(never) => match never {},
+                }
+            }
+        }
+        res
+    }
+}
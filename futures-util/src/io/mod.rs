@@ -6,37 +6,192 @@
 //! to the `AsyncRead` and `AsyncWrite` types.
 
 
+use std::collections::HashMap;
+use std::string::String;
+use std::time::{Duration, Instant};
 use std::vec::Vec;
 
-pub use futures_io::{AsyncRead, AsyncWrite, IoVec};
+use futures_core::{Future, Never};
+use futures_core::task;
 
+pub use futures_io::{AsyncBufRead, AsyncRead, AsyncReadable, AsyncSeek, AsyncWritable,
+                     AsyncWrite, IoVec};
+
+pub use self::ack_on_flush::AckOnFlush;
 pub use self::allow_std::AllowStdIo;
+pub use self::assert_reads::AssertNondecreasingReads;
+pub use self::at_eof::AtEof;
+pub use self::atomic_frame::{AtomicFrameWriter, CloseFrames, FlushFrames, SendFrame};
+pub use self::bounded_concat::{bounded_concat, BoundedConcat};
+pub use self::bounded_unflushed::BoundedUnflushed;
+pub use self::budget_write::BudgetWrite;
+pub use self::chain::Chain;
+pub use self::collect_pairs::{CollectPairs, DuplicateKeys};
+pub use self::content_length::ContentLength;
+pub use self::copy::{copy, Copy};
 pub use self::copy_into::CopyInto;
+pub use self::cork::{AsyncCork, CorkWriter};
+pub use self::count::{CountRead, CountWrite};
+pub use self::crc_trailer::CrcTrailerWriter;
+pub use self::datagram::DatagramWriter;
+pub use self::dedup_bytes::DedupBytes;
+#[cfg(feature = "deflate")]
+pub use self::deflate::{deflate_write, inflate_read, DeflateWriter, InflateReader};
+pub use self::demux::DemuxReader;
+pub use self::detect_format::{Detected, DetectFormat};
+pub use self::expect_prefix::ExpectPrefix;
+pub use self::fenced_writer::FencedWriter;
+pub use self::finalize_once::FinalizeOnce;
 pub use self::flush::Flush;
+pub use self::from_bytes::from_bytes;
+pub use self::fuse::Fuse;
+pub use self::fuse_writer::FuseWriter;
+pub use self::gather::GatherUntilClose;
+pub use self::group_flush::{GroupFlushWriter, WriteAndFlush};
+pub use self::hooked::{Hooked, ReadOutcome};
+pub use self::inspect::{Inspect, InspectWrite};
+pub use self::inspect_eof::InspectEofPosition;
+pub use self::isolate_messages::{IsolateMessages, SendMessage};
+#[cfg(feature = "json")]
+pub use self::json_lines::{json_lines_sink, JsonLines, JsonLinesSink};
+pub use self::keepalive::KeepaliveWriter;
+pub use self::latency::LatencyWriter;
+pub use self::lines::Lines;
+pub use self::log_reader::LogReader;
+pub use self::log_writer::LogWriter;
+pub use self::min_buffer::RequireMinBuffer;
+pub use self::min_throughput::MinThroughput;
+pub use self::newline_convert::NewlineConvertWriter;
+pub use self::pipe::{pipe, PipeReader, PipeWriter};
+pub use self::position::{PositionReader, PositionWriter};
+pub use self::quota::{Quota, QuotaReader};
 pub use self::read::Read;
+pub use self::read_all::ReadAll;
+pub use self::read_crlf_line::ReadCrlfLine;
 pub use self::read_exact::ReadExact;
+pub use self::read_records::ReadNRecords;
 pub use self::read_to_end::ReadToEnd;
 pub use self::close::Close;
-pub use self::split::{ReadHalf, WriteHalf};
+pub use self::read_to_string::ReadToString;
+pub use self::read_until::ReadUntil;
+pub use self::readable::Readable;
+pub use self::reorder::ReorderReader;
+pub use self::replayable::{Replay, Replayable};
+pub use self::result_frames::ResultFrames;
+pub use self::retry_read::{RetryPolicy, RetryRead};
+pub use self::rotating::RotatingWriter;
+pub use self::sharded::{sharded_write, SendShard, ShardedWriter};
+pub use self::shuffle_reads::ShuffleReads;
+pub use self::sniff_prefix::SniffPrefix;
+pub use self::splice_from::SpliceFrom;
+pub use self::split::{reunite, ReadHalf, ReuniteError, WriteHalf};
+pub use self::split_at_marker::{MarkerBody, SplitAtMarker};
+pub use self::struct_reader::{ReadStruct, StructReader};
+pub use self::take::Take;
+pub use self::tee::Tee;
+pub use self::tee_to::TeeTo;
+pub use self::throttle::Throttle;
+pub use self::timeout::Timeout;
+pub use self::until_cancelled::{CancellationToken, UntilCancelled};
+pub use self::varint_delimited::{varint_delimited_sink, VarintDelimited, VarintDelimitedSink};
+pub use self::watermark::{Watermark, WatermarkWriter};
 pub use self::window::Window;
+pub use self::with_permit::{Semaphore, SemaphorePermit, WithPermit};
+pub use self::writable::Writable;
 pub use self::write_all::WriteAll;
+pub use self::write_all_timeout::WriteAllTimeout;
+pub use self::write_all_tolerant::WriteAllTolerant;
+pub use self::write_joined::WriteJoined;
+pub use self::xor_stream::{XorStreamReader, XorStreamWriter};
 
-// Temporarily removed until AsyncBufRead is implemented
-// pub use io::lines::{lines, Lines};
-// pub use io::read_until::{read_until, ReadUntil};
-// mod lines;
-// mod read_until;
-
+mod ack_on_flush;
 mod allow_std;
+mod assert_reads;
+mod at_eof;
+mod atomic_frame;
+mod bounded_concat;
+mod bounded_unflushed;
+#[cfg(feature = "bytes")]
+mod buf;
+mod budget_write;
+mod chain;
+mod collect_pairs;
+mod content_length;
+mod copy;
 mod copy_into;
+mod cork;
+mod count;
+mod crc_trailer;
+mod datagram;
+mod dedup_bytes;
+#[cfg(feature = "deflate")]
+mod deflate;
+mod demux;
+mod detect_format;
+mod expect_prefix;
+mod fenced_writer;
+mod finalize_once;
 mod flush;
+mod from_bytes;
+mod fuse;
+mod fuse_writer;
+mod gather;
+mod group_flush;
+mod hooked;
+mod inspect;
+mod inspect_eof;
+mod isolate_messages;
+#[cfg(feature = "json")]
+mod json_lines;
+mod keepalive;
+mod latency;
+mod lines;
+mod log_reader;
+mod log_writer;
+mod min_buffer;
+mod min_throughput;
+mod newline_convert;
+mod pipe;
+mod position;
+mod quota;
 mod read;
+mod read_all;
+mod read_crlf_line;
 mod read_exact;
+mod read_records;
 mod read_to_end;
 mod close;
+mod read_to_string;
+mod read_until;
+mod readable;
+mod reorder;
+mod replayable;
+mod result_frames;
+mod retry_read;
+mod rotating;
+mod sharded;
+mod shuffle_reads;
+mod sniff_prefix;
+mod splice_from;
 mod split;
+mod split_at_marker;
+mod struct_reader;
+mod take;
+mod tee;
+mod tee_to;
+mod throttle;
+mod timeout;
+mod until_cancelled;
+mod varint_delimited;
+mod watermark;
 mod window;
+mod with_permit;
+mod writable;
 mod write_all;
+mod write_all_timeout;
+mod write_all_tolerant;
+mod write_joined;
+mod xor_stream;
 
 /// An extension trait which adds utility methods to `AsyncRead` types.
 pub trait AsyncReadExt: AsyncRead {
@@ -60,98 +215,957 @@ pub trait AsyncReadExt: AsyncRead {
     /// Tries to read some bytes directly into the given `buf` in asynchronous
     /// manner, returning a future type.
     ///
-    /// The returned future will resolve to both the I/O stream and the buffer
-    /// as well as the number of bytes read once the read operation is completed.
-    fn read<T>(self, buf: T) -> Read<Self, T>
-        where T: AsMut<[u8]>,
-              Self: Sized,
-    {
+    /// The returned future will resolve to the number of bytes read once the
+    /// read operation is completed. An empty `buf` resolves right away with a
+    /// count of zero, without polling the reader.
+    ///
+    /// Each poll of the future just polls `poll_read` again, so dropping it
+    /// before it completes leaves the reader untouched.
+    fn read<'a>(&'a mut self, buf: &'a mut [u8]) -> Read<'a, Self> {
         read::read(self, buf)
     }
 
-
     /// Creates a future which will read exactly enough bytes to fill `buf`,
     /// returning an error if EOF is hit sooner.
     ///
-    /// The returned future will resolve to both the I/O stream as well as the
-    /// buffer once the read operation is completed.
-    ///
-    /// In the case of an error the buffer and the object will be discarded, with
-    /// the error yielded. In the case of success the object will be destroyed and
-    /// the buffer will be returned, with all data read from the stream appended to
-    /// the buffer.
-    fn read_exact<T>(self, buf: T) -> ReadExact<Self, T>
-        where T: AsMut<[u8]>,
-              Self: Sized,
-    {
+    /// The returned future will resolve to `()` once the read operation is
+    /// completed, re-issuing reads against the unfilled tail of `buf` as
+    /// often as it takes. If the reader hits EOF first, the future fails
+    /// with an `UnexpectedEof` error and the contents of `buf` are
+    /// unspecified.
+    fn read_exact<'a>(&'a mut self, buf: &'a mut [u8]) -> ReadExact<'a, Self> {
         read_exact::read_exact(self, buf)
     }
 
-    /// Creates a future which will read all the bytes from this `AsyncRead`.
+    /// Creates a future which will read all the bytes from this `AsyncRead`
+    /// and append them to `buf`.
     ///
-    /// In the case of an error the buffer and the object will be discarded, with
-    /// the error yielded. In the case of success the object will be destroyed and
-    /// the buffer will be returned, with all data read from the stream appended to
-    /// the buffer.
-    fn read_to_end(self, buf: Vec<u8>) -> ReadToEnd<Self>
-        where Self: Sized,
-    {
+    /// The returned future resolves to the number of bytes appended by this
+    /// call, not the final length of `buf`. Bytes read before a `Pending` or
+    /// an error stay in `buf`. Newly reserved space is only zeroed before
+    /// being read into if the reader's `initializer` asks for it.
+    fn read_to_end<'a>(&'a mut self, buf: &'a mut Vec<u8>) -> ReadToEnd<'a, Self> {
         read_to_end::read_to_end(self, buf)
     }
 
     /// Helper method for splitting this read/write object into two halves.
     ///
-    /// The two halves returned implement the `Read` and `Write` traits,
-    /// respectively.
+    /// The two halves returned implement the `AsyncRead` and `AsyncWrite`
+    /// traits, respectively. They share ownership of this object, which is
+    /// only locked while one of them is being polled, so they can be handed
+    /// to different tasks. Dropping one half leaves the other fully usable,
+    /// and `reunite` puts the two back together.
     fn split(self) -> (ReadHalf<Self>, WriteHalf<Self>)
         where Self: AsyncWrite + Sized,
     {
         split::split(self)
     }
+
+    /// Creates an adapter which verifies that this `AsyncRead` starts with
+    /// exactly the bytes in `prefix`, such as the magic number of a binary
+    /// format.
+    ///
+    /// The prefix is consumed by the first read on the returned adapter, and
+    /// the rest of the stream is then passed through unchanged. If the stream
+    /// contains anything else the read fails with an `InvalidData` error, and
+    /// if it ends early with an `UnexpectedEof` error.
+    fn expect_prefix(self, prefix: &[u8]) -> ExpectPrefix<Self>
+        where Self: Sized,
+    {
+        expect_prefix::expect_prefix(self, prefix)
+    }
+
+    /// Creates a future which resolves once this reader has at least one byte
+    /// available (or has reached EOF), as reported by
+    /// `AsyncReadable::poll_readable`.
+    ///
+    /// No data is consumed, so consumers can use this to put off expensive
+    /// setup until there is actually something to read.
+    fn readable<'a>(&'a mut self) -> Readable<'a, Self>
+        where Self: AsyncReadable,
+    {
+        readable::readable(self)
+    }
+
+    /// Creates an adapter which returns the bytes of this reader in small
+    /// groups whose order has been shuffled, for testing robustness.
+    ///
+    /// Each read is limited to a small window of bytes which is shuffled with
+    /// a pseudo-random generator seeded by `seed`. The overall multiset of
+    /// bytes is preserved, but their order is not.
+    ///
+    /// This deliberately violates the ordering guarantees of a byte stream.
+    /// It is only intended for tests, to catch parsers which wrongly make
+    /// assumptions about the order of bytes within a buffer.
+    fn shuffle_reads(self, seed: u64) -> ShuffleReads<Self>
+        where Self: Sized,
+    {
+        shuffle_reads::shuffle_reads(self, seed)
+    }
+
+    /// Creates a stream of the `(sequence, timestamp, payload)` events in
+    /// a log written by `LogWriter`.
+    ///
+    /// Frames may be split across any number of reads. If the log ends in the
+    /// middle of a frame the stream yields an `UnexpectedEof` error. Gaps in
+    /// sequence numbers are counted by `LogReader::gaps`, and can optionally
+    /// be turned into errors with `LogReader::set_error_on_gap`.
+    fn dechunk_log(self) -> LogReader<Self>
+        where Self: Sized,
+    {
+        log_reader::dechunk_log(self)
+    }
+
+    /// Creates an adapter which fails with a `TimedOut` error once this
+    /// reader delivers fewer than `bytes_per_sec` bytes per second, measured
+    /// over a sliding `window`, such as to defend against slowloris-style
+    /// attacks.
+    ///
+    /// Time is obtained from the `now` closure, which allows a fake clock to
+    /// be used in tests. Throughput is checked whenever the adapter is
+    /// polled, but not within the first `window` after the first read. Once
+    /// EOF has been reached reads are passed through unchecked.
+    ///
+    /// A reader which stops delivering anything at all doesn't wake the task
+    /// to be checked, so a read which has to wait also starts a timer, the
+    /// future obtained by calling `timer` with `window`, as for `timeout`. If
+    /// it fires before the reader returns anything, the read fails with a
+    /// `TimedOut` error.
+    fn min_throughput<C, F, T>(self, bytes_per_sec: u64, window: Duration, now: C,
+                               timer: F)
+        -> MinThroughput<Self, C, F, T>
+        where C: FnMut() -> Instant,
+              F: FnMut(Duration) -> T,
+              T: Future<Item = (), Error = Never>,
+              Self: Sized,
+    {
+        min_throughput::min_throughput(self, bytes_per_sec, window, now, timer)
+    }
+
+    /// Creates an adapter which retries reads failing with transient errors,
+    /// such as a `ConnectionReset`, according to `policy`.
+    ///
+    /// Before each retry the adapter waits for a delay future obtained by
+    /// calling `timer` with the backoff prescribed by `policy`, which keeps
+    /// this crate independent of any particular timer implementation. Once
+    /// the retries are exhausted the last error is returned. The retry count
+    /// is reset by every successful read.
+    fn retry_read<F, T>(self, policy: RetryPolicy, timer: F) -> RetryRead<Self, F, T>
+        where F: FnMut(Duration) -> T,
+              T: Future<Item = (), Error = Never>,
+              Self: Sized,
+    {
+        retry_read::retry_read(self, policy, timer)
+    }
+
+    /// Creates a future which will read all the bytes from this `AsyncRead`
+    /// into a new vector.
+    ///
+    /// Unlike `read_to_end`, the future owns the reader, and on success
+    /// resolves to both the reader and the collected bytes, so the reader can
+    /// be reused afterwards, for example to read the next message on a
+    /// keep-alive connection.
+    fn read_all(self) -> ReadAll<Self>
+        where Self: Sized,
+    {
+        read_all::read_all(self)
+    }
+
+    /// Creates a future which reads all the remaining bytes of this reader
+    /// and appends them to `buf`, resolving to the number of bytes read.
+    ///
+    /// The bytes are validated as UTF-8 once EOF has been reached. If they
+    /// aren't valid the future fails with an `InvalidData` error and nothing
+    /// is appended to `buf`; the bytes read are lost.
+    fn read_to_string<'a>(&'a mut self, buf: &'a mut String) -> ReadToString<'a, Self> {
+        read_to_string::read_to_string(self, buf)
+    }
+
+    /// Splits a multiplexed stream into one reader per tag.
+    ///
+    /// The stream is expected to consist of frames made of a tag byte, a
+    /// big-endian `u32` payload length and the payload itself. The payload of
+    /// each frame is routed to the reader for its tag, which is found in the
+    /// returned map. A frame with a tag not in `tags` is an `InvalidData`
+    /// error. A tag listed more than once still gets a single reader.
+    ///
+    /// Reading from any of the returned readers drives the underlying stream.
+    /// If the buffer for a tag fills up, no further data is read until the
+    /// reader for that tag drains it, so an idle reader eventually stalls the
+    /// others. Dropping a reader discards the frames for its tag from then on
+    /// instead.
+    fn demux(self, tags: &[u8]) -> HashMap<u8, DemuxReader<Self>>
+        where Self: Sized,
+    {
+        demux::demux(self, tags)
+    }
+
+    /// Wraps this reader so that `before` is called before and `after` is
+    /// called after every read of it.
+    ///
+    /// The `after` hook is given the outcome of the read, including reads
+    /// which returned `Pending`. This is a convenient place to hook in
+    /// tracing or metrics without changing the reader itself.
+    fn hooked<B, A>(self, before: B, after: A) -> Hooked<Self, B, A>
+        where B: FnMut(&mut task::Context),
+              A: FnMut(ReadOutcome),
+              Self: Sized,
+    {
+        hooked::hooked(self, before, after)
+    }
+
+    /// Creates a future which reads one line terminated by `\r\n` into `buf`.
+    ///
+    /// The line is appended to `buf` without its terminator, and the future
+    /// resolves to the number of bytes read including the terminator, so an
+    /// empty line yields 2. At EOF before any byte of a new line it resolves
+    /// to 0. A bare `\n` or `\r` is an `InvalidData`
+    /// error, as required by strict CRLF protocols such as HTTP/1.1 headers,
+    /// and EOF in the middle of a line is an `UnexpectedEof` error.
+    ///
+    /// Bytes are read one at a time so that nothing after the terminator is
+    /// consumed, so this is best used on a buffered reader.
+    fn read_crlf_line<'a>(&'a mut self, buf: &'a mut Vec<u8>) -> ReadCrlfLine<'a, Self> {
+        read_crlf_line::read_crlf_line(self, buf)
+    }
+
+    /// Decodes this reader into a stream of frames which are each either a
+    /// success or an application-level error.
+    ///
+    /// `decode` is called with the bytes read so far. If they start with a
+    /// complete frame it removes that frame from the buffer and returns
+    /// `Some(Ok(item))` or `Some(Err(app_error))`; otherwise it returns
+    /// `None` and more data is read. Application errors are yielded as items
+    /// and don't end the stream, whereas I/O errors from the reader are
+    /// reported as errors of the stream itself. If the reader ends in the
+    /// middle of a frame the stream yields an `UnexpectedEof` error.
+    fn result_frames<D, T, E>(self, decode: D) -> ResultFrames<Self, D>
+        where D: FnMut(&mut Vec<u8>) -> Option<Result<T, E>>,
+              Self: Sized,
+    {
+        result_frames::result_frames(self, decode)
+    }
+
+    /// Wraps this reader so that it is never polled again after it has
+    /// reached EOF or returned an error.
+    ///
+    /// Once that has happened every further read returns `Ready(0)` without
+    /// touching the underlying reader, which protects against readers that
+    /// misbehave when polled after they are done. This is the byte reader
+    /// counterpart of `StreamExt::fuse`.
+    fn fuse(self) -> Fuse<Self>
+        where Self: Sized,
+    {
+        fuse::fuse(self)
+    }
+
+    /// Decodes this reader as newline-delimited JSON, yielding one value of
+    /// type `T` per line.
+    ///
+    /// A line which isn't valid JSON for `T` yields an `InvalidData` error.
+    /// Blank lines are skipped by default; see
+    /// [`JsonLines::skip_blank_lines`]. A final line without a trailing
+    /// newline is decoded too.
+    ///
+    /// This method is only available with the `json` feature.
+    ///
+    /// [`JsonLines::skip_blank_lines`]: struct.JsonLines.html#method.skip_blank_lines
+    #[cfg(feature = "json")]
+    fn json_lines<T>(self) -> JsonLines<Self, T>
+        where T: ::serde::de::DeserializeOwned,
+              Self: Sized,
+    {
+        json_lines::json_lines(self)
+    }
+
+    /// Creates a future which reads exactly `count` records of `size` bytes
+    /// each, resolving to a vector of the records.
+    ///
+    /// If the stream ends before `count * size` bytes have been read the
+    /// future fails with an `UnexpectedEof` error, like `read_exact`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count * size` overflows `usize`.
+    fn read_n_records<'a>(&'a mut self, count: usize, size: usize) -> ReadNRecords<'a, Self> {
+        read_records::read_n_records(self, count, size)
+    }
+
+    /// Wraps this reader so that each read holds a permit from `semaphore`.
+    ///
+    /// A read first acquires a permit, returning `Pending` until one is
+    /// available, and releases it as soon as the underlying read returns.
+    /// Sharing one semaphore between many readers bounds how many of them
+    /// perform I/O at the same time.
+    fn with_permit(self, semaphore: Semaphore) -> WithPermit<Self>
+        where Self: Sized,
+    {
+        with_permit::with_permit(self, semaphore)
+    }
+
+    /// Wraps this reader so that it fails with an `Interrupted` error once
+    /// `token` is cancelled.
+    ///
+    /// Reads are passed through until then. A read which is pending when the
+    /// token is cancelled is woken up and fails right away instead of waiting
+    /// for the underlying reader, as does every read after that. Cloning the
+    /// token lets one `cancel` call stop any number of readers.
+    fn until_cancelled(self, token: CancellationToken) -> UntilCancelled<Self>
+        where Self: Sized,
+    {
+        until_cancelled::until_cancelled(self, token)
+    }
+
+    /// Wraps this reader so that runs of identical consecutive bytes are
+    /// collapsed into a single byte, such as `aaabbbc` into `abc`.
+    ///
+    /// The last byte yielded is remembered across reads, so a run split
+    /// between two reads of the underlying reader is still collapsed.
+    fn dedup_bytes(self) -> DedupBytes<Self>
+        where Self: Sized,
+    {
+        dedup_bytes::dedup_bytes(self)
+    }
+
+    /// Wraps this reader so that the first `n` bytes of the stream are passed
+    /// to `sniff` before any of them are yielded, such as for detecting the
+    /// content type or logging.
+    ///
+    /// The first read buffers the prefix and calls `sniff` with it once.
+    /// Unlike a peek, nothing is lost: the buffered prefix is yielded first,
+    /// followed by the rest of the stream unchanged. If the stream ends
+    /// before `n` bytes, `sniff` is called with what there is.
+    fn sniff_prefix<F>(self, n: usize, sniff: F) -> SniffPrefix<Self, F>
+        where F: FnOnce(&[u8]),
+              Self: Sized,
+    {
+        sniff_prefix::sniff_prefix(self, n, sniff)
+    }
+
+    /// Wraps this reader so that the stream has to be exactly `expected`
+    /// bytes long, like an HTTP body with a `Content-Length` header.
+    ///
+    /// Reads are clamped so that no more than `expected` bytes are yielded.
+    /// Reaching EOF before that fails with an `InvalidData` error. Once
+    /// `expected` bytes have been read, the next read checks that the
+    /// underlying reader is at EOF as well, and fails with an `InvalidData`
+    /// error if it isn't; the extra byte it read is lost.
+    fn content_length(self, expected: u64) -> ContentLength<Self>
+        where Self: Sized,
+    {
+        content_length::content_length(self, expected)
+    }
+
+    /// Wraps this reader so that reads into buffers smaller than `min` bytes
+    /// fail with an `InvalidInput` error instead of reaching it.
+    ///
+    /// This protects decoders which can't make progress with a buffer below
+    /// some size, turning what would be a stall or a spurious EOF into a
+    /// clear error.
+    fn require_min_buffer(self, min: usize) -> RequireMinBuffer<Self>
+        where Self: Sized,
+    {
+        min_buffer::require_min_buffer(self, min)
+    }
+
+    /// Creates a stream of the messages read from this reader, each prefixed
+    /// with its length as a LEB128 varint, the framing used for streams of
+    /// protocol buffers. Such a stream is written by `varint_delimited_sink`.
+    ///
+    /// A length which doesn't fit in a `u64` is an `InvalidData` error, and a
+    /// stream ending in the middle of a message is an `UnexpectedEof` error.
+    fn varint_delimited(self) -> VarintDelimited<Self>
+        where Self: Sized,
+    {
+        varint_delimited::varint_delimited(self)
+    }
+
+    /// Creates a future which reads this reader up to the first occurrence
+    /// of `marker`, resolving to the bytes before it and a reader for the
+    /// bytes after it.
+    ///
+    /// This suits protocols made of a header and a body, such as HTTP with
+    /// the header ending at `\r\n\r\n`. The marker itself is dropped. Bytes
+    /// read past the marker while looking for it are yielded by the body
+    /// reader before the rest of the stream. A stream ending before the
+    /// marker is an `UnexpectedEof` error.
+    fn split_at_marker(self, marker: &[u8]) -> SplitAtMarker<Self>
+        where Self: Sized,
+    {
+        split_at_marker::split_at_marker(self, marker)
+    }
+
+    /// Wraps this reader so that the position at which the underlying reader
+    /// first reported EOF is recorded and available through
+    /// `eof_position`.
+    ///
+    /// Reads are passed through unchanged. This tells an empty stream, whose
+    /// EOF position is zero, apart from one which was cut short at a known
+    /// offset, which helps diagnosing unexpectedly short inputs.
+    fn inspect_eof_position(self) -> InspectEofPosition<Self>
+        where Self: Sized,
+    {
+        inspect_eof::inspect_eof_position(self)
+    }
+
+    /// Creates an adapter which reads everything from this reader and then
+    /// everything from `next`.
+    ///
+    /// The switch to `next` only happens once this reader has returned
+    /// `Ok(Async::Ready(0))` for a non-empty buffer; a `Pending` from it is
+    /// passed on as is. This is handy for putting back bytes which have
+    /// already been read, such as a header, in front of the rest of the
+    /// stream.
+    fn chain<U>(self, next: U) -> Chain<Self, U>
+        where U: AsyncRead,
+              Self: Sized,
+    {
+        chain::chain(self, next)
+    }
+
+    /// Creates a future which detects the format of this stream from the
+    /// magic bytes it starts with.
+    ///
+    /// `detectors` pairs the magic bytes of each format with a value of the
+    /// caller's choosing, which the future resolves to as
+    /// `Detected::Known` if the stream starts with them, or
+    /// `Detected::Unknown` if it starts with none of them. If several
+    /// match, the first one in the list wins. Reading stops as soon as the
+    /// bytes read so far decide the format, and those bytes are put back in
+    /// front of the rest of the stream by the returned reader, so the
+    /// consumer sees the stream from its start.
+    fn detect_format<F>(self, detectors: Vec<(&[u8], F)>) -> DetectFormat<Self, F>
+        where Self: Sized,
+    {
+        detect_format::detect_format(self, detectors)
+    }
+
+    /// Creates an adapter which reads at most `limit` bytes from this
+    /// reader.
+    ///
+    /// Reads are clamped to the number of bytes left, which goes down by the
+    /// number of bytes actually read. Once it reaches zero, reads report EOF
+    /// without touching this reader, so a `limit` of zero is at EOF right
+    /// away. This is useful for reading a length-delimited frame from a
+    /// stream which continues after it.
+    fn take(self, limit: u64) -> Take<Self>
+        where Self: Sized,
+    {
+        take::take(self, limit)
+    }
+
+    /// Wraps this reader so that, in debug builds, a read reporting more
+    /// bytes than fit in the caller's buffer panics.
+    ///
+    /// A reader which does so has a bug, typically writing past the end of
+    /// the buffer it was given, which callers would otherwise only notice
+    /// later as corrupt data or out-of-bounds slicing. This is meant for
+    /// catching such readers early while debugging; in release builds the
+    /// adapter passes reads through without any checks.
+    fn assert_nondecreasing_reads(self) -> AssertNondecreasingReads<Self>
+        where Self: Sized,
+    {
+        assert_reads::assert_nondecreasing_reads(self)
+    }
+
+    /// Creates a future which decodes this reader into key-value pairs and
+    /// collects them into a `HashMap`, such as for reading a serialized map
+    /// or configuration blob in one go.
+    ///
+    /// `decode` is called with the bytes read so far. If they start with a
+    /// complete frame it removes that frame from the buffer and returns
+    /// `Some((key, value))`; otherwise it returns `None` and more data is
+    /// read. A key which occurs more than once is handled as `duplicates`
+    /// prescribes. The future resolves to this reader and the map once the
+    /// reader reaches EOF, or fails with an `UnexpectedEof` error if that
+    /// happens in the middle of a frame.
+    fn collect_pairs<D>(self, decode: D, duplicates: DuplicateKeys) -> CollectPairs<Self, D>
+        where D: FnMut(&mut Vec<u8>) -> Option<(Vec<u8>, Vec<u8>)>,
+              Self: Sized,
+    {
+        collect_pairs::collect_pairs(self, decode, duplicates)
+    }
+
+    /// Creates a stream over the lines of this reader, like
+    /// `std::io::BufRead::lines`.
+    ///
+    /// Each line is yielded as a `String` without its trailing `\n` or
+    /// `\r\n`. A line may span any number of reads; the last line is yielded
+    /// even if it doesn't end in a newline, and the stream ends at EOF. A
+    /// line which isn't valid UTF-8 is an `InvalidData` error; the stream
+    /// may be polled again for the following lines.
+    fn lines(self) -> Lines<Self>
+        where Self: AsyncBufRead + Sized,
+    {
+        lines::lines(self)
+    }
+
+    /// Wraps this reader so that every byte read from it is also written to
+    /// `out`, such as for logging the traffic of a protocol.
+    ///
+    /// Each read writes the bytes it read to `out` before returning. If `out`
+    /// doesn't accept all of them right away, the rest is buffered, and the
+    /// next read first finishes writing it out and only then reads more from
+    /// this reader; an error or `Pending` from `out` is reported by that next
+    /// read. This way no byte is lost if `out` is slower than this reader.
+    fn tee<W>(self, out: W) -> Tee<Self, W>
+        where W: AsyncWrite,
+              Self: Sized,
+    {
+        tee::tee(self, out)
+    }
+
+    /// Wraps this reader so that it counts the bytes read through it, such
+    /// as for metrics. The count is returned by `byte_count` on the wrapper;
+    /// an inherent `count` there would lose to this method during method
+    /// lookup.
+    fn count(self) -> CountRead<Self>
+        where Self: Sized,
+    {
+        count::count_read(self)
+    }
+
+    /// Wraps this reader so that `f` is called with the bytes of every
+    /// successful read, such as for debugging framing.
+    ///
+    /// `f` sees exactly the bytes each read returned, and an empty slice at
+    /// the end of the stream; it isn't called for reads which are pending or
+    /// fail. Unlike `tee`, this doesn't need a writer to copy the bytes to.
+    fn inspect<F>(self, f: F) -> Inspect<Self, F>
+        where F: FnMut(&[u8]),
+              Self: Sized,
+    {
+        inspect::inspect(self, f)
+    }
+
+    /// Wraps this reader so that a single read returns at most
+    /// `bytes_per_poll` bytes, such as for simulating a slow network in tests.
+    ///
+    /// Reads are cut short by reading into a shorter buffer, so a read which
+    /// hits the limit is still ready; no read is ever delayed. This makes the
+    /// chunks a reader returns deterministic. The limit can be changed with
+    /// `set_limit`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes_per_poll` is zero, as that would starve the reader.
+    fn throttle(self, bytes_per_poll: usize) -> Throttle<Self>
+        where Self: Sized,
+    {
+        throttle::throttle(self, bytes_per_poll)
+    }
+
+    /// Attempts to read into the remaining capacity of a `BufMut` from the
+    /// `bytes` crate, such as a `BytesMut`, advancing it by the number of
+    /// bytes read.
+    ///
+    /// The buffer's uninitialized memory is prepared as this reader's
+    /// `initializer` requires before it is read into. A buffer without any
+    /// remaining capacity reads zero bytes without polling the reader.
+    ///
+    /// This method is only available with the `bytes` feature.
+    #[cfg(feature = "bytes")]
+    fn poll_read_buf<B>(&mut self, cx: &mut task::Context, buf: &mut B)
+        -> ::futures_core::Poll<usize, ::std::io::Error>
+        where B: ::bytes::BufMut,
+    {
+        buf::poll_read_buf(self, cx, buf)
+    }
+
+    /// Wraps this reader so that a read fails with a `TimedOut` error if
+    /// this reader returns nothing for `dur`.
+    ///
+    /// The idle timer is a future obtained by calling `timer` with `dur`,
+    /// which keeps this crate independent of any particular timer
+    /// implementation. It is started by the first read which has to wait and
+    /// restarted after every read which returns, so the timeout limits how
+    /// long the reader may be idle, not how long the whole stream takes. If
+    /// the reader and the timer are ready at once, the read wins. After a
+    /// timeout the reader can be read again, starting a new timer.
+    fn timeout<F, T>(self, dur: Duration, timer: F) -> Timeout<Self, F, T>
+        where F: FnMut(Duration) -> T,
+              T: Future<Item = (), Error = Never>,
+              Self: Sized,
+    {
+        timeout::timeout(self, dur, timer)
+    }
 }
 
 impl<T: AsyncRead + ?Sized> AsyncReadExt for T {}
 
+/// An extension trait which adds utility methods to `AsyncBufRead` types.
+pub trait AsyncBufReadExt: AsyncBufRead {
+    /// Creates a future which will read all the bytes of this reader into
+    /// `buf` until the delimiter `byte` is reached. This method is the async
+    /// equivalent to [`BufRead::read_until`].
+    ///
+    /// The delimiter is appended to `buf` as well, if found, and nothing
+    /// after it is consumed. The future resolves to the number of bytes
+    /// appended. Reaching EOF before the delimiter is not an error: the
+    /// future resolves with whatever was read, which may be nothing.
+    ///
+    /// [`BufRead::read_until`]: https://doc.rust-lang.org/std/io/trait.BufRead.html#method.read_until
+    fn read_until<'a>(&'a mut self, byte: u8, buf: &'a mut Vec<u8>) -> ReadUntil<'a, Self> {
+        read_until::read_until(self, byte, buf)
+    }
+
+    /// Creates a future which resolves to `true` if this reader is at the end
+    /// of its stream, and `false` if more data is available.
+    ///
+    /// The buffer is filled if it is empty, but no data is consumed, so a
+    /// following read sees the same bytes.
+    fn at_eof<'a>(&'a mut self) -> AtEof<'a, Self> {
+        at_eof::at_eof(self)
+    }
+}
+
+impl<T: AsyncBufRead + ?Sized> AsyncBufReadExt for T {}
+
 /// An extension trait which adds utility methods to `AsyncWrite` types.
 pub trait AsyncWriteExt: AsyncWrite {
-    /// Creates a future which will entirely flush this `AsyncWrite` and then return `self`.
+    /// Creates a future which will entirely flush this `AsyncWrite`,
+    /// resolving to `()`.
     ///
-    /// This function will consume `self` if an error occurs.
-    fn flush(self) -> Flush<Self>
-        where Self: Sized,
-    {
+    /// Each poll of the future just polls `poll_flush` again, so dropping it
+    /// before it completes leaves the writer intact, and the flush can be
+    /// started over later. Run once `write_all` has completed, this expresses a
+    /// complete write followed by a flush.
+    fn flush<'a>(&'a mut self) -> Flush<'a, Self> {
         flush::flush(self)
     }
 
-    /// Creates a future which will entirely close this `AsyncWrite` and then return `self`.
+    /// Creates a future which will entirely close this `AsyncWrite`,
+    /// resolving to `()`.
     ///
-    /// This function will consume the object provided if an error occurs.
-    fn close(self) -> Close<Self>
-        where Self: Sized,
-    {
+    /// As with `flush`, the future can be dropped at any time without
+    /// affecting the writer.
+    fn close<'a>(&'a mut self) -> Close<'a, Self> {
         close::close(self)
     }
 
-    /// Write a `Buf` into this value, returning how many bytes were written.
     /// Creates a future that will write the entire contents of the buffer `buf` into
     /// this `AsyncWrite`.
     ///
-    /// The returned future will not complete until all the data has been written.
-    /// The future will resolve to a tuple of `self` and `buf`
-    /// (so the buffer can be reused as needed).
+    /// The returned future will not complete until all the data has been
+    /// written, re-issuing writes for whatever a partial `poll_write` left
+    /// over, and then resolves to `()`. A writer which accepts zero bytes
+    /// while some remain fails the future with a `WriteZero` error.
+    ///
+    /// The writer isn't flushed, so data held by a buffering writer stays
+    /// buffered; chain `flush` afterwards if it should be pushed out.
+    fn write_all<'a>(&'a mut self, buf: &'a [u8]) -> WriteAll<'a, Self> {
+        write_all::write_all(self, buf)
+    }
+
+    /// Creates a future that will write every buffer in `parts` into this
+    /// `AsyncWrite`, inserting `sep` between consecutive parts, similar to
+    /// `[T]::join`.
+    ///
+    /// The data is handed to `poll_vectored_write` without being copied into
+    /// an intermediate buffer, and the returned future will not complete
+    /// until all of it has been written, even if the writer only accepts part
+    /// of a field or separator at a time.
+    fn write_joined<'a>(&'a mut self, parts: &'a [&'a [u8]], sep: &'a [u8])
+        -> WriteJoined<'a, Self>
+    {
+        write_joined::write_joined(self, parts, sep)
+    }
+
+    /// Creates a future which resolves once this writer is ready to accept at
+    /// least one more byte, as reported by `AsyncWritable::poll_writable`.
+    ///
+    /// Producers can wait on this before generating expensive data, instead
+    /// of generating it and then finding out that the writer is full.
+    fn writable<'a>(&'a mut self) -> Writable<'a, Self>
+        where Self: AsyncWritable,
+    {
+        writable::writable(self)
+    }
+
+    /// Creates a future that will write the entire contents of `buf` into
+    /// this `AsyncWrite`, failing with a `TimedOut` error if that takes longer
+    /// than `dur`.
+    ///
+    /// The deadline is a future obtained by calling `timer` with `dur`, which
+    /// keeps this crate independent of any particular timer implementation.
+    /// The deadline covers the whole write, not each individual call to
+    /// `poll_write`. Bytes written before the deadline fires have reached the
+    /// writer, and `WriteAllTimeout::remaining` tells how many did not.
+    fn write_all_timeout<'a, F, T>(&'a mut self, buf: &'a [u8], dur: Duration, timer: F)
+        -> WriteAllTimeout<'a, Self, T>
+        where F: FnOnce(Duration) -> T,
+              T: Future<Item = (), Error = Never>,
+    {
+        write_all_timeout::write_all_timeout(self, buf, dur, timer)
+    }
+
+    /// Wraps this writer so that it is flushed and closed at most once.
+    ///
+    /// The first completed `poll_close` flushes and then closes the
+    /// underlying writer; every later `poll_close` or `poll_flush` returns
+    /// `Ready(())` without touching it. This protects writers which fail when
+    /// closed twice from pipelines where several layers close them.
+    fn finalize_once(self) -> FinalizeOnce<Self>
+        where Self: Sized,
+    {
+        finalize_once::finalize_once(self)
+    }
+
+    /// Wraps this writer so that at most `max` bytes are written to it
+    /// between flushes.
+    ///
+    /// Once `max` bytes have been written since the last completed flush,
+    /// the next write first flushes the underlying writer, returning
+    /// `Pending` until the flush completes. A single write is shortened so
+    /// that it doesn't exceed the limit. This bounds how much data a
+    /// buffering writer downstream can accumulate.
+    ///
+    /// # Panics
     ///
-    /// Any error which happens during writing will cause both the stream and the
-    /// buffer to be destroyed.
+    /// Panics if `max` is 0.
+    fn bounded_unflushed(self, max: usize) -> BoundedUnflushed<Self>
+        where Self: Sized,
+    {
+        bounded_unflushed::bounded_unflushed(self, max)
+    }
+
+    /// Wraps this writer so that it is never called again after it has been
+    /// closed or has failed.
+    ///
+    /// After a completed `poll_close`, further writes return `Ready(0)` and
+    /// flushes and closes return `Ready(())`. After an error other than
+    /// `WouldBlock` or `Interrupted`, every further call fails with an error
+    /// of the same kind. `Pending` is passed through and fuses nothing.
+    fn fuse_write(self) -> FuseWriter<Self>
+        where Self: Sized,
+    {
+        fuse_writer::fuse_write(self)
+    }
+
+    /// Wraps this writer so that `ack` is called with the total number of
+    /// bytes written every time a flush completes.
     ///
-    /// The `buf` parameter here only requires the `AsRef<[u8]>` trait, which should
-    /// be broadly applicable to accepting data which can be converted to a slice.
-    /// The `Window` struct is also available in this crate to provide a different
-    /// window into a slice if necessary.
-    fn write_all<T>(self, buf: T) -> WriteAll<Self, T>
-        where T: AsRef<[u8]>,
+    /// Since a completed flush covers everything written before it, this is
+    /// the number of bytes known to have been flushed, and it never
+    /// decreases between calls. This is useful for acknowledgement-based
+    /// flow control, such as advancing a replication offset.
+    fn ack_on_flush<A>(self, ack: A) -> AckOnFlush<Self, A>
+        where A: FnMut(u64),
               Self: Sized,
     {
-        write_all::write_all(self, buf)
+        ack_on_flush::ack_on_flush(self, ack)
+    }
+
+    /// Creates a future which moves up to `len` bytes from `reader` into
+    /// this writer, or every byte until EOF if `len` is `None`, and then
+    /// flushes it. The future resolves to the number of bytes moved.
+    ///
+    /// This is `copy_into` as a writer method with an optional bound. Neither
+    /// trait can report a zero-copy path, so the bytes go through an
+    /// internal ring buffer, which is filled with `poll_vectored_read` and
+    /// drained with `poll_vectored_write` whenever its contents wrap around,
+    /// letting reads and writes overlap. Fewer than `len` bytes are moved if
+    /// `reader` reaches EOF first.
+    fn splice_from<'a, R>(&'a mut self, reader: &'a mut R, len: Option<u64>)
+        -> SpliceFrom<'a, Self, R>
+        where R: ?Sized + AsyncRead,
+    {
+        splice_from::splice_from(self, reader, len)
+    }
+
+    /// Wraps this writer so that writes are kept in memory, one buffer per
+    /// write, until the writer is flushed or closed.
+    ///
+    /// The buffers are not concatenated: `poll_close` hands all of them to
+    /// the underlying writer's `poll_vectored_write` at once, continuing
+    /// where it left off if only part of them is accepted, and then closes
+    /// it. `poll_flush` does the same and then flushes instead, so that a
+    /// flush still guarantees the bytes have left this adapter. This keeps
+    /// the number of writes down for write-once, flush-once patterns.
+    fn gather_until_close(self) -> GatherUntilClose<Self>
+        where Self: Sized,
+    {
+        gather::gather_until_close(self)
+    }
+
+    /// Wraps this writer so that each write is treated as one message of at
+    /// most `max_datagram` bytes, for sinks such as UDP sockets where message
+    /// boundaries matter.
+    ///
+    /// Every message is handed to the underlying writer in a single
+    /// `poll_write` call and is never split across calls: a larger message
+    /// fails with an `InvalidInput` error without anything being written, and
+    /// an underlying writer which accepts only part of a message fails the
+    /// write with a `WriteZero` error. Note that `write_all` only retries the
+    /// unwritten rest of a buffer, which this adapter never leaves behind.
+    fn datagram_write(self, max_datagram: usize) -> DatagramWriter<Self>
+        where Self: Sized,
+    {
+        datagram::datagram_write(self, max_datagram)
+    }
+
+    /// Wraps this writer so that after every `n` writes which accept at
+    /// least one byte, the next write returns `Pending` once, waking its task
+    /// right away.
+    ///
+    /// This forces a yield at deterministic points, which makes it possible
+    /// to test that combinators writing in a loop cooperate with the
+    /// executor instead of monopolizing it. The budget is reset by the write
+    /// which yields.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    fn budget_write(self, n: usize) -> BudgetWrite<Self>
+        where Self: Sized,
+    {
+        budget_write::budget_write(self, n)
+    }
+
+    /// Wraps this writer so that every byte written to it is also written to
+    /// `capture`, such as an audit log.
+    ///
+    /// This writer stays the primary one: a write reports the number of bytes
+    /// it accepted, and exactly those bytes are then written to `capture`, so
+    /// neither side misses or repeats anything even when they accept
+    /// different amounts at a time. Bytes which `capture` can't take right
+    /// away are buffered and written before the next write is accepted. Both
+    /// writers are flushed by `poll_flush` and closed by `poll_close`.
+    fn tee_to<C>(self, capture: C) -> TeeTo<Self, C>
+        where C: AsyncWrite,
+              Self: Sized,
+    {
+        tee_to::tee_to(self, capture)
+    }
+
+    /// Wraps this writer so that the CRC-32 of everything written through it
+    /// is appended to the stream, as four big-endian bytes, when it's closed.
+    ///
+    /// This is the CRC-32 used by zlib and gzip. The trailer is written by
+    /// `poll_close` before the underlying writer is closed, continuing across
+    /// calls if the underlying writer accepts only part of it at a time.
+    /// Writing after `poll_close` has been called is an error.
+    fn crc_trailer_write(self) -> CrcTrailerWriter<Self>
+        where Self: Sized,
+    {
+        crc_trailer::crc_trailer_write(self)
+    }
+
+    /// Wraps this writer so that `frame` is written to it whenever nothing
+    /// has been written for `interval`, to keep a connection alive.
+    ///
+    /// The idle timer is a future obtained by calling `timer` with
+    /// `interval`, which keeps this crate independent of any particular timer
+    /// implementation. It is started when the adapter is created and
+    /// restarted after every write and every keepalive frame. Since an idle
+    /// writer isn't polled by anyone, the keepalive frames are written by
+    /// [`KeepaliveWriter::poll_keepalive`], which the owner of the writer must
+    /// poll while it has nothing else to write.
+    ///
+    /// [`KeepaliveWriter::poll_keepalive`]: struct.KeepaliveWriter.html#method.poll_keepalive
+    fn keepalive_write<F, T>(self, interval: Duration, timer: F, frame: Vec<u8>)
+        -> KeepaliveWriter<Self, F, T>
+        where F: FnMut(Duration) -> T,
+              T: Future<Item = (), Error = Never>,
+              Self: Sized,
+    {
+        keepalive::keepalive_write(self, interval, timer, frame)
+    }
+
+    /// Wraps this writer so that line endings are converted on the fly,
+    /// from `\n` to `\r\n` if `to_crlf` is true and from `\r\n` to `\n`
+    /// otherwise.
+    ///
+    /// Line endings which are already in the target form are left alone,
+    /// as are `\r`s which aren't followed by a `\n`. Writes report the number
+    /// of input bytes consumed, and a `\r\n` may be split across writes.
+    /// When converting to `\n`, a trailing `\r` is held back until the next
+    /// byte shows whether it ends a line, so it's only written by a later
+    /// write or by `poll_close`, not by `poll_flush`.
+    fn newline_convert_write(self, to_crlf: bool) -> NewlineConvertWriter<Self>
+        where Self: Sized,
+    {
+        newline_convert::newline_convert_write(self, to_crlf)
+    }
+
+    /// Creates a future that will write the entire contents of `buf` into
+    /// this `AsyncWrite`, tolerating writes which momentarily accept zero
+    /// bytes.
+    ///
+    /// Where `write_all` fails with a `WriteZero` error as soon as
+    /// `poll_write` returns `Ok(Async::Ready(0))`, this future waits for a
+    /// future obtained by calling `timer` with `backoff` and tries again, up
+    /// to `max_retries` times in a row. A write which makes progress resets
+    /// the count. Once the retries are exhausted it fails with `WriteZero`
+    /// after all, and `WriteAllTolerant::remaining` tells how many bytes
+    /// were not written.
+    fn write_all_tolerant<'a, F, T>(&'a mut self,
+                                    buf: &'a [u8],
+                                    max_retries: usize,
+                                    backoff: Duration,
+                                    timer: F)
+        -> WriteAllTolerant<'a, Self, F, T>
+        where F: FnMut(Duration) -> T,
+              T: Future<Item = (), Error = Never>,
+    {
+        write_all_tolerant::write_all_tolerant(self, buf, max_retries, backoff, timer)
+    }
+
+    /// Wraps this writer so that each message sent with
+    /// `IsolateMessages::send_message` is preceded and followed by a flush.
+    ///
+    /// The first flush pushes out anything written before the message, and
+    /// the second one the message itself, so every message starts and ends
+    /// at a flush boundary. This helps track down interleaving bugs and suits
+    /// protocols which need each message flushed on its own. It comes at a
+    /// cost, though: every message takes two flushes, which for a buffered
+    /// or networked writer can mean two system calls or packets, and small
+    /// messages can't be coalesced.
+    fn isolate_messages(self) -> IsolateMessages<Self>
+        where Self: Sized,
+    {
+        isolate_messages::isolate_messages(self)
+    }
+
+    /// Wraps this writer so that it counts the bytes written through it,
+    /// such as for metrics. The count is returned by `byte_count` on the
+    /// wrapper.
+    ///
+    /// Only the bytes the underlying writer actually accepted are counted,
+    /// so a partial write adds only the length it reports.
+    fn count_write(self) -> CountWrite<Self>
+        where Self: Sized,
+    {
+        count::count_write(self)
+    }
+
+    /// Wraps this writer so that `f` is called with the bytes the underlying
+    /// writer accepted on every successful write.
+    ///
+    /// `f` sees exactly the part of the buffer which was written, so a
+    /// partial write passes only the bytes it reports; it isn't called for
+    /// writes which are pending or fail.
+    fn inspect_write<F>(self, f: F) -> InspectWrite<Self, F>
+        where F: FnMut(&[u8]),
+              Self: Sized,
+    {
+        inspect::inspect_write(self, f)
+    }
+
+    /// Attempts to write the remaining bytes of a `Buf` from the `bytes`
+    /// crate, such as a `Bytes`, advancing it by the number of bytes written.
+    ///
+    /// A buffer made of several non-contiguous segments is written with a
+    /// single vectored write. An empty buffer writes zero bytes without
+    /// polling the writer.
+    ///
+    /// This method is only available with the `bytes` feature.
+    #[cfg(feature = "bytes")]
+    fn poll_write_buf<B>(&mut self, cx: &mut task::Context, buf: &mut B)
+        -> ::futures_core::Poll<usize, ::std::io::Error>
+        where B: ::bytes::Buf,
+    {
+        buf::poll_write_buf(self, cx, buf)
     }
 }
 
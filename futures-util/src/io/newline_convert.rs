@@ -0,0 +1,126 @@
+use std::io;
+use std::vec::Vec;
+
+use {Async, Poll, task};
+
+use futures_io::AsyncWrite;
+
+/// A writer which converts line endings between `\n` and `\r\n` on their
+/// way to the underlying writer.
+///
+/// Created by the [`newline_convert_write`] method.
+///
+/// [`newline_convert_write`]: trait.AsyncWriteExt.html#method.newline_convert_write
+#[derive(Debug)]
+pub struct NewlineConvertWriter<W> {
+    inner: W,
+    to_crlf: bool,
+    // Whether the last byte written was a `\r`. When converting to `\n` it
+    // hasn't been passed on yet, since it's dropped if a `\n` follows.
+    cr: bool,
+    out: Vec<u8>,
+    pos: usize,
+}
+
+pub fn newline_convert_write<W: AsyncWrite>(inner: W, to_crlf: bool) -> NewlineConvertWriter<W> {
+    NewlineConvertWriter {
+        inner,
+        to_crlf,
+        cr: false,
+        out: Vec::new(),
+        pos: 0,
+    }
+}
+
+impl<W> NewlineConvertWriter<W> {
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes this `NewlineConvertWriter`, returning the underlying writer.
+    ///
+    /// Any converted output which hasn't been written yet is lost.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    fn convert(&mut self, buf: &[u8]) {
+        for &byte in buf {
+            match byte {
+                b'\n' => {
+                    if self.to_crlf && !self.cr {
+                        self.out.push(b'\r');
+                    }
+                    self.out.push(b'\n');
+                }
+                b'\r' => {
+                    // Converting to `\n`, only the previous `\r` is
+                    // known not to be followed by a `\n`.
+                    if self.to_crlf || self.cr {
+                        self.out.push(b'\r');
+                    }
+                }
+                _ => {
+                    if !self.to_crlf && self.cr {
+                        self.out.push(b'\r');
+                    }
+                    self.out.push(byte);
+                }
+            }
+            self.cr = byte == b'\r';
+        }
+    }
+}
+
+impl<W: AsyncWrite> NewlineConvertWriter<W> {
+    fn poll_out(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        while self.pos < self.out.len() {
+            let n = try_ready!(self.inner.poll_write(cx, &self.out[self.pos..]));
+            if n == 0 {
+                return Err(io::Error::new(io::ErrorKind::WriteZero,
+                                          "failed to write converted data"))
+            }
+            self.pos += n;
+        }
+        self.out.clear();
+        self.pos = 0;
+        Ok(Async::Ready(()))
+    }
+}
+
+impl<W: AsyncWrite> AsyncWrite for NewlineConvertWriter<W> {
+    fn poll_write(&mut self, cx: &mut task::Context, buf: &[u8])
+        -> Poll<usize, io::Error>
+    {
+        try_ready!(self.poll_out(cx));
+        if buf.is_empty() {
+            return Ok(Async::Ready(0))
+        }
+        self.convert(buf);
+
+        // The input is ours now; try to get the output going, but any error
+        // or `Pending` is reported by the next call instead.
+        let _ = self.poll_out(cx);
+        Ok(Async::Ready(buf.len()))
+    }
+
+    fn poll_flush(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        try_ready!(self.poll_out(cx));
+        self.inner.poll_flush(cx)
+    }
+
+    fn poll_close(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        if !self.to_crlf && self.cr {
+            self.out.push(b'\r');
+            self.cr = false;
+        }
+        try_ready!(self.poll_out(cx));
+        self.inner.poll_close(cx)
+    }
+}
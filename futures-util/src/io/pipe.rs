@@ -0,0 +1,155 @@
+use std::cmp;
+use std::collections::VecDeque;
+use std::io;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use {Async, Poll, task};
+use futures_core::task::Waker;
+
+use futures_io::{AsyncRead, AsyncWrite};
+
+// The number of bytes buffered before writes to the pipe pend.
+const CAPACITY: usize = 8 * 1024;
+
+/// The reading end of an in-memory pipe.
+///
+/// Created by the [`pipe`] function.
+///
+/// [`pipe`]: fn.pipe.html
+#[derive(Debug)]
+pub struct PipeReader {
+    shared: Arc<Mutex<Shared>>,
+}
+
+/// The writing end of an in-memory pipe.
+///
+/// Created by the [`pipe`] function.
+///
+/// [`pipe`]: fn.pipe.html
+#[derive(Debug)]
+pub struct PipeWriter {
+    shared: Arc<Mutex<Shared>>,
+}
+
+#[derive(Debug)]
+struct Shared {
+    buf: VecDeque<u8>,
+    // Whether the writer has been closed or dropped.
+    closed: bool,
+    reader_dropped: bool,
+    read_waker: Option<Waker>,
+    write_waker: Option<Waker>,
+}
+
+/// Creates an in-memory pipe, returning its connected reading and writing
+/// ends.
+///
+/// Bytes written to the `PipeWriter` can be read from the `PipeReader`, with
+/// up to 8 KiB buffered in between; writes pend while the buffer is full and
+/// reads pend while it's empty. Once the writer is closed or dropped, the
+/// reader yields the remaining bytes and then reports EOF. Once the reader
+/// is dropped, writes fail with a `BrokenPipe` error.
+///
+/// This is handy for testing code which uses I/O objects without setting
+/// up sockets.
+pub fn pipe() -> (PipeReader, PipeWriter) {
+    let shared = Arc::new(Mutex::new(Shared {
+        buf: VecDeque::new(),
+        closed: false,
+        reader_dropped: false,
+        read_waker: None,
+        write_waker: None,
+    }));
+    (PipeReader { shared: shared.clone() }, PipeWriter { shared })
+}
+
+fn lock<'a>(shared: &'a Mutex<Shared>) -> MutexGuard<'a, Shared> {
+    shared.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl AsyncRead for PipeReader {
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        let mut shared = lock(&self.shared);
+        if shared.buf.is_empty() {
+            if shared.closed || buf.is_empty() {
+                return Ok(Async::Ready(0))
+            }
+            shared.read_waker = Some(cx.waker().clone());
+            return Ok(Async::Pending)
+        }
+
+        let n = cmp::min(buf.len(), shared.buf.len());
+        for (dst, src) in buf.iter_mut().zip(shared.buf.drain(..n)) {
+            *dst = src;
+        }
+        if let Some(waker) = shared.write_waker.take() {
+            waker.wake();
+        }
+        Ok(Async::Ready(n))
+    }
+}
+
+impl Drop for PipeReader {
+    fn drop(&mut self) {
+        let mut shared = lock(&self.shared);
+        shared.reader_dropped = true;
+        if let Some(waker) = shared.write_waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl AsyncWrite for PipeWriter {
+    fn poll_write(&mut self, cx: &mut task::Context, buf: &[u8])
+        -> Poll<usize, io::Error>
+    {
+        let mut shared = lock(&self.shared);
+        if shared.reader_dropped {
+            return Err(io::Error::new(io::ErrorKind::BrokenPipe,
+                                      "the pipe's reader was dropped"))
+        }
+        if shared.closed {
+            return Err(io::Error::new(io::ErrorKind::Other, "write after close"))
+        }
+        if buf.is_empty() {
+            return Ok(Async::Ready(0))
+        }
+        let space = CAPACITY - shared.buf.len();
+        if space == 0 {
+            shared.write_waker = Some(cx.waker().clone());
+            return Ok(Async::Pending)
+        }
+
+        let n = cmp::min(buf.len(), space);
+        shared.buf.extend(&buf[..n]);
+        if let Some(waker) = shared.read_waker.take() {
+            waker.wake();
+        }
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        let mut shared = lock(&self.shared);
+        shared.closed = true;
+        if let Some(waker) = shared.read_waker.take() {
+            waker.wake();
+        }
+        Ok(Async::Ready(()))
+    }
+}
+
+impl Drop for PipeWriter {
+    fn drop(&mut self) {
+        let mut shared = lock(&self.shared);
+        shared.closed = true;
+        if let Some(waker) = shared.read_waker.take() {
+            waker.wake();
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use std::io;
+
+use {Async, Poll, task};
+
+use futures_io::{AsyncRead, AsyncWrite, Initializer, IoVec};
+
+/// A reader which counts the bytes read through it.
+///
+/// This reports offsets into the stream, for example in error messages,
+/// without requiring the underlying reader to be seekable.
+#[derive(Debug)]
+pub struct PositionReader<R> {
+    inner: R,
+    pos: u64,
+}
+
+impl<R: AsyncRead> PositionReader<R> {
+    /// Creates a new `PositionReader` starting at position 0.
+    pub fn new(inner: R) -> PositionReader<R> {
+        PositionReader { inner, pos: 0 }
+    }
+
+    /// Returns the number of bytes read so far.
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// Note that bytes read from the underlying reader directly are not
+    /// counted.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this `PositionReader`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for PositionReader<R> {
+    unsafe fn initializer(&self) -> Initializer {
+        self.inner.initializer()
+    }
+
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        let n = try_ready!(self.inner.poll_read(cx, buf));
+        self.pos += n as u64;
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_vectored_read(&mut self, cx: &mut task::Context, vec: &mut [&mut IoVec])
+        -> Poll<usize, io::Error>
+    {
+        let n = try_ready!(self.inner.poll_vectored_read(cx, vec));
+        self.pos += n as u64;
+        Ok(Async::Ready(n))
+    }
+}
+
+/// A writer which counts the bytes accepted by the underlying writer.
+///
+/// This gives encoders the current output offset, for example to write
+/// offsets pointing back into the stream.
+#[derive(Debug)]
+pub struct PositionWriter<W> {
+    inner: W,
+    pos: u64,
+}
+
+impl<W: AsyncWrite> PositionWriter<W> {
+    /// Creates a new `PositionWriter` starting at position 0.
+    pub fn new(inner: W) -> PositionWriter<W> {
+        PositionWriter { inner, pos: 0 }
+    }
+
+    /// Returns the number of bytes written so far.
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// Note that bytes written to the underlying writer directly are not
+    /// counted.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes this `PositionWriter`, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: AsyncWrite> AsyncWrite for PositionWriter<W> {
+    fn poll_write(&mut self, cx: &mut task::Context, buf: &[u8])
+        -> Poll<usize, io::Error>
+    {
+        let n = try_ready!(self.inner.poll_write(cx, buf));
+        self.pos += n as u64;
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_vectored_write(&mut self, cx: &mut task::Context, vec: &[&IoVec])
+        -> Poll<usize, io::Error>
+    {
+        let n = try_ready!(self.inner.poll_vectored_write(cx, vec));
+        self.pos += n as u64;
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_flush(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        self.inner.poll_flush(cx)
+    }
+
+    fn poll_close(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        self.inner.poll_close(cx)
+    }
+}
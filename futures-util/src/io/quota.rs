@@ -0,0 +1,119 @@
+use std::cmp;
+use std::io;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use {Async, Poll, task};
+
+use futures_io::{AsyncRead, Initializer};
+
+/// A number of bytes which may be read in total by any number of
+/// `QuotaReader`s.
+///
+/// Handles created with `clone` share the same quota.
+#[derive(Debug, Clone)]
+pub struct Quota {
+    remaining: Arc<AtomicUsize>,
+}
+
+impl Quota {
+    /// Creates a quota of `bytes` bytes.
+    pub fn new(bytes: usize) -> Quota {
+        Quota { remaining: Arc::new(AtomicUsize::new(bytes)) }
+    }
+
+    /// Returns the number of bytes left in this quota.
+    pub fn remaining(&self) -> usize {
+        self.remaining.load(Ordering::SeqCst)
+    }
+
+    /// Takes up to `max` bytes from the quota, returning how many were taken.
+    fn reserve(&self, max: usize) -> usize {
+        let mut current = self.remaining.load(Ordering::SeqCst);
+        loop {
+            let take = cmp::min(current, max);
+            if take == 0 {
+                return 0
+            }
+            match self.remaining.compare_exchange(current, current - take,
+                                                  Ordering::SeqCst, Ordering::SeqCst) {
+                Ok(_) => return take,
+                Err(actual) => current = actual,
+            }
+        }
+    }
+
+    fn release(&self, bytes: usize) {
+        if bytes > 0 {
+            self.remaining.fetch_add(bytes, Ordering::SeqCst);
+        }
+    }
+}
+
+/// A reader which reports EOF once a shared `Quota` is used up.
+///
+/// Each read takes the bytes it returns from the quota, and is shortened to
+/// what is left of it, so the readers sharing a quota never yield more than
+/// it allows in total. Bytes are only taken for data actually read: a read
+/// which returns fewer bytes than it asked for, `Pending` or an error gives
+/// the rest back. Once the quota is used up every read reports EOF, while
+/// the underlying reader is left where it stopped.
+///
+/// This can enforce an aggregate cap, such as on downloads, across many
+/// concurrent streams.
+#[derive(Debug)]
+pub struct QuotaReader<R> {
+    inner: R,
+    quota: Quota,
+}
+
+impl<R: AsyncRead> QuotaReader<R> {
+    /// Creates a new `QuotaReader` reading from `inner` within `quota`.
+    pub fn new(inner: R, quota: Quota) -> QuotaReader<R> {
+        QuotaReader { inner, quota }
+    }
+}
+
+impl<R> QuotaReader<R> {
+    /// Returns a reference to the quota this reader reads within.
+    pub fn quota(&self) -> &Quota {
+        &self.quota
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this `QuotaReader`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for QuotaReader<R> {
+    unsafe fn initializer(&self) -> Initializer {
+        self.inner.initializer()
+    }
+
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        let reserved = self.quota.reserve(buf.len());
+        if reserved == 0 {
+            return Ok(Async::Ready(0))
+        }
+        let res = self.inner.poll_read(cx, &mut buf[..reserved]);
+        let n = match res {
+            Ok(Async::Ready(n)) => n,
+            _ => 0,
+        };
+        self.quota.release(reserved - n);
+        res
+    }
+}
@@ -1,52 +1,35 @@
 use std::io;
-use std::mem;
 
-use {Future, Poll, task};
+use {Async, Future, Poll, task};
 
 use io::AsyncRead;
 
-#[derive(Debug)]
-enum State<R, T> {
-    Pending {
-        rd: R,
-        buf: T,
-    },
-    Empty,
-}
-
-pub fn read<R, T>(rd: R, buf: T) -> Read<R, T>
-    where R: AsyncRead,
-          T: AsMut<[u8]>
-{
-    Read { state: State::Pending { rd, buf } }
-}
-
 /// A future which can be used to easily read available number of bytes to fill
 /// a buffer.
 ///
-/// Created by the [`read`] function.
+/// Created by the [`read`] method.
+///
+/// [`read`]: trait.AsyncReadExt.html#method.read
 #[derive(Debug)]
-pub struct Read<R, T> {
-    state: State<R, T>,
+pub struct Read<'a, R: ?Sized + 'a> {
+    reader: &'a mut R,
+    buf: &'a mut [u8],
 }
 
-impl<R, T> Future for Read<R, T>
-    where R: AsyncRead,
-          T: AsMut<[u8]>
+pub fn read<'a, R>(reader: &'a mut R, buf: &'a mut [u8]) -> Read<'a, R>
+    where R: ?Sized + AsyncRead,
 {
-    type Item = (R, T, usize);
-    type Error = io::Error;
+    Read { reader, buf }
+}
 
-    fn poll(&mut self, cx: &mut task::Context) -> Poll<(R, T, usize), io::Error> {
-        let nread = match self.state {
-            State::Pending { ref mut rd, ref mut buf } =>
-                try_ready!(rd.poll_read(cx, &mut buf.as_mut()[..])),
-            State::Empty => panic!("poll a Read after it's done"),
-        };
+impl<'a, R: ?Sized + AsyncRead> Future for Read<'a, R> {
+    type Item = usize;
+    type Error = io::Error;
 
-        match mem::replace(&mut self.state, State::Empty) {
-            State::Pending { rd, buf } => Ok((rd, buf, nread).into()),
-            State::Empty => panic!("invalid internal state"),
+    fn poll(&mut self, cx: &mut task::Context) -> Poll<usize, io::Error> {
+        if self.buf.is_empty() {
+            return Ok(Async::Ready(0))
         }
+        self.reader.poll_read(cx, self.buf)
     }
 }
@@ -0,0 +1,61 @@
+use std::io;
+use std::mem;
+use std::vec::Vec;
+
+use {Poll, Future, task};
+
+use io::AsyncRead;
+
+use super::read_to_end::read_to_end_internal;
+
+/// A future which reads the entire contents of a stream into a new vector,
+/// handing the stream back along with it.
+///
+/// Created by the [`read_all`] method.
+///
+/// [`read_all`]: trait.AsyncReadExt.html#method.read_all
+#[derive(Debug)]
+pub struct ReadAll<A> {
+    state: State<A>,
+}
+
+#[derive(Debug)]
+enum State<A> {
+    Reading {
+        a: A,
+        buf: Vec<u8>,
+    },
+    Empty,
+}
+
+pub fn read_all<A>(a: A) -> ReadAll<A>
+    where A: AsyncRead,
+{
+    ReadAll {
+        state: State::Reading {
+            a,
+            buf: Vec::new(),
+        }
+    }
+}
+
+impl<A> Future for ReadAll<A>
+    where A: AsyncRead,
+{
+    type Item = (A, Vec<u8>);
+    type Error = io::Error;
+
+    fn poll(&mut self, cx: &mut task::Context) -> Poll<(A, Vec<u8>), io::Error> {
+        match self.state {
+            State::Reading { ref mut a, ref mut buf } => {
+                try_ready!(read_to_end_internal(a, cx, buf));
+            },
+            State::Empty => panic!("poll ReadAll after it's done"),
+        }
+
+        match mem::replace(&mut self.state, State::Empty) {
+            State::Reading { a, buf } => Ok((a, buf).into()),
+            State::Empty => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use std::io;
+use std::vec::Vec;
+
+use {Async, Future, Poll, task};
+
+use futures_io::AsyncRead;
+
+/// A future which reads a line terminated by `\r\n`.
+///
+/// This is created by the [`read_crlf_line`] method.
+///
+/// [`read_crlf_line`]: trait.AsyncReadExt.html#method.read_crlf_line
+#[derive(Debug)]
+pub struct ReadCrlfLine<'a, R: ?Sized + 'a> {
+    reader: &'a mut R,
+    buf: &'a mut Vec<u8>,
+    read: usize,
+    cr: bool,
+}
+
+pub fn read_crlf_line<'a, R>(reader: &'a mut R, buf: &'a mut Vec<u8>) -> ReadCrlfLine<'a, R>
+    where R: ?Sized + AsyncRead,
+{
+    ReadCrlfLine { reader, buf, read: 0, cr: false }
+}
+
+fn invalid(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+impl<'a, R: ?Sized + AsyncRead> Future for ReadCrlfLine<'a, R> {
+    type Item = usize;
+    type Error = io::Error;
+
+    fn poll(&mut self, cx: &mut task::Context) -> Poll<usize, io::Error> {
+        loop {
+            let mut byte = [0];
+            let n = try_ready!(self.reader.poll_read(cx, &mut byte));
+            if n == 0 {
+                if self.read == 0 && !self.cr {
+                    return Ok(Async::Ready(0))
+                }
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof,
+                                          "stream ended before `\\r\\n`"))
+            }
+            match (self.cr, byte[0]) {
+                (true, b'\n') => {
+                    self.cr = false;
+                    let read = self.read + 2;
+                    self.read = 0;
+                    return Ok(Async::Ready(read))
+                }
+                (true, _) => return Err(invalid("bare `\\r` in line")),
+                (false, b'\n') => return Err(invalid("bare `\\n` in line")),
+                (false, b'\r') => self.cr = true,
+                (false, b) => {
+                    self.buf.push(b);
+                    self.read += 1;
+                }
+            }
+        }
+    }
+}
@@ -1,41 +1,29 @@
 use std::io;
-use std::mem;
 
-use {Poll, Future, task};
+use {Async, Poll, Future, task};
 
 use io::AsyncRead;
 
 /// A future which can be used to easily read exactly enough bytes to fill
 /// a buffer.
 ///
-/// Created by the [`read_exact`] function.
+/// Created by the [`read_exact`] method.
 ///
-/// [`read_exact`]: fn.read_exact.html
+/// [`read_exact`]: trait.AsyncReadExt.html#method.read_exact
 #[derive(Debug)]
-pub struct ReadExact<A, T> {
-    state: State<A, T>,
+pub struct ReadExact<'a, A: ?Sized + 'a> {
+    reader: &'a mut A,
+    buf: &'a mut [u8],
+    pos: usize,
 }
 
-#[derive(Debug)]
-enum State<A, T> {
-    Reading {
-        a: A,
-        buf: T,
-        pos: usize,
-    },
-    Empty,
-}
-
-pub fn read_exact<A, T>(a: A, buf: T) -> ReadExact<A, T>
-    where A: AsyncRead,
-          T: AsMut<[u8]>,
+pub fn read_exact<'a, A>(reader: &'a mut A, buf: &'a mut [u8]) -> ReadExact<'a, A>
+    where A: ?Sized + AsyncRead,
 {
     ReadExact {
-        state: State::Reading {
-            a,
-            buf,
-            pos: 0,
-        },
+        reader,
+        buf,
+        pos: 0,
     }
 }
 
@@ -43,31 +31,30 @@ fn eof() -> io::Error {
     io::Error::new(io::ErrorKind::UnexpectedEof, "early eof")
 }
 
-impl<A, T> Future for ReadExact<A, T>
-    where A: AsyncRead,
-          T: AsMut<[u8]>,
+// Fills `buf[*pos..]`, advancing `pos` past every byte read so that a
+// `Pending` can be resumed by the next call. Shared with the futures which
+// own the buffer they read into.
+pub fn read_exact_internal<A: AsyncRead + ?Sized>(a: &mut A,
+                                                   cx: &mut task::Context,
+                                                   buf: &mut [u8],
+                                                   pos: &mut usize)
+    -> Poll<(), io::Error>
 {
-    type Item = (A, T);
-    type Error = io::Error;
-
-    fn poll(&mut self, cx: &mut task::Context) -> Poll<(A, T), io::Error> {
-        match self.state {
-            State::Reading { ref mut a, ref mut buf, ref mut pos } => {
-                let buf = buf.as_mut();
-                while *pos < buf.len() {
-                    let n = try_ready!(a.poll_read(cx, &mut buf[*pos..]));
-                    *pos += n;
-                    if n == 0 {
-                        return Err(eof())
-                    }
-                }
-            }
-            State::Empty => panic!("poll a ReadExact after it's done"),
+    while *pos < buf.len() {
+        let n = try_ready!(a.poll_read(cx, &mut buf[*pos..]));
+        *pos += n;
+        if n == 0 {
+            return Err(eof())
         }
+    }
+    Ok(Async::Ready(()))
+}
 
-        match mem::replace(&mut self.state, State::Empty) {
-            State::Reading { a, buf, .. } => Ok((a, buf).into()),
-            State::Empty => panic!(),
-        }
+impl<'a, A: ?Sized + AsyncRead> Future for ReadExact<'a, A> {
+    type Item = ();
+    type Error = io::Error;
+
+    fn poll(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        read_exact_internal(self.reader, cx, self.buf, &mut self.pos)
     }
 }
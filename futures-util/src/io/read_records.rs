@@ -0,0 +1,49 @@
+use std::io;
+use std::vec::Vec;
+
+use {Async, Future, Poll, task};
+
+use futures_io::AsyncRead;
+
+use super::read_exact::read_exact_internal;
+
+/// A future which reads a number of fixed-size records.
+///
+/// This is created by the [`read_n_records`] method.
+///
+/// [`read_n_records`]: trait.AsyncReadExt.html#method.read_n_records
+#[derive(Debug)]
+pub struct ReadNRecords<'a, R: ?Sized + 'a> {
+    reader: &'a mut R,
+    buf: Vec<u8>,
+    pos: usize,
+    count: usize,
+    size: usize,
+}
+
+pub fn read_n_records<'a, R>(reader: &'a mut R, count: usize, size: usize)
+    -> ReadNRecords<'a, R>
+    where R: ?Sized + AsyncRead,
+{
+    let len = count.checked_mul(size).expect("record table size overflows usize");
+    ReadNRecords {
+        reader,
+        buf: vec![0; len],
+        pos: 0,
+        count,
+        size,
+    }
+}
+
+impl<'a, R: ?Sized + AsyncRead> Future for ReadNRecords<'a, R> {
+    type Item = Vec<Vec<u8>>;
+    type Error = io::Error;
+
+    fn poll(&mut self, cx: &mut task::Context) -> Poll<Vec<Vec<u8>>, io::Error> {
+        try_ready!(read_exact_internal(self.reader, cx, &mut self.buf, &mut self.pos));
+        if self.size == 0 {
+            return Ok(Async::Ready(vec![Vec::new(); self.count]))
+        }
+        Ok(Async::Ready(self.buf.chunks(self.size).map(|r| r.to_vec()).collect()))
+    }
+}
@@ -1,5 +1,4 @@
 use std::io;
-use std::mem;
 use std::vec::Vec;
 
 use {Async, Poll, Future, task};
@@ -9,31 +8,24 @@ use io::AsyncRead;
 /// A future which can be used to easily read the entire contents of a stream
 /// into a vector.
 ///
-/// Created by the [`read_to_end`] function.
+/// Created by the [`read_to_end`] method.
 ///
-/// [`read_to_end`]: fn.read_to_end.html
+/// [`read_to_end`]: trait.AsyncReadExt.html#method.read_to_end
 #[derive(Debug)]
-pub struct ReadToEnd<A> {
-    state: State<A>,
+pub struct ReadToEnd<'a, A: ?Sized + 'a> {
+    reader: &'a mut A,
+    buf: &'a mut Vec<u8>,
+    start_len: usize,
 }
 
-#[derive(Debug)]
-enum State<A> {
-    Reading {
-        a: A,
-        buf: Vec<u8>,
-    },
-    Empty,
-}
-
-pub fn read_to_end<A>(a: A, buf: Vec<u8>) -> ReadToEnd<A>
-    where A: AsyncRead,
+pub fn read_to_end<'a, A>(reader: &'a mut A, buf: &'a mut Vec<u8>) -> ReadToEnd<'a, A>
+    where A: ?Sized + AsyncRead,
 {
+    let start_len = buf.len();
     ReadToEnd {
-        state: State::Reading {
-            a,
-            buf,
-        }
+        reader,
+        buf,
+        start_len,
     }
 }
 
@@ -54,7 +46,7 @@ impl<'a> Drop for Guard<'a> {
 //
 // Because we're extending the buffer with uninitialized data for trusted
 // readers, we need to make sure to truncate that if any of this panics.
-fn read_to_end_internal<R: AsyncRead + ?Sized>(r: &mut R, cx: &mut task::Context, buf: &mut Vec<u8>)
+pub fn read_to_end_internal<R: AsyncRead + ?Sized>(r: &mut R, cx: &mut task::Context, buf: &mut Vec<u8>)
     -> Poll<usize, io::Error>
 {
     let start_len = buf.len();
@@ -87,26 +79,16 @@ fn read_to_end_internal<R: AsyncRead + ?Sized>(r: &mut R, cx: &mut task::Context
     ret
 }
 
-impl<A> Future for ReadToEnd<A>
-    where A: AsyncRead,
-{
-    type Item = (A, Vec<u8>);
+impl<'a, A: ?Sized + AsyncRead> Future for ReadToEnd<'a, A> {
+    type Item = usize;
     type Error = io::Error;
 
-    fn poll(&mut self, cx: &mut task::Context) -> Poll<(A, Vec<u8>), io::Error> {
-        match self.state {
-            State::Reading { ref mut a, ref mut buf } => {
-                // If we get `Ok`, then we know the stream hit EOF and we're done. If we
-                // hit "would block" then all the read data so far is in our buffer, and
-                // otherwise we propagate errors
-                try_ready!(read_to_end_internal(a, cx, buf));
-            },
-            State::Empty => panic!("poll ReadToEnd after it's done"),
-        }
-
-        match mem::replace(&mut self.state, State::Empty) {
-            State::Reading { a, buf } => Ok((a, buf).into()),
-            State::Empty => unreachable!(),
-        }
+    fn poll(&mut self, cx: &mut task::Context) -> Poll<usize, io::Error> {
+        // If we get `Ok`, then we know the stream hit EOF and we're done. If we
+        // hit "would block" then all the read data so far is in our buffer, and
+        // otherwise we propagate errors. The count covers every poll, not just
+        // the last one.
+        try_ready!(read_to_end_internal(self.reader, cx, self.buf));
+        Ok(Async::Ready(self.buf.len() - self.start_len))
     }
 }
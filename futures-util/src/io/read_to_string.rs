@@ -0,0 +1,49 @@
+use std::io;
+use std::mem;
+use std::string::String;
+use std::vec::Vec;
+
+use {Async, Future, Poll, task};
+
+use futures_io::AsyncRead;
+
+use super::read_to_end::read_to_end_internal;
+
+/// A future which reads the rest of a stream and appends it to a `String`.
+///
+/// This is created by the [`read_to_string`] method.
+///
+/// [`read_to_string`]: trait.AsyncReadExt.html#method.read_to_string
+#[derive(Debug)]
+pub struct ReadToString<'a, R: ?Sized + 'a> {
+    reader: &'a mut R,
+    bytes: Vec<u8>,
+    buf: &'a mut String,
+}
+
+pub fn read_to_string<'a, R>(reader: &'a mut R, buf: &'a mut String) -> ReadToString<'a, R>
+    where R: ?Sized + AsyncRead,
+{
+    ReadToString {
+        reader,
+        bytes: Vec::new(),
+        buf,
+    }
+}
+
+impl<'a, R: ?Sized + AsyncRead> Future for ReadToString<'a, R> {
+    type Item = usize;
+    type Error = io::Error;
+
+    fn poll(&mut self, cx: &mut task::Context) -> Poll<usize, io::Error> {
+        try_ready!(read_to_end_internal(self.reader, cx, &mut self.bytes));
+        let bytes = mem::replace(&mut self.bytes, Vec::new());
+        // The bytes are only appended once they are known to be valid, so
+        // `buf` is left as it was on error.
+        let s = String::from_utf8(bytes).map_err(|_| {
+            io::Error::new(io::ErrorKind::InvalidData, "stream did not contain valid UTF-8")
+        })?;
+        self.buf.push_str(&s);
+        Ok(Async::Ready(s.len()))
+    }
+}
@@ -1,75 +1,60 @@
-use std::io::{self, BufRead};
-use std::mem;
+use std::io;
 use std::vec::Vec;
 
-use {Poll, Future};
+use {Async, Future, Poll, task};
 
-use io::AsyncRead;
+use futures_io::AsyncBufRead;
 
 /// A future which can be used to easily read the contents of a stream into a
 /// vector until the delimiter is reached.
 ///
-/// Created by the [`read_until`] function.
+/// Created by the [`read_until`] method.
 ///
-/// [`read_until`]: fn.read_until.html
+/// [`read_until`]: trait.AsyncBufReadExt.html#method.read_until
 #[derive(Debug)]
-pub struct ReadUntil<A> {
-    state: State<A>,
+pub struct ReadUntil<'a, A: ?Sized + 'a> {
+    reader: &'a mut A,
+    byte: u8,
+    buf: &'a mut Vec<u8>,
+    read: usize,
 }
 
-#[derive(Debug)]
-enum State<A> {
-    Reading {
-        a: A,
-        byte: u8,
-        buf: Vec<u8>,
-    },
-    Empty,
-}
-
-/// Creates a future which will read all the bytes associated with the I/O
-/// object `A` into the buffer provided until the delimiter `byte` is reached.
-/// This method is the async equivalent to [`BufRead::read_until`].
-///
-/// In case of an error the buffer and the object will be discarded, with
-/// the error yielded. In the case of success the object will be destroyed and
-/// the buffer will be returned, with all bytes up to, and including, the delimiter
-/// (if found).
-///
-/// [`BufRead::read_until`]: https://doc.rust-lang.org/std/io/trait.BufRead.html#method.read_until
-pub fn read_until<A>(a: A, byte: u8, buf: Vec<u8>) -> ReadUntil<A>
-    where A: AsyncRead + BufRead,
+pub fn read_until<'a, A>(reader: &'a mut A, byte: u8, buf: &'a mut Vec<u8>) -> ReadUntil<'a, A>
+    where A: ?Sized + AsyncBufRead,
 {
     ReadUntil {
-        state: State::Reading {
-            a: a,
-            byte: byte,
-            buf: buf,
-        }
+        reader,
+        byte,
+        buf,
+        read: 0,
     }
 }
 
-impl<A> Future for ReadUntil<A>
-    where A: AsyncRead + BufRead
-{
-    type Item = (A, Vec<u8>);
+impl<'a, A: ?Sized + AsyncBufRead> Future for ReadUntil<'a, A> {
+    type Item = usize;
     type Error = io::Error;
 
-    fn poll(&mut self) -> Poll<(A, Vec<u8>), io::Error> {
-        match self.state {
-            State::Reading { ref mut a, byte, ref mut buf } => {
-                // If we get `Ok(n)`, then we know the stream hit EOF or the delimiter.
-                // and just return it, as we are finished.
-                // If we hit "would block" then all the read data so far
-                // is in our buffer, and otherwise we propagate errors.
-                try_ready!(a.read_until(byte, buf));
-            },
-            State::Empty => panic!("poll ReadUntil after it's done"),
-        }
-
-        match mem::replace(&mut self.state, State::Empty) {
-            State::Reading { a, byte: _, buf } => Ok((a, buf).into()),
-            State::Empty => unreachable!(),
+    fn poll(&mut self, cx: &mut task::Context) -> Poll<usize, io::Error> {
+        let byte = self.byte;
+        loop {
+            let (done, used) = {
+                let available = try_ready!(self.reader.poll_fill_buf(cx));
+                match available.iter().position(|&b| b == byte) {
+                    Some(i) => {
+                        self.buf.extend_from_slice(&available[..i + 1]);
+                        (true, i + 1)
+                    }
+                    None => {
+                        self.buf.extend_from_slice(available);
+                        (available.is_empty(), available.len())
+                    }
+                }
+            };
+            self.reader.consume(used);
+            self.read += used;
+            if done {
+                return Ok(Async::Ready(self.read))
+            }
         }
     }
 }
@@ -0,0 +1,30 @@
+use std::io;
+
+use {Future, Poll, task};
+
+use futures_io::AsyncReadable;
+
+/// A future which resolves once an I/O object has data available to read.
+///
+/// This is created by the [`readable`] method.
+///
+/// [`readable`]: trait.AsyncReadExt.html#method.readable
+#[derive(Debug)]
+pub struct Readable<'a, R: ?Sized + 'a> {
+    reader: &'a mut R,
+}
+
+pub fn readable<'a, R>(reader: &'a mut R) -> Readable<'a, R>
+    where R: ?Sized + AsyncReadable,
+{
+    Readable { reader }
+}
+
+impl<'a, R: ?Sized + AsyncReadable> Future for Readable<'a, R> {
+    type Item = ();
+    type Error = io::Error;
+
+    fn poll(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        self.reader.poll_readable(cx)
+    }
+}
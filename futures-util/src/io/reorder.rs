@@ -0,0 +1,148 @@
+use std::cmp;
+use std::collections::BTreeMap;
+use std::io;
+use std::vec::Vec;
+
+use {Async, Poll, task};
+use futures_core::Stream;
+
+use futures_io::{AsyncRead, AsyncReadable};
+
+/// An `AsyncRead` which reassembles a byte stream out of segments labeled
+/// with their offset, which may arrive in any order.
+///
+/// Segments are pulled from the underlying stream and buffered until the
+/// segment covering the current read position is available, so bytes are
+/// always served strictly in offset order. Reads return `Async::Pending`
+/// while the next in-order segment has not yet arrived.
+///
+/// Overlapping and duplicate segments are allowed: any range which has
+/// already been delivered is ignored. If the underlying stream ends while
+/// there is still a gap before buffered segments, reading fails with an
+/// `UnexpectedEof` error.
+#[derive(Debug)]
+pub struct ReorderReader<S> {
+    stream: S,
+    segments: BTreeMap<u64, Vec<u8>>,
+    pos: u64,
+    done: bool,
+}
+
+impl<S> ReorderReader<S>
+    where S: Stream<Item = (u64, Vec<u8>), Error = io::Error>,
+{
+    /// Creates a new `ReorderReader` reassembling the segments of `stream`,
+    /// starting at offset 0.
+    pub fn new(stream: S) -> ReorderReader<S> {
+        ReorderReader {
+            stream,
+            segments: BTreeMap::new(),
+            pos: 0,
+            done: false,
+        }
+    }
+
+    /// Returns the offset of the next byte to be read.
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    /// Returns a reference to the underlying stream.
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    /// Returns a mutable reference to the underlying stream.
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+
+    /// Consumes this `ReorderReader`, returning the underlying stream.
+    ///
+    /// Any buffered segments are lost.
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+
+    fn insert(&mut self, offset: u64, data: Vec<u8>) {
+        if offset + data.len() as u64 <= self.pos {
+            return
+        }
+        let replace = match self.segments.get(&offset) {
+            Some(existing) => existing.len() < data.len(),
+            None => true,
+        };
+        if replace {
+            self.segments.insert(offset, data);
+        }
+    }
+
+    fn has_next(&self) -> bool {
+        let pos = self.pos;
+        self.segments.range(..pos + 1)
+            .any(|(&offset, segment)| offset + segment.len() as u64 > pos)
+    }
+
+    fn read_buffered(&mut self, buf: &mut [u8]) -> Option<usize> {
+        while let Some(offset) = self.segments.keys().next().cloned() {
+            if offset > self.pos {
+                return None
+            }
+            let segment = self.segments.remove(&offset).unwrap();
+            if offset + segment.len() as u64 <= self.pos {
+                continue
+            }
+            let start = (self.pos - offset) as usize;
+            let n = cmp::min(buf.len(), segment.len() - start);
+            buf[..n].copy_from_slice(&segment[start..start + n]);
+            self.pos += n as u64;
+            if start + n < segment.len() {
+                self.segments.insert(offset, segment);
+            }
+            return Some(n)
+        }
+        None
+    }
+}
+
+impl<S> AsyncRead for ReorderReader<S>
+    where S: Stream<Item = (u64, Vec<u8>), Error = io::Error>,
+{
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        if buf.is_empty() {
+            return Ok(Async::Ready(0))
+        }
+        loop {
+            if let Some(n) = self.read_buffered(buf) {
+                return Ok(Async::Ready(n))
+            }
+            if self.done {
+                if self.segments.is_empty() {
+                    return Ok(Async::Ready(0))
+                }
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof,
+                                          "segment stream ended with a gap"))
+            }
+            match try_ready!(self.stream.poll_next(cx)) {
+                Some((offset, data)) => self.insert(offset, data),
+                None => self.done = true,
+            }
+        }
+    }
+}
+
+impl<S> AsyncReadable for ReorderReader<S>
+    where S: Stream<Item = (u64, Vec<u8>), Error = io::Error>,
+{
+    fn poll_readable(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        while !self.done && !self.has_next() {
+            match try_ready!(self.stream.poll_next(cx)) {
+                Some((offset, data)) => self.insert(offset, data),
+                None => self.done = true,
+            }
+        }
+        Ok(Async::Ready(()))
+    }
+}
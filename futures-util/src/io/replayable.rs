@@ -0,0 +1,131 @@
+use std::cmp;
+use std::io;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::vec::Vec;
+
+use {Async, Poll, task};
+
+use futures_io::AsyncRead;
+
+// The number of bytes requested from the source at a time.
+const CHUNK: usize = 8 * 1024;
+
+/// A reader which caches every byte read from the underlying reader, so that
+/// the stream can be read again from the start with [`replay`].
+///
+/// Reading a `Replayable` reads the underlying reader lazily, as the bytes
+/// are asked for. Each reader created by `replay` starts again at the first
+/// byte and is served from the cache; one which catches up with the cache
+/// goes on reading the underlying reader itself, so a replay can be created
+/// and read before the first pass has finished, or without it ever being
+/// read at all.
+///
+/// The whole stream is kept in memory for as long as any of the readers
+/// lives.
+///
+/// [`replay`]: #method.replay
+#[derive(Debug)]
+pub struct Replayable<R> {
+    shared: Arc<Mutex<Shared<R>>>,
+    pos: usize,
+}
+
+/// A reader over the bytes of a `Replayable`, from the start.
+///
+/// Created by the [`replay`] method.
+///
+/// [`replay`]: struct.Replayable.html#method.replay
+#[derive(Debug)]
+pub struct Replay<R> {
+    shared: Arc<Mutex<Shared<R>>>,
+    pos: usize,
+}
+
+#[derive(Debug)]
+struct Shared<R> {
+    inner: R,
+    cache: Vec<u8>,
+    eof: bool,
+}
+
+fn lock<'a, R>(shared: &'a Mutex<Shared<R>>) -> MutexGuard<'a, Shared<R>> {
+    shared.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl<R: AsyncRead> Replayable<R> {
+    /// Creates a new `Replayable` reading from `inner`.
+    pub fn new(inner: R) -> Replayable<R> {
+        Replayable {
+            shared: Arc::new(Mutex::new(Shared {
+                inner,
+                cache: Vec::new(),
+                eof: false,
+            })),
+            pos: 0,
+        }
+    }
+
+    /// Creates a reader which yields the stream again from the first byte.
+    pub fn replay(&self) -> Replay<R> {
+        Replay {
+            shared: self.shared.clone(),
+            pos: 0,
+        }
+    }
+
+    /// Returns the number of bytes read from the underlying reader so far.
+    pub fn cached(&self) -> usize {
+        lock(&self.shared).cache.len()
+    }
+}
+
+impl<R: AsyncRead> Replay<R> {
+    /// Creates another reader which yields the stream from the first byte.
+    pub fn replay(&self) -> Replay<R> {
+        Replay {
+            shared: self.shared.clone(),
+            pos: 0,
+        }
+    }
+}
+
+impl<R: AsyncRead> Shared<R> {
+    fn poll_read_at(&mut self, cx: &mut task::Context, pos: &mut usize, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        if *pos == self.cache.len() && !self.eof && !buf.is_empty() {
+            let len = self.cache.len();
+            self.cache.resize(len + CHUNK, 0);
+            let res = self.inner.poll_read(cx, &mut self.cache[len..]);
+            let n = match res {
+                Ok(Async::Ready(n)) => n,
+                _ => 0,
+            };
+            self.cache.truncate(len + n);
+            if try_ready!(res) == 0 {
+                self.eof = true;
+            }
+        }
+
+        let n = cmp::min(buf.len(), self.cache.len() - *pos);
+        buf[..n].copy_from_slice(&self.cache[*pos..*pos + n]);
+        *pos += n;
+        Ok(Async::Ready(n))
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for Replayable<R> {
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        lock(&self.shared).poll_read_at(cx, &mut self.pos, buf)
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for Replay<R> {
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        lock(&self.shared).poll_read_at(cx, &mut self.pos, buf)
+    }
+}
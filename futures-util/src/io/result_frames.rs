@@ -0,0 +1,86 @@
+use std::io;
+use std::vec::Vec;
+
+use {Async, Poll, task};
+use futures_core::Stream;
+
+use futures_io::AsyncRead;
+
+/// A stream of the frames decoded from a reader, each of which is either a
+/// success or an application-level error.
+///
+/// Created by the [`result_frames`] method.
+///
+/// [`result_frames`]: trait.AsyncReadExt.html#method.result_frames
+#[derive(Debug)]
+pub struct ResultFrames<R, D> {
+    inner: R,
+    decode: D,
+    buf: Vec<u8>,
+    eof: bool,
+}
+
+pub fn result_frames<R, D, T, E>(inner: R, decode: D) -> ResultFrames<R, D>
+    where R: AsyncRead,
+          D: FnMut(&mut Vec<u8>) -> Option<Result<T, E>>,
+{
+    ResultFrames {
+        inner,
+        decode,
+        buf: Vec::new(),
+        eof: false,
+    }
+}
+
+impl<R, D> ResultFrames<R, D> {
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this `ResultFrames`, returning the underlying reader.
+    ///
+    /// Any buffered data which has not been decoded yet is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R, D, T, E> Stream for ResultFrames<R, D>
+    where R: AsyncRead,
+          D: FnMut(&mut Vec<u8>) -> Option<Result<T, E>>,
+{
+    type Item = Result<T, E>;
+    type Error = io::Error;
+
+    fn poll_next(&mut self, cx: &mut task::Context)
+        -> Poll<Option<Result<T, E>>, io::Error>
+    {
+        loop {
+            if let Some(frame) = (self.decode)(&mut self.buf) {
+                return Ok(Async::Ready(Some(frame)))
+            }
+
+            if self.eof {
+                if self.buf.is_empty() {
+                    return Ok(Async::Ready(None))
+                }
+                self.buf.clear();
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof,
+                                          "stream ended in the middle of a frame"))
+            }
+
+            let mut chunk = [0; 1024];
+            let n = try_ready!(self.inner.poll_read(cx, &mut chunk));
+            if n == 0 {
+                self.eof = true;
+            }
+            self.buf.extend_from_slice(&chunk[..n]);
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use std::io;
+use std::time::Duration;
+use std::vec::Vec;
+
+use {Async, Future, Poll, task};
+use futures_core::Never;
+
+use futures_io::{AsyncRead, Initializer};
+
+/// Configures which read errors are retried by [`retry_read`], how often,
+/// and how long to wait in between.
+///
+/// The delay before the `n`th retry is the initial backoff doubled `n - 1`
+/// times, saturating at the largest `Duration`.
+///
+/// [`retry_read`]: trait.AsyncReadExt.html#method.retry_read
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    kinds: Vec<io::ErrorKind>,
+    max_retries: usize,
+    backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Creates a policy retrying up to `max_retries` times in a row, first
+    /// waiting for `backoff`.
+    ///
+    /// By default the `ConnectionReset`, `ConnectionAborted` and `TimedOut`
+    /// error kinds are considered transient.
+    pub fn new(max_retries: usize, backoff: Duration) -> RetryPolicy {
+        RetryPolicy {
+            kinds: vec![
+                io::ErrorKind::ConnectionReset,
+                io::ErrorKind::ConnectionAborted,
+                io::ErrorKind::TimedOut,
+            ],
+            max_retries,
+            backoff,
+        }
+    }
+
+    /// Sets the error kinds which are considered transient and retried.
+    pub fn transient_kinds(&mut self, kinds: &[io::ErrorKind]) -> &mut RetryPolicy {
+        self.kinds = kinds.to_vec();
+        self
+    }
+
+    /// Returns whether errors of the given kind are retried.
+    pub fn is_transient(&self, kind: io::ErrorKind) -> bool {
+        self.kinds.contains(&kind)
+    }
+
+    /// Returns the delay before the `attempt`th retry in a row, counting from
+    /// one.
+    ///
+    /// A delay too long for a `Duration` is the largest `Duration` instead.
+    pub fn backoff(&self, attempt: usize) -> Duration {
+        let mut delay = self.backoff;
+        for _ in 1..attempt {
+            delay = match delay.checked_mul(2) {
+                Some(delay) => delay,
+                None => return Duration::new(::std::u64::MAX, 999_999_999),
+            };
+        }
+        delay
+    }
+}
+
+/// An `AsyncRead` which retries reads failing with transient errors.
+///
+/// Created by the [`retry_read`] method.
+///
+/// [`retry_read`]: trait.AsyncReadExt.html#method.retry_read
+#[derive(Debug)]
+pub struct RetryRead<R, F, T> {
+    inner: R,
+    policy: RetryPolicy,
+    timer: F,
+    delay: Option<T>,
+    attempts: usize,
+}
+
+pub fn retry_read<R, F, T>(inner: R, policy: RetryPolicy, timer: F) -> RetryRead<R, F, T>
+    where R: AsyncRead,
+          F: FnMut(Duration) -> T,
+          T: Future<Item = (), Error = Never>,
+{
+    RetryRead {
+        inner,
+        policy,
+        timer,
+        delay: None,
+        attempts: 0,
+    }
+}
+
+impl<R, F, T> RetryRead<R, F, T> {
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this `RetryRead`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R, F, T> AsyncRead for RetryRead<R, F, T>
+    where R: AsyncRead,
+          F: FnMut(Duration) -> T,
+          T: Future<Item = (), Error = Never>,
+{
+    unsafe fn initializer(&self) -> Initializer {
+        self.inner.initializer()
+    }
+
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        loop {
+            if let Some(ref mut delay) = self.delay {
+                match delay.poll(cx) {
+                    Ok(Async::Ready(())) => {}
+                    Ok(Async::Pending) => return Ok(Async::Pending),
+                    Err(never) => match never {},
+                }
+            }
+            self.delay = None;
+
+            match self.inner.poll_read(cx, buf) {
+                Ok(Async::Ready(n)) => {
+                    self.attempts = 0;
+                    return Ok(Async::Ready(n))
+                }
+                Ok(Async::Pending) => return Ok(Async::Pending),
+                Err(ref e) if self.policy.is_transient(e.kind()) &&
+                              self.attempts < self.policy.max_retries => {
+                    self.attempts += 1;
+                    let backoff = self.policy.backoff(self.attempts);
+                    self.delay = Some((self.timer)(backoff));
+                }
+                Err(e) => {
+                    self.attempts = 0;
+                    return Err(e)
+                }
+            }
+        }
+    }
+}
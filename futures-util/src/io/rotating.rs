@@ -0,0 +1,120 @@
+use std::cmp;
+use std::io;
+
+use {Async, Poll, task};
+
+use futures_io::AsyncWrite;
+
+/// A writer which rolls over to a fresh underlying writer every time a fixed
+/// number of bytes has been written, such as for rotating log files.
+///
+/// Underlying writers are obtained lazily from a factory closure. Once the
+/// current writer has received `limit` bytes it is closed and the next write
+/// goes to a new writer, with a single `poll_write` spanning the boundary if
+/// needed. `poll_flush` and `poll_close` only affect the current writer.
+#[derive(Debug)]
+pub struct RotatingWriter<W, F> {
+    factory: F,
+    current: Option<W>,
+    written: u64,
+    limit: u64,
+}
+
+impl<W, F> RotatingWriter<W, F>
+    where W: AsyncWrite,
+          F: FnMut() -> W,
+{
+    /// Creates a new `RotatingWriter` which writes at most `limit` bytes to
+    /// each writer returned by `factory`.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `limit` is zero.
+    pub fn new(limit: u64, factory: F) -> RotatingWriter<W, F> {
+        assert!(limit > 0, "rotation limit must be non-zero");
+        RotatingWriter {
+            factory,
+            current: None,
+            written: 0,
+            limit,
+        }
+    }
+
+    /// Returns the number of bytes written to the current writer.
+    pub fn written(&self) -> u64 {
+        self.written
+    }
+
+    /// Returns a reference to the current writer, if one has been opened.
+    pub fn get_ref(&self) -> Option<&W> {
+        self.current.as_ref()
+    }
+
+    /// Returns a mutable reference to the current writer, if one has been
+    /// opened.
+    pub fn get_mut(&mut self) -> Option<&mut W> {
+        self.current.as_mut()
+    }
+
+    /// Consumes this `RotatingWriter`, returning the current writer, if any.
+    pub fn into_inner(self) -> Option<W> {
+        self.current
+    }
+
+    fn write_some(&mut self, cx: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        if self.written == self.limit {
+            if let Some(ref mut current) = self.current {
+                try_ready!(current.poll_close(cx));
+            }
+            self.current = None;
+            self.written = 0;
+        }
+        if self.current.is_none() {
+            self.current = Some((self.factory)());
+        }
+
+        let left = self.limit - self.written;
+        let len = cmp::min(buf.len() as u64, left) as usize;
+        let n = try_ready!(self.current.as_mut().unwrap().poll_write(cx, &buf[..len]));
+        if n == 0 {
+            return Err(io::Error::new(io::ErrorKind::WriteZero, "zero-length write"))
+        }
+        self.written += n as u64;
+        Ok(Async::Ready(n))
+    }
+}
+
+impl<W, F> AsyncWrite for RotatingWriter<W, F>
+    where W: AsyncWrite,
+          F: FnMut() -> W,
+{
+    fn poll_write(&mut self, cx: &mut task::Context, buf: &[u8])
+        -> Poll<usize, io::Error>
+    {
+        let mut total = 0;
+        while total < buf.len() {
+            match self.write_some(cx, &buf[total..]) {
+                Ok(Async::Ready(n)) => total += n,
+                // Bytes have already been accepted, so report those and let
+                // the next call retry (and surface any error).
+                Ok(Async::Pending) | Err(_) if total > 0 => break,
+                other => return other,
+            }
+        }
+        Ok(Async::Ready(total))
+    }
+
+    fn poll_flush(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        match self.current {
+            Some(ref mut current) => current.poll_flush(cx),
+            None => Ok(Async::Ready(())),
+        }
+    }
+
+    fn poll_close(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        match self.current {
+            Some(ref mut current) => current.poll_close(cx),
+            None => Ok(Async::Ready(())),
+        }
+    }
+}
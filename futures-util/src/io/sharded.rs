@@ -0,0 +1,145 @@
+use std::io;
+use std::vec::Vec;
+
+use {Async, Future, Poll, task};
+
+use futures_io::AsyncWrite;
+
+/// A set of writers to which each payload is routed by the hash of its key.
+///
+/// Created by the [`sharded_write`] function.
+///
+/// [`sharded_write`]: fn.sharded_write.html
+#[derive(Debug)]
+pub struct ShardedWriter<W, F> {
+    backends: Vec<W>,
+    closed: Vec<bool>,
+    key_fn: F,
+}
+
+/// Creates a `ShardedWriter` which writes the payload for `key` to
+/// `backends[key_fn(key) % backends.len()]`.
+///
+/// Payloads with the same key therefore always end up in the same backend,
+/// in the order they were sent.
+///
+/// # Panics
+///
+/// Panics if `backends` is empty.
+pub fn sharded_write<W, F>(backends: Vec<W>, key_fn: F) -> ShardedWriter<W, F>
+    where W: AsyncWrite,
+          F: FnMut(&[u8]) -> u64,
+{
+    assert!(!backends.is_empty(), "sharded_write needs at least one backend");
+    ShardedWriter {
+        closed: vec![false; backends.len()],
+        backends,
+        key_fn,
+    }
+}
+
+impl<W, F> ShardedWriter<W, F>
+    where W: AsyncWrite,
+          F: FnMut(&[u8]) -> u64,
+{
+    /// Returns the index of the backend payloads for `key` are written to.
+    pub fn shard_for(&mut self, key: &[u8]) -> usize {
+        ((self.key_fn)(key) % self.backends.len() as u64) as usize
+    }
+
+    /// Creates a future which writes all of `buf` to the backend for `key`.
+    pub fn send<'a>(&'a mut self, key: &[u8], buf: &'a [u8]) -> SendShard<'a, W> {
+        let shard = self.shard_for(key);
+        SendShard {
+            writer: &mut self.backends[shard],
+            buf,
+        }
+    }
+
+    /// Flushes every backend, returning `Ready` once all of them are flushed.
+    pub fn poll_flush(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        let mut pending = false;
+        for backend in &mut self.backends {
+            if backend.poll_flush(cx)?.is_pending() {
+                pending = true;
+            }
+        }
+        if pending {
+            Ok(Async::Pending)
+        } else {
+            Ok(Async::Ready(()))
+        }
+    }
+
+    /// Flushes and closes every backend, returning `Ready` once all of them
+    /// are closed.
+    ///
+    /// Backends which have already been closed are not closed again.
+    pub fn poll_close(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        let mut pending = false;
+        for (backend, closed) in self.backends.iter_mut().zip(&mut self.closed) {
+            if *closed {
+                continue
+            }
+            match backend.poll_flush(cx)? {
+                Async::Ready(()) => {}
+                Async::Pending => {
+                    pending = true;
+                    continue
+                }
+            }
+            match backend.poll_close(cx)? {
+                Async::Ready(()) => *closed = true,
+                Async::Pending => pending = true,
+            }
+        }
+        if pending {
+            Ok(Async::Pending)
+        } else {
+            Ok(Async::Ready(()))
+        }
+    }
+
+    /// Returns references to the backends.
+    pub fn get_ref(&self) -> &[W] {
+        &self.backends
+    }
+
+    /// Returns mutable references to the backends.
+    pub fn get_mut(&mut self) -> &mut [W] {
+        &mut self.backends
+    }
+
+    /// Consumes this `ShardedWriter`, returning the backends.
+    pub fn into_inner(self) -> Vec<W> {
+        self.backends
+    }
+}
+
+/// A future which writes one payload to a backend of a `ShardedWriter`.
+///
+/// Created by the [`send`] method.
+///
+/// [`send`]: struct.ShardedWriter.html#method.send
+#[derive(Debug)]
+pub struct SendShard<'a, W: 'a> {
+    writer: &'a mut W,
+    buf: &'a [u8],
+}
+
+impl<'a, W: AsyncWrite> Future for SendShard<'a, W> {
+    type Item = ();
+    type Error = io::Error;
+
+    fn poll(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        while !self.buf.is_empty() {
+            let n = try_ready!(self.writer.poll_write(cx, self.buf));
+            if n == 0 {
+                return Err(io::Error::new(io::ErrorKind::WriteZero,
+                                          "failed to write whole payload"))
+            }
+            self.buf = &self.buf[n..];
+        }
+        Ok(Async::Ready(()))
+    }
+}
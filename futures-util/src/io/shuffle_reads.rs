@@ -0,0 +1,74 @@
+use std::cmp;
+use std::io;
+
+use {Async, Poll, task};
+
+use futures_io::{AsyncRead, Initializer};
+
+// The largest group of bytes which is shuffled together.
+const WINDOW: usize = 16;
+
+/// An `AsyncRead` which returns the bytes of the underlying reader in small,
+/// locally shuffled groups.
+///
+/// Created by the [`shuffle_reads`] method.
+///
+/// [`shuffle_reads`]: trait.AsyncReadExt.html#method.shuffle_reads
+#[derive(Debug)]
+pub struct ShuffleReads<R> {
+    inner: R,
+    state: u64,
+}
+
+pub fn shuffle_reads<R>(inner: R, seed: u64) -> ShuffleReads<R>
+    where R: AsyncRead,
+{
+    ShuffleReads {
+        inner,
+        // xorshift gets stuck on an all-zero state
+        state: if seed == 0 { 0x9e37_79b9_7f4a_7c15 } else { seed },
+    }
+}
+
+impl<R> ShuffleReads<R> {
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this `ShuffleReads`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn next_random(&mut self) -> u64 {
+        // xorshift64*
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for ShuffleReads<R> {
+    unsafe fn initializer(&self) -> Initializer {
+        self.inner.initializer()
+    }
+
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        let len = cmp::min(buf.len(), WINDOW);
+        let n = try_ready!(self.inner.poll_read(cx, &mut buf[..len]));
+        for i in (1..n).rev() {
+            let j = (self.next_random() % (i as u64 + 1)) as usize;
+            buf.swap(i, j);
+        }
+        Ok(Async::Ready(n))
+    }
+}
@@ -0,0 +1,98 @@
+use std::cmp;
+use std::io;
+use std::vec::Vec;
+
+use {Async, Poll, task};
+
+use futures_io::AsyncRead;
+
+/// A reader which shows the first bytes of the stream to a callback before
+/// yielding them.
+///
+/// Created by the [`sniff_prefix`] method.
+///
+/// [`sniff_prefix`]: trait.AsyncReadExt.html#method.sniff_prefix
+#[derive(Debug)]
+pub struct SniffPrefix<R, F> {
+    inner: R,
+    len: usize,
+    sniff: Option<F>,
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+pub fn sniff_prefix<R, F>(inner: R, len: usize, sniff: F) -> SniffPrefix<R, F>
+    where R: AsyncRead,
+          F: FnOnce(&[u8]),
+{
+    SniffPrefix {
+        inner,
+        len,
+        sniff: Some(sniff),
+        buf: Vec::with_capacity(len),
+        pos: 0,
+    }
+}
+
+impl<R, F> SniffPrefix<R, F> {
+    /// Returns whether the prefix has been passed to the callback yet.
+    pub fn is_sniffed(&self) -> bool {
+        self.sniff.is_none()
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this `SniffPrefix`, returning the underlying reader.
+    ///
+    /// Any buffered bytes of the prefix which haven't been read yet are lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R, F> AsyncRead for SniffPrefix<R, F>
+    where R: AsyncRead,
+          F: FnOnce(&[u8]),
+{
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        if self.sniff.is_some() {
+            while self.buf.len() < self.len {
+                let filled = self.buf.len();
+                self.buf.resize(self.len, 0);
+                let res = self.inner.poll_read(cx, &mut self.buf[filled..]);
+                let n = match res {
+                    Ok(Async::Ready(n)) => n,
+                    _ => 0,
+                };
+                self.buf.truncate(filled + n);
+                if try_ready!(res) == 0 {
+                    break
+                }
+            }
+            let sniff = self.sniff.take().unwrap();
+            sniff(&self.buf);
+        }
+
+        if self.pos < self.buf.len() {
+            let n = cmp::min(buf.len(), self.buf.len() - self.pos);
+            buf[..n].copy_from_slice(&self.buf[self.pos..self.pos + n]);
+            self.pos += n;
+            if self.pos == self.buf.len() {
+                self.buf = Vec::new();
+                self.pos = 0;
+            }
+            return Ok(Async::Ready(n))
+        }
+        self.inner.poll_read(cx, buf)
+    }
+}
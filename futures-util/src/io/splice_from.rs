@@ -0,0 +1,143 @@
+use std::cmp;
+use std::io;
+use std::boxed::Box;
+
+use {Async, Future, Poll, task};
+
+use futures_io::{AsyncRead, AsyncWrite, IoVec};
+
+// The capacity of the ring buffer bytes move through.
+const CAPACITY: usize = 8 * 1024;
+
+/// A future which moves bytes from a reader into a writer.
+///
+/// This is created by the [`splice_from`] method.
+///
+/// [`splice_from`]: trait.AsyncWriteExt.html#method.splice_from
+#[derive(Debug)]
+pub struct SpliceFrom<'a, W: ?Sized + 'a, R: ?Sized + 'a> {
+    writer: &'a mut W,
+    reader: &'a mut R,
+    // The number of bytes still to be read, if bounded.
+    limit: Option<u64>,
+    read_done: bool,
+    buf: Box<[u8]>,
+    // The filled part of `buf` starts at `head`, is `len` bytes long and may
+    // wrap around its end.
+    head: usize,
+    len: usize,
+    amt: u64,
+}
+
+pub fn splice_from<'a, W, R>(writer: &'a mut W, reader: &'a mut R, len: Option<u64>)
+    -> SpliceFrom<'a, W, R>
+    where W: ?Sized + AsyncWrite,
+          R: ?Sized + AsyncRead,
+{
+    SpliceFrom {
+        writer,
+        reader,
+        limit: len,
+        read_done: len == Some(0),
+        buf: Box::new([0; CAPACITY]),
+        head: 0,
+        len: 0,
+        amt: 0,
+    }
+}
+
+impl<'a, W, R> SpliceFrom<'a, W, R>
+    where W: ?Sized + AsyncWrite,
+          R: ?Sized + AsyncRead,
+{
+    fn poll_fill(&mut self, cx: &mut task::Context) -> Poll<usize, io::Error> {
+        let mut max = CAPACITY - self.len;
+        if let Some(limit) = self.limit {
+            max = cmp::min(max as u64, limit) as usize;
+        }
+        let end = (self.head + self.len) % CAPACITY;
+        let (front, back) = self.buf.split_at_mut(end);
+        let (first, second) = if end >= self.head {
+            (back, &mut front[..self.head])
+        } else {
+            (&mut back[..self.head - end], &mut front[..0])
+        };
+        let first_len = cmp::min(first.len(), max);
+        let second_len = cmp::min(second.len(), max - first_len);
+        let first = &mut first[..first_len];
+        let second = &mut second[..second_len];
+
+        match (IoVec::from_bytes_mut(first), IoVec::from_bytes_mut(second)) {
+            (Some(a), Some(b)) => self.reader.poll_vectored_read(cx, &mut [a, b]),
+            (Some(a), None) => self.reader.poll_read(cx, a),
+            _ => unreachable!(),
+        }
+    }
+
+    fn poll_drain(&mut self, cx: &mut task::Context) -> Poll<usize, io::Error> {
+        let first_end = cmp::min(self.head + self.len, CAPACITY);
+        let second_end = self.head + self.len - first_end;
+        let first = &self.buf[self.head..first_end];
+        let second = &self.buf[..second_end];
+
+        match (IoVec::from_bytes(first), IoVec::from_bytes(second)) {
+            (Some(a), Some(b)) => self.writer.poll_vectored_write(cx, &[a, b]),
+            (Some(a), None) => self.writer.poll_write(cx, a),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<'a, W, R> Future for SpliceFrom<'a, W, R>
+    where W: ?Sized + AsyncWrite,
+          R: ?Sized + AsyncRead,
+{
+    type Item = u64;
+    type Error = io::Error;
+
+    fn poll(&mut self, cx: &mut task::Context) -> Poll<u64, io::Error> {
+        loop {
+            let mut progress = false;
+
+            if !self.read_done && self.len < CAPACITY {
+                if let Async::Ready(n) = self.poll_fill(cx)? {
+                    progress = true;
+                    if n == 0 {
+                        self.read_done = true;
+                    }
+                    self.len += n;
+                    if let Some(ref mut limit) = self.limit {
+                        *limit -= n as u64;
+                        if *limit == 0 {
+                            self.read_done = true;
+                        }
+                    }
+                }
+            }
+
+            if self.len > 0 {
+                if let Async::Ready(n) = self.poll_drain(cx)? {
+                    if n == 0 {
+                        return Err(io::Error::new(io::ErrorKind::WriteZero,
+                                                  "write zero byte into writer"))
+                    }
+                    progress = true;
+                    self.head = (self.head + n) % CAPACITY;
+                    self.len -= n;
+                    self.amt += n as u64;
+                    if self.len == 0 {
+                        self.head = 0;
+                    }
+                }
+            }
+
+            if self.read_done && self.len == 0 {
+                try_ready!(self.writer.poll_flush(cx));
+                return Ok(Async::Ready(self.amt))
+            }
+            if !progress {
+                return Ok(Async::Pending)
+            }
+        }
+    }
+}
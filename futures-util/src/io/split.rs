@@ -1,3 +1,6 @@
+use std::any::Any;
+use std::error::Error as StdError;
+use std::fmt;
 use std::io;
 
 use {Async, Poll, task};
@@ -6,12 +9,18 @@ use lock::BiLock;
 use futures_io::{AsyncRead, AsyncWrite, Error, IoVec};
 
 /// The readable half of an object returned from `AsyncRead::split`.
+///
+/// The underlying object is only locked while this half is being polled,
+/// so the two halves may be used from different tasks.
 #[derive(Debug)]
 pub struct ReadHalf<T> {
     handle: BiLock<T>,
 }
 
 /// The writable half of an object returned from `AsyncRead::split`.
+///
+/// The underlying object is only locked while this half is being polled,
+/// so the two halves may be used from different tasks.
 #[derive(Debug)]
 pub struct WriteHalf<T> {
     handle: BiLock<T>,
@@ -31,6 +40,35 @@ pub fn split<T: AsyncRead + AsyncWrite>(t: T) -> (ReadHalf<T>, WriteHalf<T>) {
     (ReadHalf { handle: a }, WriteHalf { handle: b })
 }
 
+/// Puts the two halves of a split I/O object back together.
+///
+/// Succeeds only if `read` and `write` are a matching pair originating from
+/// the same call to `AsyncReadExt::split`; otherwise both halves are handed
+/// back in the error.
+pub fn reunite<T>(read: ReadHalf<T>, write: WriteHalf<T>) -> Result<T, ReuniteError<T>> {
+    read.handle.reunite(write.handle).map_err(|err| {
+        ReuniteError(ReadHalf { handle: err.0 }, WriteHalf { handle: err.1 })
+    })
+}
+
+impl<T> ReadHalf<T> {
+    /// Attempts to put the two halves of a split I/O object back together.
+    /// Succeeds only if `self` and `other` are a matching pair originating
+    /// from the same call to `AsyncReadExt::split`.
+    pub fn reunite(self, other: WriteHalf<T>) -> Result<T, ReuniteError<T>> {
+        reunite(self, other)
+    }
+}
+
+impl<T> WriteHalf<T> {
+    /// Attempts to put the two halves of a split I/O object back together.
+    /// Succeeds only if `self` and `other` are a matching pair originating
+    /// from the same call to `AsyncReadExt::split`.
+    pub fn reunite(self, other: ReadHalf<T>) -> Result<T, ReuniteError<T>> {
+        reunite(other, self)
+    }
+}
+
 impl<T: AsyncRead> AsyncRead for ReadHalf<T> {
     fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
         -> Poll<usize, io::Error>
@@ -66,3 +104,27 @@ impl<T: AsyncWrite> AsyncWrite for WriteHalf<T> {
         lock_and_then(&self.handle, cx, |l, cx| l.poll_close(cx))
     }
 }
+
+/// Error indicating a `ReadHalf<T>` and `WriteHalf<T>` were not two halves
+/// of the same I/O object, and thus could not be `reunite`d.
+pub struct ReuniteError<T>(pub ReadHalf<T>, pub WriteHalf<T>);
+
+impl<T> fmt::Debug for ReuniteError<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_tuple("ReuniteError")
+            .field(&"...")
+            .finish()
+    }
+}
+
+impl<T> fmt::Display for ReuniteError<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "tried to reunite a ReadHalf and WriteHalf that don't form a pair")
+    }
+}
+
+impl<T: Any> StdError for ReuniteError<T> {
+    fn description(&self) -> &str {
+        "tried to reunite a ReadHalf and WriteHalf that don't form a pair"
+    }
+}
@@ -0,0 +1,124 @@
+use std::cmp;
+use std::io;
+use std::mem;
+use std::vec::Vec;
+
+use {Async, Future, Poll, task};
+
+use futures_io::AsyncRead;
+
+/// A future which reads a stream up to a marker, resolving to the bytes
+/// before it and a reader for the bytes after it.
+///
+/// This is created by the [`split_at_marker`] method.
+///
+/// [`split_at_marker`]: trait.AsyncReadExt.html#method.split_at_marker
+#[derive(Debug)]
+pub struct SplitAtMarker<R> {
+    inner: Option<R>,
+    marker: Vec<u8>,
+    buf: Vec<u8>,
+    // The number of bytes at the start of `buf` known not to start a marker.
+    searched: usize,
+}
+
+/// The part of a stream after the marker found by `split_at_marker`.
+///
+/// Bytes which were read past the marker while looking for it are yielded
+/// first, followed by the rest of the underlying reader.
+#[derive(Debug)]
+pub struct MarkerBody<R> {
+    inner: R,
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+pub fn split_at_marker<R: AsyncRead>(inner: R, marker: &[u8]) -> SplitAtMarker<R> {
+    SplitAtMarker {
+        inner: Some(inner),
+        marker: marker.to_vec(),
+        buf: Vec::new(),
+        searched: 0,
+    }
+}
+
+impl<R: AsyncRead> SplitAtMarker<R> {
+    fn find(&mut self) -> Option<usize> {
+        let len = self.marker.len();
+        if self.buf.len() < len {
+            return None
+        }
+        let found = (self.searched..self.buf.len() - len + 1)
+            .find(|&i| self.buf[i..i + len] == self.marker[..]);
+        if found.is_none() {
+            self.searched = self.buf.len() - len + 1;
+        }
+        found
+    }
+}
+
+impl<R: AsyncRead> Future for SplitAtMarker<R> {
+    type Item = (Vec<u8>, MarkerBody<R>);
+    type Error = io::Error;
+
+    fn poll(&mut self, cx: &mut task::Context) -> Poll<(Vec<u8>, MarkerBody<R>), io::Error> {
+        loop {
+            if let Some(at) = self.find() {
+                let rest = self.buf.split_off(at + self.marker.len());
+                self.buf.truncate(at);
+                let body = MarkerBody {
+                    inner: self.inner.take().expect("cannot poll SplitAtMarker twice"),
+                    buf: rest,
+                    pos: 0,
+                };
+                return Ok(Async::Ready((mem::replace(&mut self.buf, Vec::new()), body)))
+            }
+
+            let mut chunk = [0; 1024];
+            let inner = self.inner.as_mut().expect("cannot poll SplitAtMarker twice");
+            let n = try_ready!(inner.poll_read(cx, &mut chunk));
+            if n == 0 {
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof,
+                                          "stream ended before the marker"))
+            }
+            self.buf.extend_from_slice(&chunk[..n]);
+        }
+    }
+}
+
+impl<R> MarkerBody<R> {
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this `MarkerBody`, returning the underlying reader.
+    ///
+    /// Any bytes read past the marker which haven't been yielded are lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for MarkerBody<R> {
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        if self.pos < self.buf.len() {
+            let n = cmp::min(buf.len(), self.buf.len() - self.pos);
+            buf[..n].copy_from_slice(&self.buf[self.pos..self.pos + n]);
+            self.pos += n;
+            if self.pos == self.buf.len() {
+                self.buf = Vec::new();
+                self.pos = 0;
+            }
+            return Ok(Async::Ready(n))
+        }
+        self.inner.poll_read(cx, buf)
+    }
+}
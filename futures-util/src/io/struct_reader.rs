@@ -0,0 +1,138 @@
+use std::io;
+use std::mem;
+use std::vec::Vec;
+
+use {Async, Future, Poll, task};
+
+use futures_io::AsyncRead;
+
+use super::read_exact::read_exact_internal;
+use super::read_to_end::read_to_end_internal;
+
+/// A description of a binary record made of fixed-length fields, optionally
+/// followed by one variable-length field running to the end of the stream.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate futures;
+/// use std::io::Cursor;
+///
+/// use futures::executor::block_on;
+/// use futures::io::StructReader;
+///
+/// # fn main() {
+/// let mut reader = Cursor::new(b"\x01\x02abcd".to_vec());
+/// let fields = block_on(StructReader::new(&[1, 1]).trailing(true).read(&mut reader)).unwrap();
+/// assert_eq!(fields, [&b"\x01"[..], b"\x02", b"abcd"]);
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct StructReader {
+    fields: Vec<usize>,
+    trailing: bool,
+}
+
+impl StructReader {
+    /// Creates a `StructReader` for records made of fields of the given
+    /// lengths, in order.
+    pub fn new(fields: &[usize]) -> StructReader {
+        StructReader {
+            fields: fields.to_vec(),
+            trailing: false,
+        }
+    }
+
+    /// Configures whether the fixed-length fields are followed by a field
+    /// made of all the remaining bytes of the stream.
+    pub fn trailing(&mut self, trailing: bool) -> &mut StructReader {
+        self.trailing = trailing;
+        self
+    }
+
+    /// Creates a future which reads one record from `reader`, resolving to
+    /// its fields in order.
+    ///
+    /// If the stream ends before all the fixed-length fields have been read
+    /// the future fails with an `UnexpectedEof` error.
+    pub fn read<'a, R>(&self, reader: &'a mut R) -> ReadStruct<'a, R>
+        where R: ?Sized + AsyncRead,
+    {
+        ReadStruct {
+            lens: self.fields.clone().into_iter(),
+            trailing: self.trailing,
+            state: State::Next(reader),
+            fields: Vec::new(),
+        }
+    }
+}
+
+/// A future which reads one record described by a `StructReader`.
+///
+/// This is created by the [`StructReader::read`] method.
+///
+/// [`StructReader::read`]: struct.StructReader.html#method.read
+#[derive(Debug)]
+pub struct ReadStruct<'a, R: ?Sized + 'a> {
+    lens: ::std::vec::IntoIter<usize>,
+    trailing: bool,
+    state: State<'a, R>,
+    fields: Vec<Vec<u8>>,
+}
+
+#[derive(Debug)]
+enum State<'a, R: ?Sized + 'a> {
+    Next(&'a mut R),
+    Fixed(&'a mut R, Vec<u8>, usize),
+    Trailing(&'a mut R, Vec<u8>),
+    Done,
+}
+
+impl<'a, R: ?Sized + AsyncRead> Future for ReadStruct<'a, R> {
+    type Item = Vec<Vec<u8>>;
+    type Error = io::Error;
+
+    fn poll(&mut self, cx: &mut task::Context) -> Poll<Vec<Vec<u8>>, io::Error> {
+        loop {
+            let reader = match self.state {
+                State::Next(_) => {
+                    match mem::replace(&mut self.state, State::Done) {
+                        State::Next(reader) => reader,
+                        _ => unreachable!(),
+                    }
+                }
+                State::Fixed(..) => {
+                    if let State::Fixed(ref mut reader, ref mut field, ref mut pos) = self.state {
+                        try_ready!(read_exact_internal(*reader, cx, field, pos));
+                    }
+                    match mem::replace(&mut self.state, State::Done) {
+                        State::Fixed(reader, field, _) => {
+                            self.fields.push(field);
+                            reader
+                        }
+                        _ => unreachable!(),
+                    }
+                }
+                State::Trailing(..) => {
+                    if let State::Trailing(ref mut reader, ref mut field) = self.state {
+                        try_ready!(read_to_end_internal(*reader, cx, field));
+                    }
+                    if let State::Trailing(_, field) = mem::replace(&mut self.state, State::Done) {
+                        self.fields.push(field);
+                    }
+                    return Ok(Async::Ready(mem::replace(&mut self.fields, Vec::new())))
+                }
+                State::Done => panic!("cannot poll ReadStruct twice"),
+            };
+
+            self.state = match self.lens.next() {
+                Some(len) => State::Fixed(reader, vec![0; len], 0),
+                None if self.trailing => State::Trailing(reader, Vec::new()),
+                None => {
+                    self.state = State::Done;
+                    return Ok(Async::Ready(mem::replace(&mut self.fields, Vec::new())))
+                }
+            };
+        }
+    }
+}
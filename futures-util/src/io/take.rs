@@ -0,0 +1,86 @@
+use std::cmp;
+use std::io;
+
+use {Async, Poll, task};
+
+use futures_io::{AsyncBufRead, AsyncRead, Initializer};
+
+/// A reader which yields at most a limited number of bytes from the
+/// underlying reader.
+///
+/// Created by the [`take`] method.
+///
+/// [`take`]: trait.AsyncReadExt.html#method.take
+#[derive(Debug)]
+pub struct Take<R> {
+    inner: R,
+    limit: u64,
+}
+
+pub fn take<R: AsyncRead>(inner: R, limit: u64) -> Take<R> {
+    Take { inner, limit }
+}
+
+impl<R> Take<R> {
+    /// Returns the number of bytes which may still be read before this
+    /// reader reports EOF.
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    /// Sets the number of bytes which may still be read, regardless of how
+    /// many have been read so far.
+    pub fn set_limit(&mut self, limit: u64) {
+        self.limit = limit;
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this `Take`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for Take<R> {
+    unsafe fn initializer(&self) -> Initializer {
+        self.inner.initializer()
+    }
+
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        if self.limit == 0 {
+            return Ok(Async::Ready(0))
+        }
+        let max = cmp::min(buf.len() as u64, self.limit) as usize;
+        let n = try_ready!(self.inner.poll_read(cx, &mut buf[..max]));
+        self.limit -= n as u64;
+        Ok(Async::Ready(n))
+    }
+}
+
+impl<R: AsyncBufRead> AsyncBufRead for Take<R> {
+    fn poll_fill_buf(&mut self, cx: &mut task::Context) -> Poll<&[u8], io::Error> {
+        if self.limit == 0 {
+            return Ok(Async::Ready(&[]))
+        }
+        let buf = try_ready!(self.inner.poll_fill_buf(cx));
+        let max = cmp::min(buf.len() as u64, self.limit) as usize;
+        Ok(Async::Ready(&buf[..max]))
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let amt = cmp::min(amt as u64, self.limit) as usize;
+        self.limit -= amt as u64;
+        self.inner.consume(amt);
+    }
+}
@@ -0,0 +1,87 @@
+use std::io;
+use std::vec::Vec;
+
+use {Async, Poll, task};
+
+use futures_io::{AsyncRead, AsyncWrite};
+
+/// A reader which mirrors every byte read from the underlying reader into a
+/// writer.
+///
+/// Created by the [`tee`] method.
+///
+/// [`tee`]: trait.AsyncReadExt.html#method.tee
+#[derive(Debug)]
+pub struct Tee<R, W> {
+    reader: R,
+    out: W,
+    // Bytes which have been read but not yet written to `out`.
+    pending: Vec<u8>,
+    pos: usize,
+}
+
+pub fn tee<R, W>(reader: R, out: W) -> Tee<R, W>
+    where R: AsyncRead,
+          W: AsyncWrite,
+{
+    Tee {
+        reader,
+        out,
+        pending: Vec::new(),
+        pos: 0,
+    }
+}
+
+impl<R, W> Tee<R, W> {
+    /// Returns references to the underlying reader and writer.
+    pub fn get_ref(&self) -> (&R, &W) {
+        (&self.reader, &self.out)
+    }
+
+    /// Returns mutable references to the underlying reader and writer.
+    pub fn get_mut(&mut self) -> (&mut R, &mut W) {
+        (&mut self.reader, &mut self.out)
+    }
+
+    /// Consumes this `Tee`, returning the underlying reader and writer.
+    ///
+    /// Any bytes which have been read but not yet written to the writer are
+    /// lost.
+    pub fn into_inner(self) -> (R, W) {
+        (self.reader, self.out)
+    }
+}
+
+impl<R, W: AsyncWrite> Tee<R, W> {
+    fn poll_mirror(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        while self.pos < self.pending.len() {
+            let n = try_ready!(self.out.poll_write(cx, &self.pending[self.pos..]));
+            if n == 0 {
+                return Err(io::Error::new(io::ErrorKind::WriteZero,
+                                          "failed to mirror read bytes"))
+            }
+            self.pos += n;
+        }
+        self.pending.clear();
+        self.pos = 0;
+        Ok(Async::Ready(()))
+    }
+}
+
+impl<R, W> AsyncRead for Tee<R, W>
+    where R: AsyncRead,
+          W: AsyncWrite,
+{
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        try_ready!(self.poll_mirror(cx));
+        let n = try_ready!(self.reader.poll_read(cx, buf));
+        self.pending.extend_from_slice(&buf[..n]);
+
+        // The caller gets the bytes either way; if they can't all be
+        // mirrored yet, any error or `Pending` is reported by the next call.
+        let _ = self.poll_mirror(cx);
+        Ok(Async::Ready(n))
+    }
+}
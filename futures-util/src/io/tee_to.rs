@@ -0,0 +1,104 @@
+use std::io;
+use std::vec::Vec;
+
+use {Async, Poll, task};
+
+use futures_io::AsyncWrite;
+
+/// A writer which writes everything to the underlying writer and a copy of
+/// it to a capture writer.
+///
+/// Created by the [`tee_to`] method.
+///
+/// [`tee_to`]: trait.AsyncWriteExt.html#method.tee_to
+#[derive(Debug)]
+pub struct TeeTo<W, C> {
+    inner: W,
+    capture: C,
+    // Bytes accepted by `inner` which `capture` hasn't accepted yet.
+    pending: Vec<u8>,
+    pos: usize,
+}
+
+pub fn tee_to<W, C>(inner: W, capture: C) -> TeeTo<W, C>
+    where W: AsyncWrite,
+          C: AsyncWrite,
+{
+    TeeTo {
+        inner,
+        capture,
+        pending: Vec::new(),
+        pos: 0,
+    }
+}
+
+impl<W, C> TeeTo<W, C> {
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns a reference to the capture writer.
+    pub fn capture_ref(&self) -> &C {
+        &self.capture
+    }
+
+    /// Returns a mutable reference to the capture writer.
+    pub fn capture_mut(&mut self) -> &mut C {
+        &mut self.capture
+    }
+
+    /// Consumes this `TeeTo`, returning the underlying and capture writers.
+    ///
+    /// Any bytes not yet written to the capture writer are lost.
+    pub fn into_inner(self) -> (W, C) {
+        (self.inner, self.capture)
+    }
+}
+
+impl<W: AsyncWrite, C: AsyncWrite> TeeTo<W, C> {
+    fn poll_capture(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        while self.pos < self.pending.len() {
+            let n = try_ready!(self.capture.poll_write(cx, &self.pending[self.pos..]));
+            if n == 0 {
+                return Err(io::Error::new(io::ErrorKind::WriteZero,
+                                          "failed to write to the capture writer"))
+            }
+            self.pos += n;
+        }
+        self.pending.clear();
+        self.pos = 0;
+        Ok(Async::Ready(()))
+    }
+}
+
+impl<W: AsyncWrite, C: AsyncWrite> AsyncWrite for TeeTo<W, C> {
+    fn poll_write(&mut self, cx: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        try_ready!(self.poll_capture(cx));
+        let n = try_ready!(self.inner.poll_write(cx, buf));
+        self.pending.extend_from_slice(&buf[..n]);
+
+        // The bytes have been accepted by `inner`, so they must be reported
+        // as written; any error or `Pending` of the capture writer is
+        // reported by the next call instead.
+        let _ = self.poll_capture(cx);
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_flush(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        try_ready!(self.poll_capture(cx));
+        try_ready!(self.inner.poll_flush(cx));
+        self.capture.poll_flush(cx)
+    }
+
+    fn poll_close(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        try_ready!(self.poll_capture(cx));
+        try_ready!(self.inner.poll_close(cx));
+        self.capture.poll_close(cx)
+    }
+}
@@ -0,0 +1,78 @@
+use std::cmp;
+use std::io;
+
+use {Async, Poll, task};
+
+use futures_io::{AsyncRead, Initializer, IoVec};
+
+/// A reader which caps the number of bytes a single read can return.
+///
+/// Created by the [`throttle`] method.
+///
+/// [`throttle`]: trait.AsyncReadExt.html#method.throttle
+#[derive(Debug)]
+pub struct Throttle<R> {
+    inner: R,
+    limit: usize,
+}
+
+pub fn throttle<R: AsyncRead>(inner: R, limit: usize) -> Throttle<R> {
+    assert!(limit > 0, "a throttle of zero bytes per read would starve the reader");
+    Throttle { inner, limit }
+}
+
+impl<R> Throttle<R> {
+    /// Returns the maximum number of bytes a single read returns.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Sets the maximum number of bytes a single read returns.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `limit` is zero.
+    pub fn set_limit(&mut self, limit: usize) {
+        assert!(limit > 0, "a throttle of zero bytes per read would starve the reader");
+        self.limit = limit;
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this `Throttle`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for Throttle<R> {
+    unsafe fn initializer(&self) -> Initializer {
+        self.inner.initializer()
+    }
+
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        let max = cmp::min(buf.len(), self.limit);
+        self.inner.poll_read(cx, &mut buf[..max])
+    }
+
+    fn poll_vectored_read(&mut self, cx: &mut task::Context, vec: &mut [&mut IoVec])
+        -> Poll<usize, io::Error>
+    {
+        // Filling several buffers could exceed the limit, so only the first
+        // non-empty one is read into.
+        match vec.iter_mut().find(|buf| !buf.is_empty()) {
+            Some(buf) => self.poll_read(cx, buf),
+            None => Ok(Async::Ready(0)),
+        }
+    }
+}
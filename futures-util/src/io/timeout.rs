@@ -0,0 +1,86 @@
+use std::io;
+use std::time::Duration;
+
+use {Async, Future, Poll, task};
+use futures_core::Never;
+
+use futures_io::{AsyncRead, Initializer};
+
+/// A reader which fails a read with a `TimedOut` error if the underlying
+/// reader stays idle for too long.
+///
+/// Created by the [`timeout`] method.
+///
+/// [`timeout`]: trait.AsyncReadExt.html#method.timeout
+#[derive(Debug)]
+pub struct Timeout<R, F, T> {
+    inner: R,
+    dur: Duration,
+    timer: F,
+    // The idle timer, started by the first read which has to wait.
+    delay: Option<T>,
+}
+
+pub fn timeout<R, F, T>(inner: R, dur: Duration, timer: F) -> Timeout<R, F, T>
+    where R: AsyncRead,
+          F: FnMut(Duration) -> T,
+          T: Future<Item = (), Error = Never>,
+{
+    Timeout {
+        inner,
+        dur,
+        timer,
+        delay: None,
+    }
+}
+
+impl<R, F, T> Timeout<R, F, T> {
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this `Timeout`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R, F, T> AsyncRead for Timeout<R, F, T>
+    where R: AsyncRead,
+          F: FnMut(Duration) -> T,
+          T: Future<Item = (), Error = Never>,
+{
+    unsafe fn initializer(&self) -> Initializer {
+        self.inner.initializer()
+    }
+
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        match self.inner.poll_read(cx, buf) {
+            Ok(Async::Pending) => {}
+            res => {
+                self.delay = None;
+                return res
+            }
+        }
+
+        let dur = self.dur;
+        let timer = &mut self.timer;
+        let delay = self.delay.get_or_insert_with(|| timer(dur));
+        match delay.poll(cx) {
+            Ok(Async::Ready(())) => {
+                self.delay = None;
+                Err(io::Error::new(io::ErrorKind::TimedOut, "read timed out"))
+            }
+            Ok(Async::Pending) => Ok(Async::Pending),
+            Err(never) => match never {},
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use std::io;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::vec::Vec;
+
+use {Async, Poll, task};
+use futures_core::task::Waker;
+
+use futures_io::{AsyncRead, Initializer};
+
+/// A flag which can be set once to cooperatively cancel I/O, waking every
+/// task waiting on it.
+///
+/// Handles created with `clone` share the same flag.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<Mutex<State>>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    cancelled: bool,
+    waiters: Vec<Waker>,
+}
+
+fn lock<'a>(state: &'a Mutex<State>) -> MutexGuard<'a, State> {
+    state.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl CancellationToken {
+    /// Creates a new token which hasn't been cancelled.
+    pub fn new() -> CancellationToken {
+        CancellationToken::default()
+    }
+
+    /// Cancels this token, waking up every task waiting on it.
+    pub fn cancel(&self) {
+        let mut state = lock(&self.inner);
+        state.cancelled = true;
+        for waker in state.waiters.drain(..) {
+            waker.wake();
+        }
+    }
+
+    /// Returns whether this token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        lock(&self.inner).cancelled
+    }
+
+    /// Returns whether this token has been cancelled, and if it hasn't,
+    /// schedules the current task to be woken up once it is.
+    pub fn poll_cancelled(&self, cx: &mut task::Context) -> bool {
+        let mut state = lock(&self.inner);
+        if !state.cancelled && !state.waiters.iter().any(|w| w.will_wake(cx.waker())) {
+            state.waiters.push(cx.waker().clone());
+        }
+        state.cancelled
+    }
+}
+
+/// A reader which fails with an `Interrupted` error once a
+/// `CancellationToken` is cancelled.
+///
+/// Created by the [`until_cancelled`] method.
+///
+/// [`until_cancelled`]: trait.AsyncReadExt.html#method.until_cancelled
+#[derive(Debug)]
+pub struct UntilCancelled<R> {
+    inner: R,
+    token: CancellationToken,
+}
+
+pub fn until_cancelled<R: AsyncRead>(inner: R, token: CancellationToken) -> UntilCancelled<R> {
+    UntilCancelled { inner, token }
+}
+
+fn cancelled() -> io::Error {
+    io::Error::new(io::ErrorKind::Interrupted, "read cancelled")
+}
+
+impl<R> UntilCancelled<R> {
+    /// Returns a reference to the token this reader is cancelled by.
+    pub fn token(&self) -> &CancellationToken {
+        &self.token
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this `UntilCancelled`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for UntilCancelled<R> {
+    unsafe fn initializer(&self) -> Initializer {
+        self.inner.initializer()
+    }
+
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        if self.token.is_cancelled() {
+            return Err(cancelled())
+        }
+        match self.inner.poll_read(cx, buf)? {
+            Async::Ready(n) => Ok(Async::Ready(n)),
+            Async::Pending => {
+                if self.token.poll_cancelled(cx) {
+                    return Err(cancelled())
+                }
+                Ok(Async::Pending)
+            }
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use std::io;
+use std::vec::Vec;
+
+use {Async, Poll, task};
+use futures_core::Stream;
+use futures_sink::Sink;
+
+use futures_io::{AsyncRead, AsyncWrite};
+
+// A `u64` takes at most ten bytes as a LEB128 varint.
+const MAX_VARINT_LEN: usize = 10;
+
+/// A stream of the messages read from a reader, each prefixed with its
+/// length as a LEB128 varint.
+///
+/// Created by the [`varint_delimited`] method.
+///
+/// [`varint_delimited`]: trait.AsyncReadExt.html#method.varint_delimited
+#[derive(Debug)]
+pub struct VarintDelimited<R> {
+    inner: R,
+    buf: Vec<u8>,
+    eof: bool,
+}
+
+pub fn varint_delimited<R: AsyncRead>(inner: R) -> VarintDelimited<R> {
+    VarintDelimited {
+        inner,
+        buf: Vec::new(),
+        eof: false,
+    }
+}
+
+/// Decodes the varint at the start of `buf`, returning its value and length,
+/// or `None` if `buf` ends before the varint does.
+fn decode_varint(buf: &[u8]) -> Result<Option<(u64, usize)>, io::Error> {
+    let mut value = 0u64;
+    for (i, &byte) in buf.iter().enumerate().take(MAX_VARINT_LEN) {
+        let bits = u64::from(byte & 0x7f);
+        if i == MAX_VARINT_LEN - 1 && bits > 1 {
+            break
+        }
+        value |= bits << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok(Some((value, i + 1)))
+        }
+    }
+    if buf.len() < MAX_VARINT_LEN {
+        return Ok(None)
+    }
+    Err(io::Error::new(io::ErrorKind::InvalidData, "message length overflows a u64"))
+}
+
+fn encode_varint(mut value: u64, buf: &mut Vec<u8>) {
+    while value >= 0x80 {
+        buf.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+impl<R> VarintDelimited<R> {
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this `VarintDelimited`, returning the underlying reader.
+    ///
+    /// Any buffered bytes of messages which haven't been yielded are lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn decode(&mut self) -> Result<Option<Vec<u8>>, io::Error> {
+        let (len, header) = match decode_varint(&self.buf)? {
+            Some(decoded) => decoded,
+            None => return Ok(None),
+        };
+        if ((self.buf.len() - header) as u64) < len {
+            return Ok(None)
+        }
+        let end = header + len as usize;
+        let message = self.buf[header..end].to_vec();
+        self.buf.drain(..end);
+        Ok(Some(message))
+    }
+}
+
+impl<R: AsyncRead> Stream for VarintDelimited<R> {
+    type Item = Vec<u8>;
+    type Error = io::Error;
+
+    fn poll_next(&mut self, cx: &mut task::Context) -> Poll<Option<Vec<u8>>, io::Error> {
+        loop {
+            if let Some(message) = self.decode()? {
+                return Ok(Async::Ready(Some(message)))
+            }
+
+            if self.eof {
+                if self.buf.is_empty() {
+                    return Ok(Async::Ready(None))
+                }
+                self.buf.clear();
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof,
+                                          "stream ended in the middle of a message"))
+            }
+
+            let mut chunk = [0; 1024];
+            let n = try_ready!(self.inner.poll_read(cx, &mut chunk));
+            if n == 0 {
+                self.eof = true;
+            }
+            self.buf.extend_from_slice(&chunk[..n]);
+        }
+    }
+}
+
+/// A sink which writes each message to a writer prefixed with its length as a
+/// LEB128 varint.
+///
+/// Created by the [`varint_delimited_sink`] function.
+///
+/// [`varint_delimited_sink`]: fn.varint_delimited_sink.html
+#[derive(Debug)]
+pub struct VarintDelimitedSink<W> {
+    inner: W,
+    header: Vec<u8>,
+    message: Vec<u8>,
+    // The number of bytes of the header followed by the message written.
+    pos: usize,
+}
+
+/// Creates a sink which writes each message to `inner` prefixed with its
+/// length as a LEB128 varint, the framing used for streams of protocol
+/// buffers. The messages can be read back with `varint_delimited`.
+///
+/// Each message is written fully before the next one is accepted.
+pub fn varint_delimited_sink<W: AsyncWrite>(inner: W) -> VarintDelimitedSink<W> {
+    VarintDelimitedSink {
+        inner,
+        header: Vec::new(),
+        message: Vec::new(),
+        pos: 0,
+    }
+}
+
+impl<W> VarintDelimitedSink<W> {
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes this `VarintDelimitedSink`, returning the underlying writer.
+    ///
+    /// Any partially written message is lost.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: AsyncWrite> VarintDelimitedSink<W> {
+    fn poll_message(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        let total = self.header.len() + self.message.len();
+        while self.pos < total {
+            let n = if self.pos < self.header.len() {
+                try_ready!(self.inner.poll_write(cx, &self.header[self.pos..]))
+            } else {
+                let pos = self.pos - self.header.len();
+                try_ready!(self.inner.poll_write(cx, &self.message[pos..]))
+            };
+            if n == 0 {
+                return Err(io::Error::new(io::ErrorKind::WriteZero,
+                                          "failed to write delimited message"))
+            }
+            self.pos += n;
+        }
+        self.header.clear();
+        self.message = Vec::new();
+        self.pos = 0;
+        Ok(Async::Ready(()))
+    }
+}
+
+impl<W: AsyncWrite> Sink for VarintDelimitedSink<W> {
+    type SinkItem = Vec<u8>;
+    type SinkError = io::Error;
+
+    fn poll_ready(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        self.poll_message(cx)
+    }
+
+    fn start_send(&mut self, message: Vec<u8>) -> Result<(), io::Error> {
+        debug_assert!(self.header.is_empty() && self.message.is_empty());
+        encode_varint(message.len() as u64, &mut self.header);
+        self.message = message;
+        Ok(())
+    }
+
+    fn poll_flush(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        try_ready!(self.poll_message(cx));
+        self.inner.poll_flush(cx)
+    }
+
+    fn poll_close(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        try_ready!(self.poll_message(cx));
+        self.inner.poll_close(cx)
+    }
+}
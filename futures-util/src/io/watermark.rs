@@ -0,0 +1,149 @@
+use std::cmp;
+use std::io;
+use std::vec::Vec;
+
+use {Async, Poll, task};
+
+use futures_io::{AsyncWritable, AsyncWrite};
+
+/// A buffer fill level transition reported by a [`WatermarkWriter`].
+///
+/// [`WatermarkWriter`]: struct.WatermarkWriter.html
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Watermark {
+    /// The buffer filled up to the high-water mark. Producers should stop
+    /// generating data until `Low` is reported.
+    High,
+    /// The buffer drained down to the low-water mark. Producers may resume.
+    Low,
+}
+
+/// A buffered writer which reports when its buffer crosses a high-water and
+/// a low-water mark.
+///
+/// Writes are copied into an internal buffer until it holds `high` bytes, at
+/// which point the callback is invoked with `Watermark::High`. From then on
+/// `poll_write` first drains the buffer into the underlying writer, and only
+/// accepts new data once the buffer is down to `low` bytes, at which point the
+/// callback is invoked with `Watermark::Low`.
+///
+/// Transitions are only ever reported from within `poll_write`, `poll_flush`
+/// and `poll_close`.
+#[derive(Debug)]
+pub struct WatermarkWriter<W, F> {
+    inner: W,
+    buf: Vec<u8>,
+    low: usize,
+    high: usize,
+    above: bool,
+    on_event: F,
+}
+
+impl<W, F> WatermarkWriter<W, F>
+    where W: AsyncWrite,
+          F: FnMut(Watermark),
+{
+    /// Creates a new `WatermarkWriter` buffering up to `high` bytes before
+    /// writing them to `inner`, invoking `on_event` on every transition.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `low` is not strictly smaller than `high`.
+    pub fn new(inner: W, low: usize, high: usize, on_event: F) -> WatermarkWriter<W, F> {
+        assert!(low < high, "low-water mark must be below the high-water mark");
+        WatermarkWriter {
+            inner,
+            buf: Vec::with_capacity(high),
+            low,
+            high,
+            above: false,
+            on_event,
+        }
+    }
+
+    /// Returns the number of bytes currently buffered.
+    pub fn buffered(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// Note that writing to the underlying writer directly will bypass the
+    /// buffered data.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes this `WatermarkWriter`, returning the underlying writer.
+    ///
+    /// Any buffered data which has not been flushed is lost.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    fn drain_to(&mut self, cx: &mut task::Context, target: usize) -> Poll<(), io::Error> {
+        while self.buf.len() > target {
+            let n = try_ready!(self.inner.poll_write(cx, &self.buf));
+            if n == 0 {
+                return Err(io::Error::new(io::ErrorKind::WriteZero,
+                                          "failed to write buffered data"))
+            }
+            self.buf.drain(..n);
+            if self.above && self.buf.len() <= self.low {
+                self.above = false;
+                (self.on_event)(Watermark::Low);
+            }
+        }
+        Ok(Async::Ready(()))
+    }
+}
+
+impl<W, F> AsyncWrite for WatermarkWriter<W, F>
+    where W: AsyncWrite,
+          F: FnMut(Watermark),
+{
+    fn poll_write(&mut self, cx: &mut task::Context, buf: &[u8])
+        -> Poll<usize, io::Error>
+    {
+        if self.above {
+            let low = self.low;
+            try_ready!(self.drain_to(cx, low));
+        }
+
+        let n = cmp::min(buf.len(), self.high - self.buf.len());
+        self.buf.extend_from_slice(&buf[..n]);
+        if self.buf.len() >= self.high {
+            self.above = true;
+            (self.on_event)(Watermark::High);
+        }
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_flush(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        try_ready!(self.drain_to(cx, 0));
+        self.inner.poll_flush(cx)
+    }
+
+    fn poll_close(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        try_ready!(self.drain_to(cx, 0));
+        self.inner.poll_close(cx)
+    }
+}
+
+impl<W, F> AsyncWritable for WatermarkWriter<W, F>
+    where W: AsyncWrite,
+          F: FnMut(Watermark),
+{
+    fn poll_writable(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        if self.above {
+            let low = self.low;
+            try_ready!(self.drain_to(cx, low));
+        }
+        Ok(Async::Ready(()))
+    }
+}
@@ -1,5 +1,11 @@
+use std::cmp;
+use std::io::{self, SeekFrom};
 use std::ops;
 
+use {Async, Poll, task};
+
+use futures_io::{AsyncRead, AsyncSeek, Initializer};
+
 /// A owned window around an underlying buffer.
 ///
 /// Normally slices work great for considering sub-portions of a buffer, but
@@ -17,10 +23,17 @@ use std::ops;
 /// combinator in this crate. Data can be sliced via `Window`, consumed by
 /// `write_all`, and then earned back once the write operation finishes through
 /// the `into_inner` method on this type.
+///
+/// A `Window` is also an `AsyncRead` and `AsyncSeek` over the bytes inside
+/// the window, which hands a bounded view of a buffer to a sub-parser
+/// without copying. Reads stop at the end of the window no matter how long
+/// the underlying buffer is. The read position is relative to the start of
+/// the window and is kept when the window is changed.
 #[derive(Debug)]
 pub struct Window<T> {
     inner: T,
     range: ops::Range<usize>,
+    pos: u64,
 }
 
 impl<T: AsRef<[u8]>> Window<T> {
@@ -33,6 +46,7 @@ impl<T: AsRef<[u8]>> Window<T> {
         Window {
             range: 0..t.as_ref().len(),
             inner: t,
+            pos: 0,
         }
     }
 
@@ -65,6 +79,11 @@ impl<T: AsRef<[u8]>> Window<T> {
         self.range.end
     }
 
+    /// Returns the read position, relative to the start of this window.
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
     /// Changes the starting index of this window to the index specified.
     ///
     /// Returns the windows back to chain multiple calls to this method.
@@ -115,3 +134,52 @@ impl<T: AsMut<[u8]>> AsMut<[u8]> for Window<T> {
         &mut self.inner.as_mut()[self.range.start..self.range.end]
     }
 }
+
+impl<T: AsRef<[u8]>> AsyncRead for Window<T> {
+    unsafe fn initializer(&self) -> Initializer {
+        Initializer::nop()
+    }
+
+    fn poll_read(&mut self, _: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        let n = {
+            let data = self.as_ref();
+            let start = cmp::min(self.pos, data.len() as u64) as usize;
+            let data = &data[start..];
+            let n = cmp::min(buf.len(), data.len());
+            buf[..n].copy_from_slice(&data[..n]);
+            n
+        };
+        self.pos += n as u64;
+        Ok(Async::Ready(n))
+    }
+}
+
+impl<T: AsRef<[u8]>> AsyncSeek for Window<T> {
+    fn poll_seek(&mut self, _: &mut task::Context, pos: SeekFrom)
+        -> Poll<u64, io::Error>
+    {
+        let (base, offset) = match pos {
+            SeekFrom::Start(n) => {
+                self.pos = n;
+                return Ok(Async::Ready(n))
+            }
+            SeekFrom::End(n) => ((self.range.end - self.range.start) as u64, n),
+            SeekFrom::Current(n) => (self.pos, n),
+        };
+        let new = if offset >= 0 {
+            base.checked_add(offset as u64)
+        } else {
+            base.checked_sub(offset.wrapping_neg() as u64)
+        };
+        match new {
+            Some(n) => {
+                self.pos = n;
+                Ok(Async::Ready(n))
+            }
+            None => Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                       "invalid seek to a negative or overflowing position")),
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::io;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use {Async, Poll, task};
+use futures_core::task::Waker;
+
+use futures_io::{AsyncRead, Initializer, IoVec};
+
+/// A counting semaphore for bounding how many tasks perform an operation at
+/// the same time.
+///
+/// Handles created with `clone` share the same permits. Each handle keeps
+/// only the waker of the last task to poll it while no permit was
+/// available, so tasks waiting at the same time should each use a handle of
+/// their own.
+#[derive(Debug)]
+pub struct Semaphore {
+    inner: Arc<Mutex<State>>,
+    id: usize,
+}
+
+#[derive(Debug)]
+struct State {
+    permits: usize,
+    // One slot per waiting handle, keyed by its id, so that a handle polled
+    // again while it waits replaces its waker.
+    waiters: HashMap<usize, Waker>,
+    next_id: usize,
+}
+
+/// A permit acquired from a `Semaphore`, which is released when dropped.
+#[derive(Debug)]
+pub struct SemaphorePermit {
+    inner: Arc<Mutex<State>>,
+}
+
+fn lock<'a>(state: &'a Mutex<State>) -> MutexGuard<'a, State> {
+    state.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl Semaphore {
+    /// Creates a new semaphore with `permits` permits available.
+    pub fn new(permits: usize) -> Semaphore {
+        Semaphore {
+            inner: Arc::new(Mutex::new(State {
+                permits,
+                waiters: HashMap::new(),
+                next_id: 1,
+            })),
+            id: 0,
+        }
+    }
+
+    /// Returns the number of permits currently available.
+    pub fn available(&self) -> usize {
+        lock(&self.inner).permits
+    }
+
+    /// Attempt to acquire a permit, returning `Pending` if none is
+    /// available.
+    ///
+    /// If no permit is available the current task is scheduled to be woken
+    /// up once one is released.
+    pub fn poll_acquire(&self, cx: &mut task::Context) -> Async<SemaphorePermit> {
+        let mut state = lock(&self.inner);
+        if state.permits == 0 {
+            state.waiters.insert(self.id, cx.waker().clone());
+            return Async::Pending
+        }
+        state.waiters.remove(&self.id);
+        state.permits -= 1;
+        Async::Ready(SemaphorePermit { inner: self.inner.clone() })
+    }
+}
+
+impl Clone for Semaphore {
+    fn clone(&self) -> Semaphore {
+        let id = {
+            let mut state = lock(&self.inner);
+            let id = state.next_id;
+            state.next_id = id.wrapping_add(1);
+            id
+        };
+        Semaphore { inner: self.inner.clone(), id }
+    }
+}
+
+impl Drop for Semaphore {
+    fn drop(&mut self) {
+        lock(&self.inner).waiters.remove(&self.id);
+    }
+}
+
+impl Drop for SemaphorePermit {
+    fn drop(&mut self) {
+        let mut state = lock(&self.inner);
+        state.permits += 1;
+        // Every waiter retries, so that a waiter which has since gone away
+        // can't keep the permit from the others.
+        for (_, waker) in state.waiters.drain() {
+            waker.wake();
+        }
+    }
+}
+
+/// An `AsyncRead` which holds a permit from a `Semaphore` while reading
+/// from the underlying reader.
+///
+/// Created by the [`with_permit`] method.
+///
+/// [`with_permit`]: trait.AsyncReadExt.html#method.with_permit
+#[derive(Debug)]
+pub struct WithPermit<R> {
+    inner: R,
+    semaphore: Semaphore,
+}
+
+pub fn with_permit<R: AsyncRead>(inner: R, semaphore: Semaphore) -> WithPermit<R> {
+    WithPermit { inner, semaphore }
+}
+
+impl<R> WithPermit<R> {
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this `WithPermit`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for WithPermit<R> {
+    unsafe fn initializer(&self) -> Initializer {
+        self.inner.initializer()
+    }
+
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        match self.semaphore.poll_acquire(cx) {
+            Async::Ready(_permit) => self.inner.poll_read(cx, buf),
+            Async::Pending => Ok(Async::Pending),
+        }
+    }
+
+    fn poll_vectored_read(&mut self, cx: &mut task::Context, vec: &mut [&mut IoVec])
+        -> Poll<usize, io::Error>
+    {
+        match self.semaphore.poll_acquire(cx) {
+            Async::Ready(_permit) => self.inner.poll_vectored_read(cx, vec),
+            Async::Pending => Ok(Async::Pending),
+        }
+    }
+}
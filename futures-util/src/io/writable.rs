@@ -0,0 +1,30 @@
+use std::io;
+
+use {Future, Poll, task};
+
+use futures_io::AsyncWritable;
+
+/// A future which resolves once an I/O object is ready to accept more data.
+///
+/// This is created by the [`writable`] method.
+///
+/// [`writable`]: trait.AsyncWriteExt.html#method.writable
+#[derive(Debug)]
+pub struct Writable<'a, W: ?Sized + 'a> {
+    writer: &'a mut W,
+}
+
+pub fn writable<'a, W>(writer: &'a mut W) -> Writable<'a, W>
+    where W: ?Sized + AsyncWritable,
+{
+    Writable { writer }
+}
+
+impl<'a, W: ?Sized + AsyncWritable> Future for Writable<'a, W> {
+    type Item = ();
+    type Error = io::Error;
+
+    fn poll(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        self.writer.poll_writable(cx)
+    }
+}
@@ -1,72 +1,43 @@
 use std::io;
-use std::mem;
 
-use {Poll, Future, task};
+use {Async, Poll, Future, task};
 
 use futures_io::AsyncWrite;
 
 /// A future used to write the entire contents of some data to a stream.
 ///
-/// This is created by the [`write_all`] top-level method.
+/// This is created by the [`write_all`] method.
 ///
-/// [`write_all`]: fn.write_all.html
+/// [`write_all`]: trait.AsyncWriteExt.html#method.write_all
 #[derive(Debug)]
-pub struct WriteAll<A, T> {
-    state: State<A, T>,
+pub struct WriteAll<'a, A: ?Sized + 'a> {
+    writer: &'a mut A,
+    buf: &'a [u8],
 }
 
-#[derive(Debug)]
-enum State<A, T> {
-    Writing {
-        a: A,
-        buf: T,
-        pos: usize,
-    },
-    Empty,
-}
-
-pub fn write_all<A, T>(a: A, buf: T) -> WriteAll<A, T>
-    where A: AsyncWrite,
-          T: AsRef<[u8]>,
+pub fn write_all<'a, A>(writer: &'a mut A, buf: &'a [u8]) -> WriteAll<'a, A>
+    where A: ?Sized + AsyncWrite,
 {
-    WriteAll {
-        state: State::Writing {
-            a: a,
-            buf: buf,
-            pos: 0,
-        },
-    }
+    WriteAll { writer, buf }
 }
 
 fn zero_write() -> io::Error {
     io::Error::new(io::ErrorKind::WriteZero, "zero-length write")
 }
 
-impl<A, T> Future for WriteAll<A, T>
-    where A: AsyncWrite,
-          T: AsRef<[u8]>,
-{
-    type Item = (A, T);
+impl<'a, A: ?Sized + AsyncWrite> Future for WriteAll<'a, A> {
+    type Item = ();
     type Error = io::Error;
 
-    fn poll(&mut self, cx: &mut task::Context) -> Poll<(A, T), io::Error> {
-        match self.state {
-            State::Writing { ref mut a, ref buf, ref mut pos } => {
-                let buf = buf.as_ref();
-                while *pos < buf.len() {
-                    let n = try_ready!(a.poll_write(cx, &buf[*pos..]));
-                    *pos += n;
-                    if n == 0 {
-                        return Err(zero_write())
-                    }
-                }
+    fn poll(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        while !self.buf.is_empty() {
+            let n = try_ready!(self.writer.poll_write(cx, self.buf));
+            if n == 0 {
+                return Err(zero_write())
             }
-            State::Empty => panic!("poll a WriteAll after it's done"),
-        }
-
-        match mem::replace(&mut self.state, State::Empty) {
-            State::Writing { a, buf, .. } => Ok((a, buf).into()),
-            State::Empty => panic!(),
+            let buf = self.buf;
+            self.buf = &buf[n..];
         }
+        Ok(Async::Ready(()))
     }
 }
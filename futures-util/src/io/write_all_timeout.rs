@@ -0,0 +1,71 @@
+use std::io;
+use std::time::Duration;
+
+use {Async, Future, Poll, task};
+use futures_core::Never;
+
+use futures_io::AsyncWrite;
+
+/// A future used to write the entire contents of a buffer to a stream within
+/// a deadline.
+///
+/// This is created by the [`write_all_timeout`] method.
+///
+/// [`write_all_timeout`]: trait.AsyncWriteExt.html#method.write_all_timeout
+#[derive(Debug)]
+pub struct WriteAllTimeout<'a, W: ?Sized + 'a, T> {
+    writer: &'a mut W,
+    buf: &'a [u8],
+    delay: T,
+}
+
+pub fn write_all_timeout<'a, W, F, T>(writer: &'a mut W, buf: &'a [u8], dur: Duration, timer: F)
+    -> WriteAllTimeout<'a, W, T>
+    where W: ?Sized + AsyncWrite,
+          F: FnOnce(Duration) -> T,
+          T: Future<Item = (), Error = Never>,
+{
+    WriteAllTimeout {
+        writer,
+        buf,
+        delay: timer(dur),
+    }
+}
+
+impl<'a, W: ?Sized + AsyncWrite, T> WriteAllTimeout<'a, W, T> {
+    /// Returns the part of the buffer which has not been written yet.
+    pub fn remaining(&self) -> &[u8] {
+        self.buf
+    }
+}
+
+impl<'a, W, T> Future for WriteAllTimeout<'a, W, T>
+    where W: ?Sized + AsyncWrite,
+          T: Future<Item = (), Error = Never>,
+{
+    type Item = ();
+    type Error = io::Error;
+
+    fn poll(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        while !self.buf.is_empty() {
+            match self.writer.poll_write(cx, self.buf)? {
+                Async::Ready(0) => {
+                    return Err(io::Error::new(io::ErrorKind::WriteZero,
+                                              "zero-length write"))
+                }
+                Async::Ready(n) => self.buf = &self.buf[n..],
+                Async::Pending => {
+                    match self.delay.poll(cx) {
+                        Ok(Async::Ready(())) => {
+                            return Err(io::Error::new(io::ErrorKind::TimedOut,
+                                                      "write timed out"))
+                        }
+                        Ok(Async::Pending) => return Ok(Async::Pending),
+                        Err(never) => match never {},
+                    }
+                }
+            }
+        }
+        Ok(Async::Ready(()))
+    }
+}
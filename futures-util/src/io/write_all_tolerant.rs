@@ -0,0 +1,90 @@
+use std::io;
+use std::time::Duration;
+
+use {Async, Future, Poll, task};
+use futures_core::Never;
+
+use futures_io::AsyncWrite;
+
+/// A future used to write the entire contents of a buffer to a stream,
+/// retrying writes which spuriously accept zero bytes.
+///
+/// This is created by the [`write_all_tolerant`] method.
+///
+/// [`write_all_tolerant`]: trait.AsyncWriteExt.html#method.write_all_tolerant
+#[derive(Debug)]
+pub struct WriteAllTolerant<'a, W: ?Sized + 'a, F, T> {
+    writer: &'a mut W,
+    buf: &'a [u8],
+    max_retries: usize,
+    backoff: Duration,
+    timer: F,
+    delay: Option<T>,
+    retries: usize,
+}
+
+pub fn write_all_tolerant<'a, W, F, T>(writer: &'a mut W,
+                                       buf: &'a [u8],
+                                       max_retries: usize,
+                                       backoff: Duration,
+                                       timer: F)
+    -> WriteAllTolerant<'a, W, F, T>
+    where W: ?Sized + AsyncWrite,
+          F: FnMut(Duration) -> T,
+          T: Future<Item = (), Error = Never>,
+{
+    WriteAllTolerant {
+        writer,
+        buf,
+        max_retries,
+        backoff,
+        timer,
+        delay: None,
+        retries: 0,
+    }
+}
+
+impl<'a, W: ?Sized + AsyncWrite, F, T> WriteAllTolerant<'a, W, F, T> {
+    /// Returns the part of the buffer which has not been written yet.
+    pub fn remaining(&self) -> &[u8] {
+        self.buf
+    }
+}
+
+impl<'a, W, F, T> Future for WriteAllTolerant<'a, W, F, T>
+    where W: ?Sized + AsyncWrite,
+          F: FnMut(Duration) -> T,
+          T: Future<Item = (), Error = Never>,
+{
+    type Item = ();
+    type Error = io::Error;
+
+    fn poll(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        while !self.buf.is_empty() {
+            if let Some(ref mut delay) = self.delay {
+                match delay.poll(cx) {
+                    Ok(Async::Ready(())) => {}
+                    Ok(Async::Pending) => return Ok(Async::Pending),
+                    Err(never) => match never {},
+                }
+            }
+            self.delay = None;
+
+            match try_ready!(self.writer.poll_write(cx, self.buf)) {
+                0 if self.retries < self.max_retries => {
+                    self.retries += 1;
+                    self.delay = Some((self.timer)(self.backoff));
+                }
+                0 => {
+                    return Err(io::Error::new(io::ErrorKind::WriteZero,
+                                              "zero-length write"))
+                }
+                n => {
+                    self.retries = 0;
+                    self.buf = &self.buf[n..];
+                }
+            }
+        }
+        Ok(Async::Ready(()))
+    }
+}
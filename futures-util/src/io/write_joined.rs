@@ -0,0 +1,94 @@
+use std::io;
+use std::vec::Vec;
+
+use {Async, Future, Poll, task};
+
+use futures_io::{AsyncWrite, IoVec};
+
+/// A future used to write a list of buffers joined by a separator.
+///
+/// This is created by the [`write_joined`] method.
+///
+/// [`write_joined`]: trait.AsyncWriteExt.html#method.write_joined
+#[derive(Debug)]
+pub struct WriteJoined<'a, W: ?Sized + 'a> {
+    writer: &'a mut W,
+    parts: &'a [&'a [u8]],
+    sep: &'a [u8],
+    // Index into the sequence `parts[0], sep, parts[1], sep, ...`.
+    piece: usize,
+    pos: usize,
+}
+
+pub fn write_joined<'a, W>(writer: &'a mut W, parts: &'a [&'a [u8]], sep: &'a [u8])
+    -> WriteJoined<'a, W>
+    where W: ?Sized + AsyncWrite,
+{
+    WriteJoined {
+        writer,
+        parts,
+        sep,
+        piece: 0,
+        pos: 0,
+    }
+}
+
+fn zero_write() -> io::Error {
+    io::Error::new(io::ErrorKind::WriteZero, "zero-length write")
+}
+
+impl<'a, W: ?Sized + AsyncWrite> WriteJoined<'a, W> {
+    fn pieces(&self) -> usize {
+        if self.parts.is_empty() { 0 } else { 2 * self.parts.len() - 1 }
+    }
+
+    fn piece(&self, i: usize) -> &'a [u8] {
+        if i % 2 == 0 { self.parts[i / 2] } else { self.sep }
+    }
+
+    fn advance(&mut self, mut n: usize) {
+        while n > 0 {
+            let left = self.piece(self.piece).len() - self.pos;
+            if n < left {
+                self.pos += n;
+                return
+            }
+            n -= left;
+            self.piece += 1;
+            self.pos = 0;
+        }
+    }
+}
+
+impl<'a, W: ?Sized + AsyncWrite> Future for WriteJoined<'a, W> {
+    type Item = ();
+    type Error = io::Error;
+
+    fn poll(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        loop {
+            // Skip over exhausted (or empty) pieces.
+            while self.piece < self.pieces() && self.pos == self.piece(self.piece).len() {
+                self.piece += 1;
+                self.pos = 0;
+            }
+            if self.piece == self.pieces() {
+                return Ok(Async::Ready(()))
+            }
+
+            let n = {
+                let mut bufs: Vec<&IoVec> = Vec::new();
+                for i in self.piece..self.pieces() {
+                    let start = if i == self.piece { self.pos } else { 0 };
+                    if let Some(iovec) = IoVec::from_bytes(&self.piece(i)[start..]) {
+                        bufs.push(iovec);
+                    }
+                }
+                try_ready!(self.writer.poll_vectored_write(cx, &bufs))
+            };
+            if n == 0 {
+                return Err(zero_write())
+            }
+            self.advance(n);
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use std::io;
+use std::vec::Vec;
+
+use {Async, Poll, task};
+
+use futures_io::{AsyncRead, AsyncWrite, Initializer};
+
+/// A reader which decrypts the underlying stream by XOR-ing each byte with a
+/// keystream.
+///
+/// The keystream is a closure mapping the absolute position of a byte in the
+/// stream to the key byte it is XOR-ed with, so the transform doesn't depend
+/// on how the stream is split into reads. A repeating key can be used with
+/// `move |pos| key[(pos % key.len() as u64) as usize]`.
+///
+/// This is only an obfuscation, not encryption in any cryptographic sense.
+#[derive(Debug)]
+pub struct XorStreamReader<R, K> {
+    inner: R,
+    keystream: K,
+    pos: u64,
+}
+
+impl<R, K> XorStreamReader<R, K>
+    where R: AsyncRead,
+          K: FnMut(u64) -> u8,
+{
+    /// Creates a new `XorStreamReader` starting at stream position 0.
+    pub fn new(inner: R, keystream: K) -> XorStreamReader<R, K> {
+        XorStreamReader { inner, keystream, pos: 0 }
+    }
+
+    /// Returns the position in the stream of the next byte to be read.
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// Note that reading from the underlying reader directly misaligns the
+    /// keystream.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this `XorStreamReader`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R, K> AsyncRead for XorStreamReader<R, K>
+    where R: AsyncRead,
+          K: FnMut(u64) -> u8,
+{
+    unsafe fn initializer(&self) -> Initializer {
+        self.inner.initializer()
+    }
+
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        let n = try_ready!(self.inner.poll_read(cx, buf));
+        for byte in &mut buf[..n] {
+            *byte ^= (self.keystream)(self.pos);
+            self.pos += 1;
+        }
+        Ok(Async::Ready(n))
+    }
+}
+
+/// A writer which encrypts written bytes by XOR-ing each of them with a
+/// keystream, the counterpart of `XorStreamReader`.
+///
+/// The keystream is aligned to the absolute position in the written stream.
+/// Only bytes accepted by the underlying writer advance the position, so
+/// partial writes don't misalign it.
+#[derive(Debug)]
+pub struct XorStreamWriter<W, K> {
+    inner: W,
+    keystream: K,
+    pos: u64,
+    buf: Vec<u8>,
+}
+
+impl<W, K> XorStreamWriter<W, K>
+    where W: AsyncWrite,
+          K: FnMut(u64) -> u8,
+{
+    /// Creates a new `XorStreamWriter` starting at stream position 0.
+    pub fn new(inner: W, keystream: K) -> XorStreamWriter<W, K> {
+        XorStreamWriter { inner, keystream, pos: 0, buf: Vec::new() }
+    }
+
+    /// Returns the position in the stream of the next byte to be written.
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    ///
+    /// Note that writing to the underlying writer directly misaligns the
+    /// keystream.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes this `XorStreamWriter`, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W, K> AsyncWrite for XorStreamWriter<W, K>
+    where W: AsyncWrite,
+          K: FnMut(u64) -> u8,
+{
+    fn poll_write(&mut self, cx: &mut task::Context, buf: &[u8])
+        -> Poll<usize, io::Error>
+    {
+        self.buf.clear();
+        let mut pos = self.pos;
+        for &byte in buf {
+            self.buf.push(byte ^ (self.keystream)(pos));
+            pos += 1;
+        }
+        let n = try_ready!(self.inner.poll_write(cx, &self.buf));
+        self.pos += n as u64;
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_flush(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        self.inner.poll_flush(cx)
+    }
+
+    fn poll_close(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        self.inner.poll_close(cx)
+    }
+}
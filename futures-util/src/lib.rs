@@ -14,6 +14,14 @@ extern crate futures_executor;
 extern crate futures_io;
 extern crate futures_sink;
 extern crate either;
+#[cfg(feature = "bytes")]
+extern crate bytes;
+#[cfg(feature = "deflate")]
+extern crate flate2;
+#[cfg(feature = "json")]
+extern crate serde;
+#[cfg(feature = "json")]
+extern crate serde_json;
 
 #[cfg(feature = "std")]
 use futures_core::{Async, Future, Poll, task};
@@ -55,13 +63,16 @@ pub mod lock;
 #[cfg(all(feature = "std", not(any(test, feature = "bench"))))]
 mod lock;
 
+#[cfg(feature = "std")]
+pub mod codec;
+
 pub mod future;
 pub use future::FutureExt;
 
 #[cfg(feature = "std")]
 pub mod io;
 #[cfg(feature = "std")]
-pub use io::{AsyncReadExt, AsyncWriteExt};
+pub use io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt};
 
 pub mod stream;
 pub use stream::StreamExt;
@@ -74,5 +85,5 @@ pub mod prelude {
     //! existing asynchronous types.
     pub use {FutureExt, StreamExt, SinkExt};
     #[cfg(feature = "std")]
-    pub use {AsyncReadExt, AsyncWriteExt};
+    pub use {AsyncBufReadExt, AsyncReadExt, AsyncWriteExt};
 }
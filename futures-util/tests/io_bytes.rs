@@ -0,0 +1,98 @@
+#![cfg(feature = "bytes")]
+
+extern crate bytes;
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use bytes::{Buf, BufMut, BytesMut, IntoBuf};
+use futures::io::IoVec;
+use futures::prelude::*;
+
+#[path = "../../futures/tests/support/mod.rs"]
+mod support;
+use support::*;
+
+/// A writer recording its writes, accepting at most `max` bytes of each.
+struct Recorder {
+    data: Vec<u8>,
+    max: usize,
+    vectored: usize,
+}
+
+impl AsyncWrite for Recorder {
+    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        let n = buf.len().min(self.max);
+        self.data.extend_from_slice(&buf[..n]);
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_vectored_write(&mut self, cx: &mut task::Context, vec: &[&IoVec])
+        -> Poll<usize, io::Error>
+    {
+        self.vectored += 1;
+        let mut total = 0;
+        for buf in vec {
+            if let Async::Ready(n) = self.poll_write(cx, buf)? {
+                total += n;
+                self.max -= n;
+            }
+        }
+        Ok(Async::Ready(total))
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+fn reads_into_spare_capacity() {
+    let mut reader = Cursor::new(b"hello world".to_vec());
+    let mut buf = BytesMut::with_capacity(8);
+    let cap = buf.remaining_mut();
+    noop_waker_cx(|cx| {
+        let n = reader.poll_read_buf(cx, &mut buf).unwrap();
+        assert_eq!(n, Async::Ready(cap.min(11)));
+        assert_eq!(&buf[..], &b"hello world"[..cap.min(11)]);
+    });
+}
+
+#[test]
+fn reads_nothing_into_full_buffer() {
+    let mut reader = Cursor::new(b"hello".to_vec());
+    let mut buf = Cursor::new([0u8; 0]);
+    noop_waker_cx(|cx| {
+        assert_eq!(reader.poll_read_buf(cx, &mut buf).unwrap(), Async::Ready(0));
+    });
+    assert_eq!(reader.position(), 0);
+}
+
+#[test]
+fn writes_contiguous_buffer() {
+    let mut writer = Recorder { data: Vec::new(), max: 3, vectored: 0 };
+    let mut buf = Cursor::new(b"hello");
+    noop_waker_cx(|cx| {
+        assert_eq!(writer.poll_write_buf(cx, &mut buf).unwrap(), Async::Ready(3));
+        assert_eq!(buf.remaining(), 2);
+    });
+    assert_eq!(writer.data, b"hel");
+    assert_eq!(writer.vectored, 0);
+}
+
+#[test]
+fn writes_segments_vectored() {
+    let mut writer = Recorder { data: Vec::new(), max: 7, vectored: 0 };
+    let mut buf = Buf::chain((&b"hello "[..]).into_buf(), &b"world"[..]);
+    noop_waker_cx(|cx| {
+        assert_eq!(writer.poll_write_buf(cx, &mut buf).unwrap(), Async::Ready(7));
+        assert_eq!(writer.poll_write_buf(cx, &mut buf).unwrap(), Async::Ready(0));
+    });
+    assert_eq!(buf.remaining(), 4);
+    assert_eq!(writer.data, b"hello w");
+    assert_eq!(writer.vectored, 1);
+}
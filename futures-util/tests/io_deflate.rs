@@ -0,0 +1,130 @@
+#![cfg(feature = "deflate")]
+
+extern crate flate2;
+extern crate futures;
+extern crate futures_util;
+
+use std::io::{self, Read as StdRead, Write as StdWrite};
+
+use flate2::Compression;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use futures::executor::block_on;
+use futures::prelude::*;
+use futures_util::io::{deflate_write, inflate_read};
+
+/// A writer which accepts at most 7 bytes per write.
+#[derive(Default)]
+struct Short(Vec<u8>);
+
+impl AsyncWrite for Short {
+    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        let n = buf.len().min(7);
+        self.0.extend_from_slice(&buf[..n]);
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+fn payload() -> Vec<u8> {
+    (0..20_000u32).flat_map(|i| format!("line {}\n", i % 97).into_bytes()).collect()
+}
+
+fn inflate(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    DeflateDecoder::new(data).read_to_end(&mut out).unwrap();
+    out
+}
+
+#[test]
+fn compresses_payload() {
+    let payload = payload();
+    let mut w = deflate_write(Short::default(), Compression::default());
+    block_on(w.write_all(&payload[..])).unwrap();
+    block_on(w.close()).unwrap();
+    assert_eq!(w.total_in(), payload.len() as u64);
+
+    let compressed = w.into_inner().0;
+    assert!(compressed.len() < payload.len() / 4);
+    assert_eq!(inflate(&compressed), payload);
+}
+
+#[test]
+fn flush_makes_written_data_decodable() {
+    let mut w = deflate_write(Short::default(), Compression::fast());
+    block_on(w.write_all(b"hello, ")).unwrap();
+    block_on(w.flush()).unwrap();
+
+    let mut out = Vec::new();
+    let mut decoder = DeflateDecoder::new(&w.get_ref().0[..]);
+    let _ = decoder.read_to_end(&mut out);
+    assert_eq!(out, b"hello, ");
+}
+
+/// A reader which yields its data in chunks of increasing size.
+struct Chunked {
+    data: Vec<u8>,
+    pos: usize,
+    next: usize,
+}
+
+impl AsyncRead for Chunked {
+    fn poll_read(&mut self, _: &mut task::Context, buf: &mut [u8]) -> Poll<usize, io::Error> {
+        self.next = self.next % 5 + 1;
+        let n = buf.len().min(self.next).min(self.data.len() - self.pos);
+        buf[..n].copy_from_slice(&self.data[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(Async::Ready(n))
+    }
+}
+
+fn deflate(data: &[u8]) -> Vec<u8> {
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+#[test]
+fn decompresses_chunked_stream() {
+    let payload = payload();
+    let mut reader = inflate_read(Chunked { data: deflate(&payload), pos: 0, next: 0 });
+    let mut data = Vec::new();
+    block_on(reader.read_to_end(&mut data)).unwrap();
+    assert_eq!(data, payload);
+    assert_eq!(reader.total_out(), payload.len() as u64);
+}
+
+#[test]
+fn round_trips_through_deflate_write() {
+    let payload = payload();
+    let mut w = deflate_write(Short::default(), Compression::best());
+    block_on(w.write_all(&payload[..])).unwrap();
+    block_on(w.close()).unwrap();
+    let compressed = w.into_inner().0;
+
+    let mut reader = inflate_read(io::Cursor::new(compressed));
+    let mut data = Vec::new();
+    block_on(reader.read_to_end(&mut data)).unwrap();
+    assert_eq!(data, payload);
+}
+
+#[test]
+fn corrupt_and_truncated_streams_are_errors() {
+    let mut reader = inflate_read(io::Cursor::new(vec![0xff; 64]));
+    let err = block_on(reader.read_to_end(&mut Vec::new())).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    let mut compressed = deflate(&payload());
+    let len = compressed.len() / 2;
+    compressed.truncate(len);
+    let mut reader = inflate_read(io::Cursor::new(compressed));
+    let err = block_on(reader.read_to_end(&mut Vec::new())).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
@@ -102,6 +102,20 @@ pub mod channel {
     pub use futures_channel::{oneshot, mpsc};
 }
 
+#[cfg(feature = "std")]
+pub mod codec {
+    //! Framing of byte streams into streams and sinks of messages.
+    //!
+    //! Implement [`Decoder`](::codec::Decoder) and
+    //! [`Encoder`](::codec::Encoder) to describe a protocol's framing, and
+    //! wrap an IO object with [`Framed`](::codec::Framed) to read and write
+    //! its frames as a `Stream` and a `Sink`.
+
+    pub use futures_util::codec::{
+        Decoder, Encoder, Framed, LengthDelimitedBuilder, LengthDelimitedCodec,
+    };
+}
+
 #[cfg(feature = "std")]
 pub mod executor {
     //! Task execution.
@@ -171,7 +185,8 @@ pub mod executor {
         LocalExecutor, LocalPool,
         Spawn, SpawnWithHandle,
         ThreadPool, ThreadPoolBuilder, JoinHandle,
-        block_on, block_on_stream, enter, spawn, spawn_with_handle
+        block_on, block_on_stream, enter, spawn, spawn_with_handle,
+        spawn_read_to_end
     };
     pub use futures_core::executor::{SpawnError, Executor};
 }
@@ -220,18 +235,48 @@ pub mod io {
     //! reading (or writing), the thread is not blocked, and instead the current
     //! task is queued to be woken when I/O is ready.
     //!
-    //! In addition, the [`AsyncReadExt`](::io::AsyncReadExt) and
+    //! In addition, the [`AsyncReadExt`](::io::AsyncReadExt),
+    //! [`AsyncBufReadExt`](::io::AsyncBufReadExt) and
     //! [`AsyncWriteExt`](::io::AsyncWriteExt) extension traits offer a variety
     //! of useful combinators for operating with asynchronous I/O objects,
     //! including ways to work with them using futures, streams and sinks.
 
     pub use futures_io::{
-        Error, Initializer, IoVec, ErrorKind, AsyncRead, AsyncWrite, Result
+        Error, Initializer, IoVec, ErrorKind, AsyncBufRead, AsyncRead,
+        AsyncReadable, AsyncSeek, AsyncWritable, AsyncWrite, BufReader,
+        BufWriter, IntoInner, Result
+    };
+    pub use futures_util::io::{
+        AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, AckOnFlush, AllowStdIo,
+        AssertNondecreasingReads, AsyncCork, AtEof, AtomicFrameWriter,
+        BoundedConcat, BoundedUnflushed, BudgetWrite, CancellationToken, Chain,
+        Close, CloseFrames, CollectPairs, ContentLength, Copy, CopyInto,
+        CorkWriter, CountRead, CountWrite, CrcTrailerWriter, DatagramWriter,
+        DedupBytes, DemuxReader, DetectFormat, Detected, DuplicateKeys,
+        ExpectPrefix, FencedWriter, FinalizeOnce, Flush, FlushFrames, Fuse,
+        FuseWriter, GatherUntilClose, GroupFlushWriter, Hooked, Inspect,
+        InspectEofPosition, InspectWrite, IsolateMessages, KeepaliveWriter,
+        LatencyWriter, Lines, LogReader, LogWriter, MarkerBody, MinThroughput,
+        NewlineConvertWriter, PipeReader, PipeWriter, PositionReader,
+        PositionWriter, Quota, QuotaReader, Read, ReadAll, ReadCrlfLine,
+        ReadExact, ReadHalf, ReadNRecords, ReadOutcome, ReadStruct, ReadToEnd,
+        ReadToString, ReadUntil, Readable, ReorderReader, Replay, Replayable,
+        RequireMinBuffer, ResultFrames, RetryPolicy, RetryRead, ReuniteError,
+        RotatingWriter, Semaphore, SemaphorePermit, SendFrame, SendMessage,
+        SendShard, ShardedWriter, ShuffleReads, SniffPrefix, SpliceFrom,
+        SplitAtMarker, StructReader, Take, Tee, TeeTo, Throttle, Timeout,
+        UntilCancelled, VarintDelimited, VarintDelimitedSink, Watermark,
+        WatermarkWriter, Window, WithPermit, Writable, WriteAll,
+        WriteAllTimeout, WriteAllTolerant, WriteAndFlush, WriteHalf,
+        WriteJoined, XorStreamReader, XorStreamWriter, bounded_concat, copy,
+        from_bytes, pipe, reunite, sharded_write, varint_delimited_sink,
     };
+    #[cfg(feature = "deflate")]
     pub use futures_util::io::{
-        AsyncReadExt, AsyncWriteExt, AllowStdIo, Close, CopyInto, Flush,
-        Read, ReadExact, ReadHalf, ReadToEnd, Window, WriteAll, WriteHalf,
+        DeflateWriter, InflateReader, deflate_write, inflate_read,
     };
+    #[cfg(feature = "json")]
+    pub use futures_util::io::{JsonLines, JsonLinesSink, json_lines_sink};
 }
 
 #[cfg(feature = "std")]
@@ -281,7 +326,9 @@ pub mod prelude {
 
     #[cfg(feature = "std")]
     pub use futures_io::{
+        AsyncBufRead,
         AsyncRead,
+        AsyncSeek,
         AsyncWrite,
     };
 
@@ -293,6 +340,7 @@ pub mod prelude {
 
     #[cfg(feature = "std")]
     pub use futures_util::{
+        AsyncBufReadExt,
         AsyncReadExt,
         AsyncWriteExt,
     };
@@ -0,0 +1,113 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::codec::{Decoder, Encoder, Framed};
+use futures::executor::block_on;
+use futures::io::pipe;
+use futures::prelude::*;
+
+/// Frames prefixed with their length as a single byte.
+struct LengthPrefixed;
+
+impl Decoder for LengthPrefixed {
+    type Item = Vec<u8>;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut Vec<u8>) -> Result<Option<Vec<u8>>, io::Error> {
+        match src.first() {
+            Some(&len) if src.len() > len as usize => {
+                let frame = src[1..len as usize + 1].to_vec();
+                src.drain(..len as usize + 1);
+                Ok(Some(frame))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+impl Encoder for LengthPrefixed {
+    type SinkItem = Vec<u8>;
+    type SinkError = io::Error;
+
+    fn encode(&mut self, item: Vec<u8>, dst: &mut Vec<u8>) -> Result<(), io::Error> {
+        dst.push(item.len() as u8);
+        dst.extend_from_slice(&item);
+        Ok(())
+    }
+}
+
+/// Lines, of which the last one needn't end with a newline.
+struct Lines;
+
+impl Decoder for Lines {
+    type Item = String;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut Vec<u8>) -> Result<Option<String>, io::Error> {
+        match src.iter().position(|&b| b == b'\n') {
+            Some(i) => {
+                let line = String::from_utf8_lossy(&src[..i]).into_owned();
+                src.drain(..i + 1);
+                Ok(Some(line))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut Vec<u8>) -> Result<Option<String>, io::Error> {
+        if let Some(line) = self.decode(src)? {
+            return Ok(Some(line))
+        }
+        if src.is_empty() {
+            return Ok(None)
+        }
+        let line = String::from_utf8_lossy(src).into_owned();
+        src.clear();
+        Ok(Some(line))
+    }
+}
+
+#[test]
+fn decodes_frames() {
+    let framed = Framed::new(Cursor::new(b"\x02hi\x00\x05there".to_vec()), LengthPrefixed);
+    let frames: Vec<_> = block_on(framed.collect()).unwrap();
+    assert_eq!(frames, vec![b"hi".to_vec(), Vec::new(), b"there".to_vec()]);
+}
+
+#[test]
+fn truncated_frame_is_an_error() {
+    let framed = Framed::new(Cursor::new(b"\x02hi\x05the".to_vec()), LengthPrefixed);
+    let (frame, framed) = block_on(framed.next()).ok().unwrap();
+    assert_eq!(frame, Some(b"hi".to_vec()));
+    let err = block_on(framed.next()).err().unwrap().0;
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn decode_eof_ends_last_frame() {
+    let framed = Framed::new(Cursor::new(b"one\ntwo".to_vec()), Lines);
+    let lines: Vec<_> = block_on(framed.collect()).unwrap();
+    assert_eq!(lines, vec!["one".to_string(), "two".to_string()]);
+}
+
+#[test]
+fn encodes_frames() {
+    let framed = Framed::new(Cursor::new(Vec::new()), LengthPrefixed);
+    let framed = block_on(framed.send(b"hi".to_vec())).unwrap();
+    let framed = block_on(framed.send(b"there".to_vec())).unwrap();
+    assert_eq!(framed.into_inner().into_inner(), b"\x02hi\x05there");
+}
+
+#[test]
+fn frames_across_a_pipe() {
+    let (reader, writer) = pipe();
+    let frames: Vec<Vec<u8>> = (0..100u8).map(|i| vec![i; i as usize]).collect();
+
+    let sink = Framed::new(writer, LengthPrefixed);
+    let stream = Framed::new(reader, LengthPrefixed);
+    let send = sink.send_all(futures::stream::iter_ok::<_, io::Error>(frames.clone()))
+        .and_then(|(sink, _)| sink.close());
+    let (_, received): (_, Vec<_>) = block_on(send.join(stream.collect())).unwrap();
+    assert_eq!(received, frames);
+}
@@ -0,0 +1,91 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::codec::{Decoder, Encoder, Framed, LengthDelimitedCodec};
+use futures::executor::block_on;
+use futures::prelude::*;
+
+#[test]
+fn decodes_default_framing() {
+    let mut codec = LengthDelimitedCodec::new();
+    let mut buf = b"\x00\x00\x00".to_vec();
+    assert_eq!(codec.decode(&mut buf).unwrap(), None);
+    buf.extend_from_slice(b"\x03ab");
+    assert_eq!(codec.decode(&mut buf).unwrap(), None);
+    buf.extend_from_slice(b"c\x00\x00");
+    assert_eq!(codec.decode(&mut buf).unwrap(), Some(b"abc".to_vec()));
+    assert_eq!(buf, b"\x00\x00");
+}
+
+#[test]
+fn round_trips_every_width() {
+    for &width in &[1, 2, 4, 8] {
+        let mut codec = LengthDelimitedCodec::builder().length_field_length(width).create();
+        let mut buf = Vec::new();
+        codec.encode(b"hello".to_vec(), &mut buf).unwrap();
+        codec.encode(Vec::new(), &mut buf).unwrap();
+        assert_eq!(buf.len(), 2 * width + 5);
+        assert_eq!(buf[width - 1], 5);
+
+        assert_eq!(codec.decode(&mut buf).unwrap(), Some(b"hello".to_vec()));
+        assert_eq!(codec.decode(&mut buf).unwrap(), Some(Vec::new()));
+        assert!(buf.is_empty());
+    }
+}
+
+#[test]
+fn rejects_oversized_frames() {
+    let mut codec = LengthDelimitedCodec::builder().max_frame_length(4).create();
+    let mut buf = b"\x00\x00\x00\x05".to_vec();
+    assert_eq!(codec.decode(&mut buf).unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+    let mut buf = b"\xff\xff\xff\xff".to_vec();
+    let mut codec = LengthDelimitedCodec::new();
+    assert_eq!(codec.decode(&mut buf).unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+    let mut codec = LengthDelimitedCodec::builder().max_frame_length(4).create();
+    let err = codec.encode(b"hello".to_vec(), &mut Vec::new()).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn rejects_lengths_not_fitting_the_field() {
+    let mut codec = LengthDelimitedCodec::builder().length_field_length(1).create();
+    let err = codec.encode(vec![0; 256], &mut Vec::new()).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn adjusts_lengths() {
+    // The length field counts itself.
+    let mut codec = LengthDelimitedCodec::builder()
+        .length_field_length(2)
+        .length_adjustment(-2)
+        .create();
+    let mut buf = Vec::new();
+    codec.encode(b"abc".to_vec(), &mut buf).unwrap();
+    assert_eq!(buf, b"\x00\x05abc");
+    assert_eq!(codec.decode(&mut buf).unwrap(), Some(b"abc".to_vec()));
+
+    let mut buf = b"\x00\x01".to_vec();
+    assert_eq!(codec.decode(&mut buf).unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+#[should_panic]
+fn rejects_odd_widths() {
+    LengthDelimitedCodec::builder().length_field_length(3);
+}
+
+#[test]
+fn frames_a_stream() {
+    let framed = Framed::new(Cursor::new(Vec::new()), LengthDelimitedCodec::new());
+    let framed = block_on(framed.send(b"one".to_vec())).unwrap();
+    let framed = block_on(framed.send(b"two".to_vec())).unwrap();
+    let bytes = framed.into_inner().into_inner();
+
+    let framed = Framed::new(Cursor::new(bytes), LengthDelimitedCodec::new());
+    let frames: Vec<_> = block_on(framed.collect()).unwrap();
+    assert_eq!(frames, vec![b"one".to_vec(), b"two".to_vec()]);
+}
@@ -0,0 +1,50 @@
+extern crate futures;
+
+use std::cell::RefCell;
+use std::io::{self, Cursor};
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+/// A writer whose flushes only complete on the second poll.
+struct SlowFlush {
+    inner: Cursor<Vec<u8>>,
+    polled: bool,
+}
+
+impl AsyncWrite for SlowFlush {
+    fn poll_write(&mut self, cx: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        self.inner.poll_write(cx, buf)
+    }
+
+    fn poll_flush(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        self.polled = !self.polled;
+        if self.polled {
+            cx.waker().wake();
+            return Ok(Async::Pending)
+        }
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+fn acks_flushed_watermarks() {
+    let acks = RefCell::new(Vec::new());
+    let mut w = SlowFlush { inner: Cursor::new(Vec::new()), polled: false }
+        .ack_on_flush(|n| acks.borrow_mut().push(n));
+
+    block_on(w.write_all(b"abc")).unwrap();
+    assert!(acks.borrow().is_empty());
+    block_on(w.flush()).unwrap();
+    block_on(w.write_all(b"defgh")).unwrap();
+    block_on(w.write_all(b"ij")).unwrap();
+    block_on(w.flush()).unwrap();
+    assert_eq!(w.written(), 10);
+    drop(w);
+
+    assert_eq!(*acks.borrow(), [3, 10]);
+}
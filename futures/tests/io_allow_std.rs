@@ -0,0 +1,74 @@
+extern crate futures;
+
+use std::io::{self, Cursor, Read, Write};
+
+use futures::executor::block_on;
+use futures::io::AllowStdIo;
+use futures::prelude::*;
+
+/// A std reader and writer failing with `kind` once before each operation.
+struct Hiccup {
+    kind: io::ErrorKind,
+    failed: bool,
+    data: Cursor<Vec<u8>>,
+    out: Vec<u8>,
+}
+
+impl Hiccup {
+    fn new(kind: io::ErrorKind, data: &[u8]) -> Hiccup {
+        Hiccup { kind, failed: false, data: Cursor::new(data.to_vec()), out: Vec::new() }
+    }
+
+    fn hiccup(&mut self) -> io::Result<()> {
+        self.failed = !self.failed;
+        if self.failed {
+            Err(io::Error::new(self.kind, "hiccup"))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Read for Hiccup {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.hiccup()?;
+        Read::read(&mut self.data, buf)
+    }
+}
+
+impl Write for Hiccup {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.hiccup()?;
+        Write::write(&mut self.out, buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn would_block_is_retried() {
+    let mut reader = AllowStdIo::new(Hiccup::new(io::ErrorKind::WouldBlock, b"data"));
+    let mut data = Vec::new();
+    block_on(AsyncReadExt::read_to_end(&mut reader, &mut data)).unwrap();
+    assert_eq!(data, b"data");
+
+    block_on(AsyncWriteExt::write_all(&mut reader, b"out")).unwrap();
+    assert_eq!(reader.into_inner().out, b"out");
+}
+
+#[test]
+fn interrupted_is_retried() {
+    let mut reader = AllowStdIo::new(Hiccup::new(io::ErrorKind::Interrupted, b"data"));
+    let mut data = Vec::new();
+    block_on(AsyncReadExt::read_to_end(&mut reader, &mut data)).unwrap();
+    assert_eq!(data, b"data");
+}
+
+#[test]
+fn other_errors_are_passed_on() {
+    let mut reader = AllowStdIo::new(Hiccup::new(io::ErrorKind::Other, b"data"));
+    let err = block_on(AsyncReadExt::read_to_end(&mut reader, &mut Vec::new())).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::Other);
+}
@@ -0,0 +1,31 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+/// A reader which claims to have filled one byte more than its buffer.
+struct OverReporting;
+
+impl AsyncRead for OverReporting {
+    fn poll_read(&mut self, _: &mut task::Context, buf: &mut [u8]) -> Poll<usize, io::Error> {
+        Ok(Async::Ready(buf.len() + 1))
+    }
+}
+
+#[test]
+fn correct_readers_pass() {
+    let mut reader = Cursor::new(b"all good".to_vec()).assert_nondecreasing_reads();
+    let mut data = Vec::new();
+    block_on(reader.read_to_end(&mut data)).unwrap();
+    assert_eq!(data, b"all good");
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "reader reported 9 bytes read into a buffer of 8")]
+fn over_reporting_readers_panic() {
+    let mut reader = OverReporting.assert_nondecreasing_reads();
+    let _ = block_on(reader.read_exact(&mut [0; 8]));
+}
@@ -0,0 +1,57 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::executor::block_on;
+use futures::io::BufReader;
+use futures::prelude::*;
+
+mod support;
+use support::*;
+
+#[test]
+fn at_eof_only_after_last_byte() {
+    let mut reader = Cursor::new(b"ab".to_vec());
+    assert!(!block_on(reader.at_eof()).unwrap());
+
+    block_on(reader.read_exact(&mut [0; 1])).unwrap();
+    assert!(!block_on(reader.at_eof()).unwrap());
+    let mut buf = [0; 1];
+    block_on(reader.read_exact(&mut buf)).unwrap();
+    assert_eq!(buf, *b"b");
+    assert!(block_on(reader.at_eof()).unwrap());
+}
+
+#[test]
+fn empty_stream_is_at_eof() {
+    let mut reader = Cursor::new(Vec::new());
+    assert!(block_on(reader.at_eof()).unwrap());
+}
+
+/// A reader which isn't ready on its first read.
+struct PendingOnce {
+    pending: bool,
+    data: &'static [u8],
+}
+
+impl AsyncRead for PendingOnce {
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8])
+        -> Poll<usize, io::Error>
+    {
+        if self.pending {
+            self.pending = false;
+            cx.waker().wake();
+            return Ok(Async::Pending)
+        }
+        self.data.poll_read(cx, buf)
+    }
+}
+
+#[test]
+fn pending_fill_is_pending() {
+    let mut reader = BufReader::new(PendingOnce { pending: true, data: b"x" });
+    noop_waker_cx(|cx| {
+        assert!(reader.at_eof().poll(cx).unwrap().is_pending());
+        assert_eq!(reader.at_eof().poll(cx).unwrap(), Async::Ready(false));
+    });
+}
@@ -0,0 +1,171 @@
+extern crate futures;
+
+use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use futures::executor::block_on;
+use futures::io::AtomicFrameWriter;
+use futures::prelude::*;
+use futures::task::{LocalMap, Wake, Waker};
+
+mod support;
+use support::*;
+
+/// A writer which accepts at most two bytes per call and returns `Pending`
+/// before every write, giving other futures a chance to run in between.
+#[derive(Clone)]
+struct Choppy {
+    data: Arc<Mutex<Vec<u8>>>,
+    ready: bool,
+}
+
+impl AsyncWrite for Choppy {
+    fn poll_write(&mut self, cx: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        if !self.ready {
+            self.ready = true;
+            cx.waker().wake();
+            return Ok(Async::Pending)
+        }
+        self.ready = false;
+        let n = buf.len().min(2);
+        self.data.lock().unwrap().extend_from_slice(&buf[..n]);
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+fn assert_contiguous(data: &[u8], frames: &[&[u8]]) {
+    let mut rest = data;
+    while !rest.is_empty() {
+        let frame = frames.iter().find(|f| rest.starts_with(f))
+            .expect("frames were interleaved");
+        rest = &rest[frame.len()..];
+    }
+}
+
+#[test]
+fn concurrent_frames_do_not_interleave() {
+    let data = Arc::new(Mutex::new(Vec::new()));
+    let writer = AtomicFrameWriter::new(Choppy { data: data.clone(), ready: false });
+
+    let a = writer.send_frame(b"aaaaaaa".to_vec())
+        .and_then(|()| writer.send_frame(b"AAAAA".to_vec()));
+    let b = writer.send_frame(b"bbbbbbbbb".to_vec())
+        .and_then(|()| writer.send_frame(b"BBB".to_vec()));
+    block_on(a.join(b)).unwrap();
+
+    let data = data.lock().unwrap();
+    assert_eq!(data.len(), 24);
+    assert_contiguous(&data, &[b"aaaaaaa", b"AAAAA", b"bbbbbbbbb", b"BBB"]);
+}
+
+#[test]
+fn frames_from_threads_do_not_interleave() {
+    let data = Arc::new(Mutex::new(Vec::new()));
+    let writer = AtomicFrameWriter::new(Choppy { data: data.clone(), ready: false });
+
+    let threads = (0..2u8).map(|i| {
+        let writer = writer.clone();
+        thread::spawn(move || {
+            for _ in 0..20 {
+                block_on(writer.send_frame(vec![b'0' + i; 11])).unwrap();
+            }
+        })
+    }).collect::<Vec<_>>();
+    for t in threads {
+        t.join().unwrap();
+    }
+
+    let data = data.lock().unwrap();
+    assert_eq!(data.len(), 2 * 20 * 11);
+    assert_contiguous(&data, &[&[b'0'; 11], &[b'1'; 11]]);
+    assert!(writer.into_inner().is_ok());
+}
+
+struct CountWakes(AtomicUsize);
+
+impl Wake for CountWakes {
+    fn wake(arc_self: &Arc<Self>) {
+        arc_self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn waiting_frame_keeps_one_waker() {
+    let data = Arc::new(Mutex::new(Vec::new()));
+    let writer = AtomicFrameWriter::new(Choppy { data: data.clone(), ready: false });
+    let mut first = writer.send_frame(b"first".to_vec());
+    let mut second = writer.send_frame(b"second".to_vec());
+
+    let wakes = Arc::new(CountWakes(AtomicUsize::new(0)));
+    let waker = Waker::from(wakes.clone());
+    let mut map = LocalMap::new();
+    let mut exec = PanicExec;
+    let mut cx = task::Context::new(&mut map, &waker, &mut exec);
+
+    noop_waker_cx(|cx| assert!(first.poll(cx).unwrap().is_pending()));
+    for _ in 0..100 {
+        assert!(second.poll(&mut cx).unwrap().is_pending());
+    }
+    block_on(first).unwrap();
+    assert_eq!(wakes.0.load(Ordering::SeqCst), 1);
+
+    block_on(second).unwrap();
+    assert_eq!(*data.lock().unwrap(), b"firstsecond");
+}
+
+/// A writer which records flushes as `F` and closes as `C` among the bytes
+/// written.
+#[derive(Clone)]
+struct Recording(Arc<Mutex<Vec<u8>>>);
+
+impl AsyncWrite for Recording {
+    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(Async::Ready(buf.len()))
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        self.0.lock().unwrap().push(b'F');
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        self.0.lock().unwrap().push(b'C');
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+fn flush_and_close_between_frames() {
+    let data = Arc::new(Mutex::new(Vec::new()));
+    let writer = AtomicFrameWriter::new(Recording(data.clone()));
+    block_on(writer.send_frame(b"ab".to_vec())).unwrap();
+    block_on(writer.flush()).unwrap();
+    block_on(writer.send_frame(b"cd".to_vec())).unwrap();
+    block_on(writer.close()).unwrap();
+    assert_eq!(*data.lock().unwrap(), b"abFcdC");
+}
+
+#[test]
+fn flush_waits_for_the_frame_being_written() {
+    let data = Arc::new(Mutex::new(Vec::new()));
+    let writer = AtomicFrameWriter::new(Choppy { data: data.clone(), ready: false });
+    let mut frame = writer.send_frame(b"frame".to_vec());
+    let mut flush = writer.flush();
+    noop_waker_cx(|cx| {
+        assert!(frame.poll(cx).unwrap().is_pending());
+        assert!(flush.poll(cx).unwrap().is_pending());
+    });
+    block_on(flush.join(frame)).unwrap();
+    assert_eq!(*data.lock().unwrap(), b"frame");
+}
@@ -0,0 +1,36 @@
+extern crate futures;
+
+use std::io::Cursor;
+
+use futures::executor::block_on;
+use futures::io::bounded_concat;
+use futures::prelude::*;
+
+#[test]
+fn reads_each_source_up_to_its_limit() {
+    let mut reader = bounded_concat(vec![
+        (Cursor::new(&b"headerXXX"[..]), 6),
+        (Cursor::new(&b"body"[..]), 10),
+        (Cursor::new(&b"trailer"[..]), 3),
+    ]);
+    let mut data = Vec::new();
+    block_on(reader.read_to_end(&mut data)).unwrap();
+    assert_eq!(data, b"headerbodytra");
+}
+
+#[test]
+fn segment_boundaries() {
+    let mut reader = bounded_concat(vec![
+        (Cursor::new(&b"aaaa"[..]), 2),
+        (Cursor::new(&b"bbbb"[..]), 3),
+    ]);
+    let mut sizes = Vec::new();
+    loop {
+        let n = block_on(reader.read(&mut [0; 16])).unwrap();
+        if n == 0 {
+            break
+        }
+        sizes.push(n);
+    }
+    assert_eq!(sizes, [2, 3]);
+}
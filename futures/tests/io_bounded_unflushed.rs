@@ -0,0 +1,49 @@
+extern crate futures;
+
+use std::io;
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+/// A writer which uses deferred flushes, recording the length of data
+/// written between them.
+#[derive(Default)]
+struct Recorder {
+    batches: Vec<usize>,
+    current: usize,
+    flush_pending: bool,
+}
+
+impl AsyncWrite for Recorder {
+    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        self.current += buf.len();
+        Ok(Async::Ready(buf.len()))
+    }
+
+    fn poll_flush(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        self.flush_pending = !self.flush_pending;
+        if self.flush_pending {
+            cx.waker().wake();
+            return Ok(Async::Pending)
+        }
+        self.batches.push(self.current);
+        self.current = 0;
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+fn forces_flush_past_limit() {
+    let mut w = Recorder::default().bounded_unflushed(4);
+    block_on(w.write_all(b"abc")).unwrap();
+    assert_eq!(w.unflushed(), 3);
+    block_on(w.write_all(b"defghij")).unwrap();
+    assert_eq!(w.unflushed(), 2);
+    block_on(w.flush()).unwrap();
+    assert_eq!(w.unflushed(), 0);
+    assert_eq!(w.into_inner().batches, [4, 4, 2]);
+}
@@ -0,0 +1,39 @@
+extern crate futures;
+
+use std::io;
+
+use futures::executor::LocalPool;
+use futures::prelude::*;
+
+/// A writer accepting at most ten bytes per call.
+#[derive(Default)]
+struct Short(Vec<u8>);
+
+impl AsyncWrite for Short {
+    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        let n = buf.len().min(10);
+        self.0.extend_from_slice(&buf[..n]);
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+fn write_all_yields_after_every_write() {
+    let mut pool = LocalPool::new();
+    let mut exec = pool.executor();
+
+    let data: Vec<u8> = (0..1000).map(|i| i as u8).collect();
+    let mut writer = Short::default().budget_write(1);
+    pool.run_until(writer.write_all(&data), &mut exec).unwrap();
+
+    assert_eq!(writer.get_ref().0, data);
+    assert_eq!(writer.yields(), 99);
+}
@@ -0,0 +1,40 @@
+#[macro_use]
+extern crate futures;
+
+use std::io::Cursor;
+
+use futures::executor::block_on;
+use futures::future::poll_fn;
+use futures::io::AsyncBufRead;
+use futures::prelude::*;
+
+#[test]
+fn cursor_fill_buf_and_consume() {
+    let mut reader = Cursor::new(b"hello world".to_vec());
+    block_on(poll_fn(|cx| {
+        assert_eq!(try_ready!(reader.poll_fill_buf(cx)), b"hello world");
+        reader.consume(6);
+        assert_eq!(try_ready!(reader.poll_fill_buf(cx)), b"world");
+        reader.consume(5);
+        assert_eq!(try_ready!(reader.poll_fill_buf(cx)), b"");
+        Ok::<_, futures::io::Error>(Async::Ready(()))
+    })).unwrap();
+}
+
+#[test]
+fn delegates_through_box_and_mut_ref() {
+    let mut slice: &[u8] = b"abc";
+    let mut boxed: Box<AsyncBufRead> = Box::new(Cursor::new(b"xyz".to_vec()));
+    block_on(poll_fn(|cx| {
+        {
+            let by_ref = &mut slice;
+            assert_eq!(try_ready!(by_ref.poll_fill_buf(cx)), b"abc");
+            by_ref.consume(1);
+        }
+        assert_eq!(try_ready!(slice.poll_fill_buf(cx)), b"bc");
+
+        boxed.consume(2);
+        assert_eq!(try_ready!(boxed.poll_fill_buf(cx)), b"z");
+        Ok::<_, futures::io::Error>(Async::Ready(()))
+    })).unwrap();
+}
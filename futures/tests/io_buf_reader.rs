@@ -0,0 +1,91 @@
+#[macro_use]
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::executor::block_on;
+use futures::future::poll_fn;
+use futures::io::{AsyncBufRead, AsyncReadable, BufReader};
+use futures::prelude::*;
+
+/// A reader counting its reads which returns `Pending` before each of them.
+struct Counting {
+    inner: Cursor<Vec<u8>>,
+    reads: usize,
+    ready: bool,
+}
+
+impl Counting {
+    fn new(data: &[u8]) -> Counting {
+        Counting { inner: Cursor::new(data.to_vec()), reads: 0, ready: false }
+    }
+}
+
+impl AsyncRead for Counting {
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8]) -> Poll<usize, io::Error> {
+        if !self.ready {
+            self.ready = true;
+            cx.waker().wake();
+            return Ok(Async::Pending)
+        }
+        self.ready = false;
+        self.reads += 1;
+        self.inner.poll_read(cx, buf)
+    }
+}
+
+#[test]
+fn small_reads_are_served_from_the_buffer() {
+    let data: Vec<u8> = (0..100).collect();
+    let mut reader = BufReader::with_capacity(64, Counting::new(&data));
+    let mut out = Vec::new();
+    loop {
+        let mut buf = [0; 3];
+        let n = block_on(reader.read(&mut buf)).unwrap();
+        if n == 0 {
+            break
+        }
+        out.extend_from_slice(&buf[..n]);
+    }
+    assert_eq!(out, data);
+    // Two reads to fill the buffer and one to find EOF.
+    assert_eq!(reader.get_ref().reads, 3);
+}
+
+#[test]
+fn large_reads_bypass_the_buffer() {
+    let mut reader = BufReader::with_capacity(4, Counting::new(b"hello world"));
+    let mut buf = [0; 16];
+    let n = block_on(reader.read(&mut buf)).unwrap();
+    assert_eq!(&buf[..n], b"hello world");
+    assert_eq!(reader.into_inner().reads, 1);
+}
+
+#[test]
+fn fill_buf_retries_after_pending() {
+    let mut reader = BufReader::new(Counting::new(b"hello world"));
+    block_on(poll_fn(|cx| {
+        assert_eq!(try_ready!(reader.poll_fill_buf(cx)), b"hello world");
+        reader.consume(6);
+        Ok::<_, io::Error>(Async::Ready(()))
+    })).unwrap();
+    let mut rest = Vec::new();
+    block_on(reader.read_to_end(&mut rest)).unwrap();
+    assert_eq!(rest, b"world");
+}
+
+#[test]
+fn readable_once_bytes_are_buffered() {
+    let mut reader = BufReader::new(Counting::new(b"hello"));
+    block_on(poll_fn(|cx| reader.poll_readable(cx))).unwrap();
+    assert_eq!(reader.get_ref().reads, 1);
+
+    // The bytes are buffered now, so this doesn't read again.
+    block_on(poll_fn(|cx| reader.poll_readable(cx))).unwrap();
+    assert_eq!(reader.get_ref().reads, 1);
+
+    let mut data = Vec::new();
+    block_on(reader.read_to_end(&mut data)).unwrap();
+    assert_eq!(data, b"hello");
+    assert_eq!(reader.get_ref().reads, 2);
+}
@@ -0,0 +1,95 @@
+extern crate futures;
+
+use std::io;
+
+use futures::executor::block_on;
+use futures::future::poll_fn;
+use futures::io::{AsyncWritable, BufWriter};
+use futures::prelude::*;
+
+/// A writer which accepts at most `max` bytes per call, returns `Pending`
+/// before every write, and counts its writes.
+struct Short {
+    data: Vec<u8>,
+    max: usize,
+    writes: usize,
+    ready: bool,
+}
+
+impl Short {
+    fn new(max: usize) -> Short {
+        Short { data: Vec::new(), max, writes: 0, ready: false }
+    }
+}
+
+impl AsyncWrite for Short {
+    fn poll_write(&mut self, cx: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        if !self.ready {
+            self.ready = true;
+            cx.waker().wake();
+            return Ok(Async::Pending)
+        }
+        self.ready = false;
+        self.writes += 1;
+        let n = buf.len().min(self.max);
+        self.data.extend_from_slice(&buf[..n]);
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+fn small_writes_are_batched() {
+    let mut writer = BufWriter::with_capacity(16, Short::new(100));
+    for _ in 0..10 {
+        block_on(writer.write_all(b"abc")).unwrap();
+    }
+    // The buffer was written out once when it was about to overflow.
+    assert_eq!(writer.get_ref().writes, 1);
+    assert_eq!(writer.get_ref().data, &b"abcabcabcabcabc"[..]);
+
+    block_on(writer.flush()).unwrap();
+    assert_eq!(writer.get_ref().writes, 2);
+    assert_eq!(writer.get_ref().data.len(), 30);
+}
+
+#[test]
+fn partial_writes_of_the_buffer_lose_nothing() {
+    let data: Vec<u8> = (0..100).collect();
+    let mut writer = BufWriter::with_capacity(32, Short::new(5));
+    block_on(writer.write_all(&data)).unwrap();
+    let inner = block_on(writer.into_inner()).unwrap();
+    assert_eq!(inner.data, data);
+}
+
+#[test]
+fn large_write_flushes_first() {
+    let mut writer = BufWriter::with_capacity(8, Short::new(100));
+    block_on(writer.write_all(b"head")).unwrap();
+    block_on(writer.write_all(b"a large write")).unwrap();
+    assert_eq!(writer.get_ref().data, b"heada large write");
+    block_on(writer.write_all(b"tail")).unwrap();
+    block_on(writer.close()).unwrap();
+    assert_eq!(writer.get_ref().data, b"heada large writetail");
+}
+
+#[test]
+fn writable_while_the_buffer_has_room() {
+    let mut writer = BufWriter::with_capacity(4, Short::new(100));
+    block_on(poll_fn(|cx| writer.poll_writable(cx))).unwrap();
+    block_on(writer.write_all(b"abc")).unwrap();
+    block_on(writer.write_all(b"d")).unwrap();
+    assert_eq!(writer.get_ref().writes, 0);
+
+    // The buffer is full, so it is written out first.
+    block_on(poll_fn(|cx| writer.poll_writable(cx))).unwrap();
+    assert_eq!(writer.get_ref().writes, 1);
+    assert_eq!(writer.get_ref().data, b"abcd");
+}
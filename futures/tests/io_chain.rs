@@ -0,0 +1,90 @@
+extern crate futures;
+
+mod support;
+
+use std::io::{self, Cursor};
+
+use futures::executor::block_on;
+use futures::io::Initializer;
+use futures::prelude::*;
+
+use support::*;
+
+/// A reader which returns `Pending` before each of its reads.
+struct Flaky {
+    data: Vec<u8>,
+    ready: bool,
+}
+
+impl AsyncRead for Flaky {
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8]) -> Poll<usize, io::Error> {
+        if !self.ready {
+            self.ready = true;
+            cx.waker().wake();
+            return Ok(Async::Pending)
+        }
+        self.ready = false;
+        let n = buf.len().min(self.data.len()).min(3);
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data.drain(..n);
+        Ok(Async::Ready(n))
+    }
+}
+
+#[test]
+fn reads_first_then_second() {
+    let mut reader = Cursor::new(b"header ".to_vec()).chain(Cursor::new(b"body".to_vec()));
+    let mut data = Vec::new();
+    block_on(reader.read_to_end(&mut data)).unwrap();
+    assert_eq!(data, b"header body");
+    let (first, second) = reader.into_inner();
+    assert_eq!(first.position(), 7);
+    assert_eq!(second.position(), 4);
+}
+
+#[test]
+fn pending_does_not_switch_readers() {
+    let first = Flaky { data: b"0123456789".to_vec(), ready: false };
+    let mut reader = first.chain(Cursor::new(b"abc".to_vec()));
+    let mut data = Vec::new();
+    block_on(reader.read_to_end(&mut data)).unwrap();
+    assert_eq!(data, b"0123456789abc");
+}
+
+/// A reader which doesn't require its buffers to be initialized.
+struct Uninit;
+
+impl AsyncRead for Uninit {
+    unsafe fn initializer(&self) -> Initializer {
+        Initializer::nop()
+    }
+
+    fn poll_read(&mut self, _: &mut task::Context, _: &mut [u8]) -> Poll<usize, io::Error> {
+        Ok(Async::Ready(0))
+    }
+}
+
+#[test]
+fn initializer_is_the_more_conservative() {
+    unsafe {
+        assert!(!Uninit.chain(Uninit).initializer().should_initialize());
+        let zeroing = Flaky { data: vec![], ready: false };
+        assert!(Uninit.chain(zeroing).initializer().should_initialize());
+        let zeroing = Flaky { data: vec![], ready: false };
+        assert!(zeroing.chain(Uninit).initializer().should_initialize());
+    }
+}
+
+#[test]
+fn buffered_chain() {
+    let mut reader = Cursor::new(b"ab".to_vec()).chain(Cursor::new(b"cd".to_vec()));
+    noop_waker_cx(|cx| {
+        assert_eq!(reader.poll_fill_buf(cx).unwrap(), Async::Ready(&b"ab"[..]));
+        reader.consume(1);
+        assert_eq!(reader.poll_fill_buf(cx).unwrap(), Async::Ready(&b"b"[..]));
+        reader.consume(1);
+        assert_eq!(reader.poll_fill_buf(cx).unwrap(), Async::Ready(&b"cd"[..]));
+        reader.consume(2);
+        assert_eq!(reader.poll_fill_buf(cx).unwrap(), Async::Ready(&b""[..]));
+    });
+}
@@ -0,0 +1,40 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::executor::block_on;
+use futures::io::DuplicateKeys;
+use futures::prelude::*;
+
+/// Decodes `key=value\n` lines.
+fn decode(buf: &mut Vec<u8>) -> Option<(Vec<u8>, Vec<u8>)> {
+    let end = buf.iter().position(|&b| b == b'\n')?;
+    let line: Vec<u8> = buf.drain(..end + 1).collect();
+    let eq = line.iter().position(|&b| b == b'=').unwrap();
+    Some((line[..eq].to_vec(), line[eq + 1..end].to_vec()))
+}
+
+const DATA: &[u8] = b"host=example.org\nport=80\nport=8080\n";
+
+#[test]
+fn last_duplicate_wins() {
+    let reader = Cursor::new(DATA.to_vec());
+    let (_, map) = block_on(reader.collect_pairs(decode, DuplicateKeys::LastWins)).unwrap();
+    assert_eq!(map.len(), 2);
+    assert_eq!(map[&b"host"[..]], b"example.org");
+    assert_eq!(map[&b"port"[..]], b"8080");
+}
+
+#[test]
+fn duplicates_can_be_errors() {
+    let reader = Cursor::new(DATA.to_vec());
+    let err = block_on(reader.collect_pairs(decode, DuplicateKeys::Error)).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn truncated_frames_are_errors() {
+    let reader = Cursor::new(b"host=example.org\nport".to_vec());
+    let err = block_on(reader.collect_pairs(decode, DuplicateKeys::LastWins)).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
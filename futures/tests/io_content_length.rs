@@ -0,0 +1,32 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+#[test]
+fn exact_length_is_accepted() {
+    let mut reader = Cursor::new(b"hello".to_vec()).content_length(5);
+    let mut body = Vec::new();
+    block_on(reader.read_to_end(&mut body)).unwrap();
+    assert_eq!(body, b"hello");
+    assert_eq!(reader.remaining(), 0);
+}
+
+#[test]
+fn short_stream_is_rejected() {
+    let mut reader = Cursor::new(b"hell".to_vec()).content_length(5);
+    let err = block_on(reader.read_to_end(&mut Vec::new())).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn long_stream_is_rejected() {
+    let mut reader = Cursor::new(b"hello!".to_vec()).content_length(5);
+    let mut buf = [0; 5];
+    block_on(reader.read_exact(&mut buf)).unwrap();
+    assert_eq!(&buf, b"hello");
+    let err = block_on(reader.read_to_end(&mut Vec::new())).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
@@ -0,0 +1,57 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::executor::block_on;
+use futures::io::copy;
+use futures::prelude::*;
+
+/// A writer accepting at most seven bytes per call which records flushes.
+#[derive(Default)]
+struct Short {
+    data: Vec<u8>,
+    flushed: bool,
+}
+
+impl AsyncWrite for Short {
+    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        let n = buf.len().min(7);
+        self.data.extend_from_slice(&buf[..n]);
+        self.flushed = false;
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        self.flushed = true;
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+fn copies_everything_and_flushes() {
+    let data: Vec<u8> = (0..5000).map(|i| i as u8).collect();
+    let mut writer = Short::default();
+    let n = block_on(copy(Cursor::new(data.clone()), &mut writer)).unwrap();
+    assert_eq!(n, 5000);
+    assert_eq!(writer.data, data);
+    assert!(writer.flushed);
+}
+
+/// A reader which fails.
+struct Broken;
+
+impl AsyncRead for Broken {
+    fn poll_read(&mut self, _: &mut task::Context, _: &mut [u8]) -> Poll<usize, io::Error> {
+        Err(io::Error::new(io::ErrorKind::Other, "broken"))
+    }
+}
+
+#[test]
+fn reader_errors_are_propagated() {
+    let err = block_on(copy(Broken, Short::default())).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::Other);
+}
@@ -0,0 +1,52 @@
+extern crate futures;
+
+use std::io;
+
+use futures::executor::block_on;
+use futures::io::{AsyncCork, CorkWriter};
+use futures::prelude::*;
+
+/// A writer recording every write as a separate chunk.
+#[derive(Default)]
+struct Chunks(Vec<Vec<u8>>);
+
+impl AsyncWrite for Chunks {
+    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        self.0.push(buf.to_vec());
+        Ok(Async::Ready(buf.len()))
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+fn corked_writes_are_coalesced() {
+    let mut writer = CorkWriter::new(Chunks::default());
+    writer.cork();
+    for chunk in &[&b"GET "[..], b"/ ", b"HTTP/1.1\r\n"] {
+        block_on(writer.write_all(*chunk)).unwrap();
+    }
+    block_on(writer.flush()).unwrap();
+    assert!(writer.get_ref().0.is_empty());
+    assert_eq!(writer.buffered(), 16);
+
+    writer.uncork();
+    block_on(writer.flush()).unwrap();
+    assert_eq!(writer.get_ref().0, [&b"GET / HTTP/1.1\r\n"[..]]);
+}
+
+#[test]
+fn close_uncorks() {
+    let mut writer = CorkWriter::new(Chunks::default());
+    writer.cork();
+    block_on(writer.write_all(b"bye")).unwrap();
+    block_on(writer.close()).unwrap();
+    assert!(!writer.is_corked());
+    assert_eq!(writer.get_ref().0, [&b"bye"[..]]);
+}
@@ -0,0 +1,64 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+mod support;
+use support::*;
+
+/// A writer accepting at most two bytes per write.
+#[derive(Debug)]
+struct Partial(Vec<u8>);
+
+impl AsyncWrite for Partial {
+    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        let n = buf.len().min(2);
+        self.0.extend_from_slice(&buf[..n]);
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+fn counts_reads() {
+    let mut reader = Cursor::new(b"hello world".to_vec()).count();
+    assert_eq!(reader.byte_count(), 0);
+    let mut data = Vec::new();
+    block_on(reader.read_to_end(&mut data)).unwrap();
+    assert_eq!(data, b"hello world");
+    assert_eq!(reader.byte_count(), 11);
+}
+
+#[test]
+fn counts_accepted_writes() {
+    let mut writer = Partial(Vec::new()).count_write();
+    noop_waker_cx(|cx| {
+        assert_eq!(writer.poll_write(cx, b"hello").unwrap(), Async::Ready(2));
+    });
+    assert_eq!(writer.byte_count(), 2);
+
+    block_on(writer.write_all(b"hello")).unwrap();
+    assert_eq!(writer.byte_count(), 7);
+    assert_eq!(writer.into_inner().0, b"hehello");
+}
+
+#[test]
+fn duplex_objects_count_both_ways() {
+    let mut reader = Cursor::new(b"abc".to_vec()).count();
+    block_on(reader.read_to_end(&mut Vec::new())).unwrap();
+    assert_eq!(reader.byte_count(), 3);
+
+    let mut writer = Cursor::new(Vec::<u8>::new()).count_write();
+    block_on(writer.write_all(b"hello")).unwrap();
+    assert_eq!(writer.byte_count(), 5);
+    assert_eq!(writer.into_inner().into_inner(), b"hello");
+}
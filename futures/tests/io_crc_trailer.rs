@@ -0,0 +1,61 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+#[test]
+fn appends_the_crc_on_close() {
+    let mut writer = Cursor::new(Vec::new()).crc_trailer_write();
+    block_on(writer.write_all(b"123456789")).unwrap();
+    assert_eq!(writer.crc(), 0xcbf4_3926);
+    block_on(writer.close()).unwrap();
+    assert_eq!(writer.into_inner().into_inner(), b"123456789\xcb\xf4\x39\x26");
+}
+
+/// A writer accepting one byte per call.
+#[derive(Default)]
+struct ByteAtATime(Vec<u8>);
+
+impl AsyncWrite for ByteAtATime {
+    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        self.0.extend_from_slice(&buf[..buf.len().min(1)]);
+        Ok(Async::Ready(buf.len().min(1)))
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+/// A bitwise CRC-32, the slow but obvious way.
+fn reference_crc(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+#[test]
+fn trailer_verifies_against_the_payload() {
+    let payload: Vec<u8> = (0..1000).map(|i| (i * 7 % 256) as u8).collect();
+    let mut writer = ByteAtATime::default().crc_trailer_write();
+    block_on(writer.write_all(&payload)).unwrap();
+    block_on(writer.close()).unwrap();
+    let stream = writer.into_inner().0;
+
+    let (body, trailer) = stream.split_at(stream.len() - 4);
+    assert_eq!(body, &payload[..]);
+    let expected = reference_crc(body);
+    assert_eq!(trailer, [(expected >> 24) as u8, (expected >> 16) as u8,
+                         (expected >> 8) as u8, expected as u8]);
+}
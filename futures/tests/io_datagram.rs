@@ -0,0 +1,65 @@
+extern crate futures;
+
+use std::io;
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+/// A writer recording every write as a separate message.
+#[derive(Default)]
+struct Messages(Vec<Vec<u8>>);
+
+impl AsyncWrite for Messages {
+    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        self.0.push(buf.to_vec());
+        Ok(Async::Ready(buf.len()))
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+fn oversized_message_is_rejected() {
+    let mut writer = Messages::default().datagram_write(4);
+    let err = block_on(writer.write_all(b"too long")).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    assert!(writer.get_ref().0.is_empty());
+}
+
+#[test]
+fn message_is_delivered_whole() {
+    let mut writer = Messages::default().datagram_write(8);
+    block_on(writer.write_all(b"one")).unwrap();
+    block_on(writer.write_all(b"two two")).unwrap();
+    assert_eq!(writer.into_inner().0, [&b"one"[..], b"two two"]);
+}
+
+/// A writer accepting at most two bytes per call.
+struct Short;
+
+impl AsyncWrite for Short {
+    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        Ok(Async::Ready(buf.len().min(2)))
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+fn partial_write_is_an_error() {
+    let mut writer = Short.datagram_write(8);
+    let err = block_on(writer.write_all(b"abc")).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::WriteZero);
+}
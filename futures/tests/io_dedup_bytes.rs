@@ -0,0 +1,36 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+#[test]
+fn collapses_runs() {
+    let mut reader = Cursor::new(b"aaabbbc".to_vec()).dedup_bytes();
+    let mut out = Vec::new();
+    block_on(reader.read_to_end(&mut out)).unwrap();
+    assert_eq!(out, b"abc");
+}
+
+/// A reader yielding the given chunks one read at a time.
+struct Chunks(Vec<&'static [u8]>);
+
+impl AsyncRead for Chunks {
+    fn poll_read(&mut self, _: &mut task::Context, buf: &mut [u8]) -> Poll<usize, io::Error> {
+        if self.0.is_empty() {
+            return Ok(Async::Ready(0))
+        }
+        let chunk = self.0.remove(0);
+        buf[..chunk.len()].copy_from_slice(chunk);
+        Ok(Async::Ready(chunk.len()))
+    }
+}
+
+#[test]
+fn runs_spanning_reads_are_collapsed() {
+    let mut reader = Chunks(vec![b"aa", b"a", b"aab", b"bb", b"bc", b"cd"]).dedup_bytes();
+    let mut out = Vec::new();
+    block_on(reader.read_to_end(&mut out)).unwrap();
+    assert_eq!(out, b"abcd");
+}
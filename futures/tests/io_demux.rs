@@ -0,0 +1,109 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+fn frame(out: &mut Vec<u8>, tag: u8, payload: &[u8]) {
+    out.push(tag);
+    out.extend_from_slice(&[0, 0, (payload.len() >> 8) as u8, payload.len() as u8]);
+    out.extend_from_slice(payload);
+}
+
+#[test]
+fn routes_payloads_by_tag() {
+    let mut data = Vec::new();
+    frame(&mut data, 1, b"one ");
+    frame(&mut data, 2, b"alpha ");
+    frame(&mut data, 1, b"two");
+    frame(&mut data, 3, b"");
+    frame(&mut data, 2, b"beta");
+
+    let mut readers = Cursor::new(data).demux(&[1, 2, 3]);
+    let mut three = readers.remove(&3).unwrap();
+    let mut two = readers.remove(&2).unwrap();
+    let mut one = readers.remove(&1).unwrap();
+
+    let mut out = Vec::new();
+    block_on(one.read_to_end(&mut out)).unwrap();
+    assert_eq!(out, b"one two");
+    out.clear();
+    block_on(two.read_to_end(&mut out)).unwrap();
+    assert_eq!(out, b"alpha beta");
+    out.clear();
+    assert_eq!(block_on(three.read_to_end(&mut out)).unwrap(), 0);
+}
+
+#[test]
+fn full_buffer_pauses_other_tags() {
+    let mut data = Vec::new();
+    frame(&mut data, 1, &vec![b'x'; 20 * 1024]);
+    frame(&mut data, 2, b"late");
+
+    let mut readers = Cursor::new(data).demux(&[1, 2]);
+    let mut one = readers.remove(&1).unwrap();
+    let mut two = readers.remove(&2).unwrap();
+
+    let mut got = Vec::new();
+    let mut late = Vec::new();
+    let both = two.read_to_end(&mut late).join(futures::future::poll_fn(|cx| {
+        let mut buf = [0; 4096];
+        loop {
+            match one.poll_read(cx, &mut buf)? {
+                Async::Ready(0) => return Ok(Async::Ready(())),
+                Async::Ready(n) => got.extend_from_slice(&buf[..n]),
+                Async::Pending => return Ok(Async::Pending),
+            }
+        }
+    }));
+    block_on(both).unwrap();
+    assert_eq!(late, b"late");
+    assert_eq!(got.len(), 20 * 1024);
+}
+
+#[test]
+fn unknown_tag_is_an_error() {
+    let mut data = Vec::new();
+    frame(&mut data, 9, b"?");
+
+    let mut readers = Cursor::new(data).demux(&[1]);
+    let mut one = readers.remove(&1).unwrap();
+    let err = block_on(one.read_to_end(&mut Vec::new())).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn dropped_reader_doesnt_stall_others() {
+    let mut data = Vec::new();
+    frame(&mut data, 1, b"first");
+    frame(&mut data, 2, b"early ");
+    frame(&mut data, 1, &vec![b'x'; 20 * 1024]);
+    frame(&mut data, 2, b"late");
+
+    let mut readers = Cursor::new(data).demux(&[1, 2]);
+    let mut one = readers.remove(&1).unwrap();
+    let mut two = readers.remove(&2).unwrap();
+
+    let mut first = [0; 5];
+    block_on(one.read_exact(&mut first)).unwrap();
+    assert_eq!(first, *b"first");
+    drop(one);
+
+    let mut rest = Vec::new();
+    block_on(two.read_to_end(&mut rest)).unwrap();
+    assert_eq!(rest, b"early late");
+}
+
+#[test]
+fn duplicate_tags_get_one_reader() {
+    let mut data = Vec::new();
+    frame(&mut data, 1, b"one");
+
+    let mut readers = Cursor::new(data).demux(&[1, 1]);
+    assert_eq!(readers.len(), 1);
+    let mut one = readers.remove(&1).unwrap();
+    let mut out = Vec::new();
+    block_on(one.read_to_end(&mut out)).unwrap();
+    assert_eq!(out, b"one");
+}
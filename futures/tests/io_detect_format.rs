@@ -0,0 +1,52 @@
+extern crate futures;
+
+use std::io::Cursor;
+
+use futures::executor::block_on;
+use futures::io::Detected;
+use futures::prelude::*;
+
+#[derive(Debug, PartialEq)]
+enum Format {
+    Gzip,
+    Png,
+}
+
+fn detectors() -> Vec<(&'static [u8], Format)> {
+    vec![
+        (&[0x1f, 0x8b][..], Format::Gzip),
+        (&b"\x89PNG\r\n\x1a\n"[..], Format::Png),
+    ]
+}
+
+fn detect(data: &[u8]) -> (Detected<Format>, Vec<u8>) {
+    let (detected, mut reader) = block_on(Cursor::new(data.to_vec()).detect_format(detectors()))
+        .unwrap();
+    let mut out = Vec::new();
+    block_on(reader.read_to_end(&mut out)).unwrap();
+    (detected, out)
+}
+
+#[test]
+fn detects_each_format_and_keeps_the_stream() {
+    let gzip = b"\x1f\x8b\x08\x00rest of the gzip stream";
+    assert_eq!(detect(gzip), (Detected::Known(Format::Gzip), gzip.to_vec()));
+
+    let png = b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR";
+    assert_eq!(detect(png), (Detected::Known(Format::Png), png.to_vec()));
+}
+
+#[test]
+fn unknown_formats() {
+    assert_eq!(detect(b"plain text"), (Detected::Unknown, b"plain text".to_vec()));
+    assert_eq!(detect(b"\x89PN"), (Detected::Unknown, b"\x89PN".to_vec()));
+    assert_eq!(detect(b""), (Detected::Unknown, Vec::new()));
+}
+
+#[test]
+fn earlier_detectors_win() {
+    let detectors = vec![(&b"ab"[..], 1), (&b"a"[..], 2)];
+    let (detected, _) = block_on(Cursor::new(b"abc".to_vec()).detect_format(detectors))
+        .unwrap();
+    assert_eq!(detected, Detected::Known(1));
+}
@@ -0,0 +1,41 @@
+extern crate futures;
+
+use std::io;
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+mod support;
+
+use support::*;
+
+#[test]
+fn reads_are_at_eof() {
+    let mut data = vec![1, 2];
+    assert_eq!(block_on(io::empty().read_to_end(&mut data)).unwrap(), 0);
+    assert_eq!(data, [1, 2]);
+
+    let mut empty = io::empty();
+    let mut buf = [0; 8];
+    noop_waker_cx(|cx| {
+        assert_eq!(empty.poll_read(cx, &mut buf).unwrap(), Async::Ready(0));
+        assert_eq!(empty.poll_fill_buf(cx).unwrap(), Async::Ready(&[][..]));
+    });
+}
+
+#[test]
+fn initializer_is_nop() {
+    unsafe {
+        assert!(!io::empty().initializer().should_initialize());
+    }
+}
+
+#[test]
+fn writes_are_discarded() {
+    let mut empty = io::empty();
+    noop_waker_cx(|cx| {
+        assert_eq!(empty.poll_write(cx, b"gone").unwrap(), Async::Ready(4));
+        assert_eq!(empty.poll_flush(cx).unwrap(), Async::Ready(()));
+        assert_eq!(empty.poll_close(cx).unwrap(), Async::Ready(()));
+    });
+}
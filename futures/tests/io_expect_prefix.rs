@@ -0,0 +1,45 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+#[test]
+fn matching_prefix_is_consumed() {
+    let mut reader = Cursor::new(&b"\x7fELF body"[..]).expect_prefix(b"\x7fELF");
+    let mut data = Vec::new();
+    block_on(reader.read_to_end(&mut data)).unwrap();
+    assert_eq!(data, b" body");
+    assert!(reader.is_verified());
+}
+
+#[test]
+fn prefix_spanning_reads() {
+    // Reading into a 2-byte buffer forces the prefix across several reads.
+    let mut reader = Cursor::new(&b"MAGICrest"[..]).expect_prefix(b"MAGIC");
+    let mut out = Vec::new();
+    loop {
+        let mut buf = [0; 2];
+        let n = block_on(reader.read(&mut buf)).unwrap();
+        if n == 0 {
+            break
+        }
+        out.extend_from_slice(&buf[..n]);
+    }
+    assert_eq!(out, b"rest");
+}
+
+#[test]
+fn mismatching_prefix_is_an_error() {
+    let mut reader = Cursor::new(&b"GIF89a"[..]).expect_prefix(b"\x89PNG");
+    let err = block_on(reader.read_to_end(&mut Vec::new())).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn short_stream_is_an_error() {
+    let mut reader = Cursor::new(&b"MA"[..]).expect_prefix(b"MAGIC");
+    let err = block_on(reader.read_to_end(&mut Vec::new())).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
@@ -0,0 +1,44 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::executor::block_on;
+use futures::io::FencedWriter;
+use futures::prelude::*;
+
+#[test]
+fn stale_writes_are_rejected() {
+    let mut writer = FencedWriter::new(Cursor::new(Vec::new()));
+    let mut in_flight = writer.clone();
+    block_on(writer.write_all(b"first ")).unwrap();
+    assert_eq!(writer.generation(), 0);
+
+    assert_eq!(writer.bump_generation(), 1);
+    assert!(in_flight.is_stale());
+    assert!(!writer.is_stale());
+
+    let err = block_on(in_flight.write_all(b"stale")).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::NotConnected);
+
+    let mut fresh = writer.clone();
+    block_on(fresh.write_all(b"second")).unwrap();
+    drop((in_flight, fresh));
+    assert_eq!(writer.into_inner().unwrap().into_inner(), b"first second");
+}
+
+#[test]
+fn reconnect_swaps_the_writer() {
+    let mut writer = FencedWriter::new(Cursor::new(Vec::new()));
+    let mut in_flight = writer.clone();
+    block_on(writer.write_all(b"old")).unwrap();
+
+    let old = writer.reconnect(Cursor::new(Vec::new()));
+    assert_eq!(old.into_inner(), b"old");
+    assert_eq!(writer.generation(), 1);
+
+    let err = block_on(in_flight.write_all(b"stale")).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::NotConnected);
+    block_on(writer.write_all(b"new")).unwrap();
+    drop(in_flight);
+    assert_eq!(writer.into_inner().unwrap().into_inner(), b"new");
+}
@@ -0,0 +1,44 @@
+extern crate futures;
+
+use std::io;
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+/// A writer recording the calls made to it, which fails if closed twice.
+#[derive(Default)]
+struct Recorder {
+    calls: Vec<&'static str>,
+}
+
+impl AsyncWrite for Recorder {
+    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        self.calls.push("write");
+        Ok(Async::Ready(buf.len()))
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        self.calls.push("flush");
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        if self.calls.contains(&"close") {
+            return Err(io::Error::new(io::ErrorKind::Other, "closed twice"))
+        }
+        self.calls.push("close");
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+fn closes_exactly_once() {
+    let mut w = Recorder::default().finalize_once();
+    block_on(w.write_all(b"data")).unwrap();
+    block_on(w.close()).unwrap();
+    assert!(w.is_closed());
+    block_on(w.close()).unwrap();
+    block_on(w.flush()).unwrap();
+    block_on(w.close()).unwrap();
+    assert_eq!(w.into_inner().calls, ["write", "flush", "close"]);
+}
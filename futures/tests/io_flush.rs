@@ -0,0 +1,86 @@
+extern crate futures;
+
+use std::io;
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+mod support;
+use support::*;
+
+/// A writer whose flushes and closes return `Pending` a few times first.
+#[derive(Default)]
+struct Slow {
+    data: Vec<u8>,
+    flushed: usize,
+    pending: usize,
+    flush_polls: usize,
+    closed: bool,
+}
+
+impl Slow {
+    fn delay(&mut self, cx: &mut task::Context) -> bool {
+        if self.pending < 2 {
+            self.pending += 1;
+            cx.waker().wake();
+            return true
+        }
+        self.pending = 0;
+        false
+    }
+}
+
+impl AsyncWrite for Slow {
+    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        self.data.extend_from_slice(buf);
+        Ok(Async::Ready(buf.len()))
+    }
+
+    fn poll_flush(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        self.flush_polls += 1;
+        if self.delay(cx) {
+            return Ok(Async::Pending)
+        }
+        self.flushed = self.data.len();
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        if self.delay(cx) {
+            return Ok(Async::Pending)
+        }
+        self.closed = true;
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+fn write_then_flush() {
+    let mut writer = Slow::default();
+    block_on(writer.write_all(b"hello")).unwrap();
+    block_on(writer.flush()).unwrap();
+    assert_eq!(writer.data, b"hello");
+    assert_eq!(writer.flushed, 5);
+    assert_eq!(writer.flush_polls, 3);
+}
+
+#[test]
+fn dropped_flush_leaves_writer_usable() {
+    let mut writer = Slow::default();
+    noop_waker_cx(|cx| {
+        let mut flush = writer.flush();
+        assert!(flush.poll(cx).unwrap().is_pending());
+    });
+    assert_eq!(writer.flushed, 0);
+
+    block_on(writer.write_all(b"more")).unwrap();
+    block_on(writer.flush()).unwrap();
+    assert_eq!(writer.flushed, 4);
+}
+
+#[test]
+fn close_by_reference() {
+    let mut writer = Slow::default();
+    block_on(writer.close()).unwrap();
+    assert!(writer.closed);
+}
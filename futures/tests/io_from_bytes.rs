@@ -0,0 +1,52 @@
+extern crate futures;
+
+use std::io::{Cursor, SeekFrom};
+
+use futures::executor::block_on;
+use futures::io::from_bytes;
+use futures::prelude::*;
+
+mod support;
+use support::*;
+
+/// A parser owning its input, which a borrowed `&[u8]` couldn't be.
+struct Parser<R> {
+    input: R,
+}
+
+fn parser(bytes: &[u8]) -> Parser<Cursor<Vec<u8>>> {
+    Parser { input: from_bytes(bytes) }
+}
+
+#[test]
+fn owned_reader_reads_to_eof() {
+    let mut parser = parser(b"abc");
+    let mut buf = [0; 2];
+    noop_waker_cx(|cx| {
+        assert_eq!(parser.input.poll_read(cx, &mut buf).unwrap(), Async::Ready(2));
+        assert_eq!(&buf, b"ab");
+        assert_eq!(parser.input.poll_read(cx, &mut buf).unwrap(), Async::Ready(1));
+        assert_eq!(buf[0], b'c');
+        assert_eq!(parser.input.poll_read(cx, &mut buf).unwrap(), Async::Ready(0));
+        assert_eq!(parser.input.poll_read(cx, &mut buf).unwrap(), Async::Ready(0));
+
+        assert_eq!(parser.input.poll_seek(cx, SeekFrom::Start(1)).unwrap(), Async::Ready(1));
+        assert_eq!(parser.input.poll_read(cx, &mut buf).unwrap(), Async::Ready(2));
+        assert_eq!(&buf, b"bc");
+    });
+}
+
+#[test]
+fn owned_reader_does_not_zero_buffers() {
+    let reader = from_bytes(b"abc");
+    let initializer = unsafe { reader.initializer() };
+    assert!(!initializer.should_initialize());
+}
+
+#[test]
+fn arrays_are_read_through_a_cursor() {
+    let mut reader = Cursor::new([1u8, 2, 3, 4]);
+    let mut data = Vec::new();
+    block_on(reader.read_to_end(&mut data)).unwrap();
+    assert_eq!(data, [1, 2, 3, 4]);
+}
@@ -0,0 +1,62 @@
+extern crate futures;
+
+use std::io;
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+/// A reader which yields its data once, reports EOF, and then panics if it
+/// is polled again.
+struct Once {
+    data: Option<&'static [u8]>,
+    eof: bool,
+}
+
+impl AsyncRead for Once {
+    fn poll_read(&mut self, _: &mut task::Context, buf: &mut [u8]) -> Poll<usize, io::Error> {
+        assert!(!self.eof, "polled after EOF");
+        match self.data.take() {
+            Some(data) => {
+                buf[..data.len()].copy_from_slice(data);
+                Ok(Async::Ready(data.len()))
+            }
+            None => {
+                self.eof = true;
+                Ok(Async::Ready(0))
+            }
+        }
+    }
+}
+
+#[test]
+fn extra_polls_after_eof_return_zero() {
+    let mut reader = Once { data: Some(b"hi"), eof: false }.fuse();
+    let mut data = Vec::new();
+    block_on(reader.read_to_end(&mut data)).unwrap();
+    assert_eq!(data, b"hi");
+    assert!(reader.is_done());
+
+    for _ in 0..3 {
+        let n = block_on(futures::future::poll_fn(|cx| reader.poll_read(cx, &mut [0; 8])))
+            .unwrap();
+        assert_eq!(n, 0);
+    }
+}
+
+#[test]
+fn extra_polls_after_error_return_zero() {
+    struct FailOnce(bool);
+
+    impl AsyncRead for FailOnce {
+        fn poll_read(&mut self, _: &mut task::Context, _: &mut [u8]) -> Poll<usize, io::Error> {
+            assert!(!self.0, "polled after error");
+            self.0 = true;
+            Err(io::Error::new(io::ErrorKind::Other, "boom"))
+        }
+    }
+
+    let mut reader = FailOnce(false).fuse();
+    let mut buf = [0; 4];
+    assert!(block_on(futures::future::poll_fn(|cx| reader.poll_read(cx, &mut buf))).is_err());
+    assert_eq!(block_on(futures::future::poll_fn(|cx| reader.poll_read(cx, &mut buf))).unwrap(), 0);
+}
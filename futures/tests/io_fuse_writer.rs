@@ -0,0 +1,73 @@
+extern crate futures;
+
+use std::io;
+
+use futures::executor::block_on;
+use futures::future::poll_fn;
+use futures::io::{AsyncWrite, AsyncWriteExt};
+use futures::{Async, Poll, task};
+
+/// A writer which panics if used after being closed, and fails every write
+/// of the byte `!`.
+#[derive(Default)]
+struct Strict {
+    data: Vec<u8>,
+    closed: bool,
+}
+
+impl AsyncWrite for Strict {
+    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        assert!(!self.closed, "write after close");
+        if buf.contains(&b'!') {
+            return Err(io::Error::new(io::ErrorKind::BrokenPipe, "bang"))
+        }
+        self.data.extend_from_slice(buf);
+        Ok(Async::Ready(buf.len()))
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        assert!(!self.closed, "flush after close");
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        assert!(!self.closed, "closed twice");
+        self.closed = true;
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+fn calls_after_close_do_not_reach_writer() {
+    let mut w = Strict::default().fuse_write();
+    block_on(poll_fn(|cx| w.poll_write(cx, b"data"))).unwrap();
+    block_on(poll_fn(|cx| w.poll_close(cx))).unwrap();
+    assert!(w.is_done());
+
+    assert_eq!(block_on(poll_fn(|cx| w.poll_write(cx, b"more"))).unwrap(), 0);
+    block_on(poll_fn(|cx| w.poll_flush(cx))).unwrap();
+    block_on(poll_fn(|cx| w.poll_close(cx))).unwrap();
+    assert_eq!(w.into_inner().data, b"data");
+}
+
+#[test]
+fn errors_are_fused() {
+    let mut w = Strict::default().fuse_write();
+    let err = block_on(poll_fn(|cx| w.poll_write(cx, b"!"))).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+
+    let err = block_on(poll_fn(|cx| w.poll_write(cx, b"fine"))).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+    assert!(w.into_inner().data.is_empty());
+}
+
+#[test]
+fn duplex_objects_fuse_under_the_prelude() {
+    use futures::prelude::*;
+    use std::io::Cursor;
+
+    let reader = Cursor::new(Vec::<u8>::new()).fuse();
+    let writer = Cursor::new(Vec::<u8>::new()).fuse_write();
+    assert!(!writer.is_done());
+    drop(reader);
+}
@@ -0,0 +1,73 @@
+extern crate futures;
+
+use std::io;
+
+use futures::executor::block_on;
+use futures::io::IoVec;
+use futures::prelude::*;
+
+/// A writer which accepts at most five bytes per call, returns `Pending`
+/// every other call, and records the number of buffers in each vectored
+/// write.
+#[derive(Default)]
+struct Recorder {
+    data: Vec<u8>,
+    writes: usize,
+    vectored: Vec<usize>,
+    closed: bool,
+}
+
+impl AsyncWrite for Recorder {
+    fn poll_write(&mut self, cx: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        self.poll_vectored_write(cx, &[buf.into()])
+    }
+
+    fn poll_vectored_write(&mut self, cx: &mut task::Context, vec: &[&IoVec])
+        -> Poll<usize, io::Error>
+    {
+        self.writes += 1;
+        if self.writes % 2 == 1 {
+            cx.waker().wake();
+            return Ok(Async::Pending)
+        }
+        self.vectored.push(vec.len());
+        let mut total = 0;
+        for buf in vec {
+            let n = buf.len().min(5 - total);
+            self.data.extend_from_slice(&buf[..n]);
+            total += n;
+            if n < buf.len() {
+                break
+            }
+        }
+        Ok(Async::Ready(total))
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        self.closed = true;
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+fn writes_gathered_buffers_on_close() {
+    let mut writer = Recorder::default().gather_until_close();
+    for chunk in &[&b"one "[..], b"", b"two ", b"three"] {
+        block_on(writer.write_all(*chunk)).unwrap();
+    }
+    assert_eq!(writer.buffered(), 13);
+    assert!(writer.get_ref().data.is_empty());
+
+    block_on(writer.close()).unwrap();
+    assert_eq!(writer.buffered(), 0);
+    let inner = writer.into_inner();
+    assert_eq!(inner.data, b"one two three");
+    assert!(inner.closed);
+    // The first vectored write is handed every buffer, later ones what is
+    // left after partial writes.
+    assert_eq!(inner.vectored, [3, 2, 1]);
+}
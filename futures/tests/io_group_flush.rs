@@ -0,0 +1,175 @@
+extern crate futures;
+
+use std::io;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use futures::executor::block_on;
+use futures::future::join_all;
+use futures::io::GroupFlushWriter;
+use futures::prelude::*;
+use futures::task::{LocalMap, Wake, Waker};
+
+mod support;
+use support::*;
+
+#[derive(Default)]
+struct State {
+    data: Vec<u8>,
+    // The length of `data` when the last flush completed.
+    flushed: usize,
+}
+
+/// A writer whose flushes take one extra poll to complete, so that several
+/// tasks get a chance to join a flush in progress.
+#[derive(Clone, Default)]
+struct SlowFlush {
+    state: Arc<Mutex<State>>,
+    started: Option<usize>,
+}
+
+impl AsyncWrite for SlowFlush {
+    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        self.state.lock().unwrap().data.extend_from_slice(buf);
+        Ok(Async::Ready(buf.len()))
+    }
+
+    fn poll_flush(&mut self, cx: &mut task::Context) -> Poll<(), io::Error> {
+        let mut state = self.state.lock().unwrap();
+        match self.started.take() {
+            Some(len) => {
+                state.flushed = len;
+                Ok(Async::Ready(()))
+            }
+            None => {
+                self.started = Some(state.data.len());
+                cx.waker().wake();
+                Ok(Async::Pending)
+            }
+        }
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+fn flushes_are_shared() {
+    let inner = SlowFlush::default();
+    let state = inner.state.clone();
+    let writer = GroupFlushWriter::new(inner);
+
+    let tasks = (0..5u8).map(|i| {
+        let state = state.clone();
+        writer.write_and_flush(vec![i; 3]).map(move |()| {
+            let state = state.lock().unwrap();
+            let end = state.data.iter().rposition(|&b| b == i).unwrap() + 1;
+            assert!(state.flushed >= end, "task {} resolved before its flush", i);
+        })
+    }).collect::<Vec<_>>();
+    block_on(join_all(tasks)).unwrap();
+
+    assert_eq!(state.lock().unwrap().data.len(), 15);
+    assert_eq!(state.lock().unwrap().flushed, 15);
+    assert!(writer.flushes() < 5, "{} flushes", writer.flushes());
+}
+
+#[test]
+fn later_writes_need_a_new_flush() {
+    let inner = SlowFlush::default();
+    let state = inner.state.clone();
+    let writer = GroupFlushWriter::new(inner);
+
+    block_on(writer.write_and_flush(b"first".to_vec())).unwrap();
+    block_on(writer.write_and_flush(b"second".to_vec())).unwrap();
+    assert_eq!(state.lock().unwrap().flushed, 11);
+    assert_eq!(writer.flushes(), 2);
+}
+
+/// A writer whose writes and flushes pend, without waking the task, until
+/// the corresponding flag is set.
+#[derive(Clone, Default)]
+struct Gated {
+    writes: Arc<AtomicBool>,
+    flushes: Arc<AtomicBool>,
+}
+
+impl AsyncWrite for Gated {
+    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        if !self.writes.load(Ordering::SeqCst) {
+            return Ok(Async::Pending)
+        }
+        Ok(Async::Ready(buf.len()))
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        if !self.flushes.load(Ordering::SeqCst) {
+            return Ok(Async::Pending)
+        }
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+/// A waker counting how often it is woken.
+struct CountWakes(AtomicUsize);
+
+impl Wake for CountWakes {
+    fn wake(arc_self: &Arc<Self>) {
+        arc_self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn waiting_to_write_keeps_one_waker() {
+    let inner = Gated::default();
+    let writer = GroupFlushWriter::new(inner.clone());
+    let mut first = writer.write_and_flush(b"first".to_vec());
+    let mut second = writer.write_and_flush(b"second".to_vec());
+
+    let wakes = Arc::new(CountWakes(AtomicUsize::new(0)));
+    let waker = Waker::from(wakes.clone());
+    let mut map = LocalMap::new();
+    let mut exec = PanicExec;
+    let mut cx = task::Context::new(&mut map, &waker, &mut exec);
+
+    noop_waker_cx(|cx| assert!(first.poll(cx).unwrap().is_pending()));
+    for _ in 0..100 {
+        assert!(second.poll(&mut cx).unwrap().is_pending());
+    }
+    drop(first);
+    assert_eq!(wakes.0.load(Ordering::SeqCst), 1);
+
+    inner.writes.store(true, Ordering::SeqCst);
+    inner.flushes.store(true, Ordering::SeqCst);
+    block_on(second).unwrap();
+}
+
+#[test]
+fn waiting_for_a_flush_keeps_one_waker() {
+    let inner = Gated::default();
+    inner.writes.store(true, Ordering::SeqCst);
+    let writer = GroupFlushWriter::new(inner.clone());
+    let mut first = writer.write_and_flush(b"first".to_vec());
+
+    let wakes = Arc::new(CountWakes(AtomicUsize::new(0)));
+    let waker = Waker::from(wakes.clone());
+    let mut map = LocalMap::new();
+    let mut exec = PanicExec;
+    let mut cx = task::Context::new(&mut map, &waker, &mut exec);
+
+    for _ in 0..100 {
+        assert!(first.poll(&mut cx).unwrap().is_pending());
+    }
+    // The first poll yields before flushing, which wakes the task once.
+    assert_eq!(wakes.0.load(Ordering::SeqCst), 1);
+
+    inner.flushes.store(true, Ordering::SeqCst);
+    block_on(writer.write_and_flush(b"second".to_vec())).unwrap();
+    assert_eq!(wakes.0.load(Ordering::SeqCst), 2);
+    assert!(first.poll(&mut cx).unwrap().is_ready());
+}
@@ -0,0 +1,71 @@
+extern crate futures;
+
+use std::cell::RefCell;
+use std::io;
+
+use futures::executor::block_on;
+use futures::io::ReadOutcome;
+use futures::prelude::*;
+
+/// A reader which returns `Pending` once before yielding its data.
+struct PendOnce {
+    data: &'static [u8],
+    pended: bool,
+}
+
+impl AsyncRead for PendOnce {
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8]) -> Poll<usize, io::Error> {
+        if !self.pended {
+            self.pended = true;
+            cx.waker().wake();
+            return Ok(Async::Pending)
+        }
+        let n = self.data.len().min(buf.len());
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data = &self.data[n..];
+        Ok(Async::Ready(n))
+    }
+}
+
+#[test]
+fn hooks_see_every_outcome() {
+    let events = RefCell::new(Vec::new());
+    let mut reader = PendOnce { data: b"hello", pended: false }.hooked(
+        |_| events.borrow_mut().push("before".to_string()),
+        |outcome| events.borrow_mut().push(match outcome {
+            ReadOutcome::Ready(n) => format!("ready {}", n),
+            ReadOutcome::Pending => "pending".to_string(),
+            ReadOutcome::Err(e) => format!("error {}", e),
+        }),
+    );
+    let mut data = Vec::new();
+    block_on(reader.read_to_end(&mut data)).unwrap();
+    assert_eq!(data, b"hello");
+    assert_eq!(*events.borrow(), [
+        "before", "pending",
+        "before", "ready 5",
+        "before", "ready 0",
+    ]);
+}
+
+#[test]
+fn after_hook_sees_errors() {
+    struct Failing;
+
+    impl AsyncRead for Failing {
+        fn poll_read(&mut self, _: &mut task::Context, _: &mut [u8]) -> Poll<usize, io::Error> {
+            Err(io::Error::new(io::ErrorKind::Other, "boom"))
+        }
+    }
+
+    let mut kinds = Vec::new();
+    {
+        let mut reader = Failing.hooked(|_| {}, |outcome| {
+            if let ReadOutcome::Err(e) = outcome {
+                kinds.push(e.kind());
+            }
+        });
+        assert!(block_on(reader.read_to_end(&mut Vec::new())).is_err());
+    }
+    assert_eq!(kinds, [io::ErrorKind::Other]);
+}
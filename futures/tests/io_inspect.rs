@@ -0,0 +1,106 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+mod support;
+use support::*;
+
+/// A reader which yields the given results in turn.
+struct Script(Vec<Poll<&'static [u8], io::Error>>);
+
+impl AsyncRead for Script {
+    fn poll_read(&mut self, _: &mut task::Context, buf: &mut [u8]) -> Poll<usize, io::Error> {
+        match self.0.remove(0)? {
+            Async::Ready(data) => {
+                buf[..data.len()].copy_from_slice(data);
+                Ok(Async::Ready(data.len()))
+            }
+            Async::Pending => Ok(Async::Pending),
+        }
+    }
+}
+
+/// A writer accepting at most two bytes per write.
+struct Partial(Vec<u8>);
+
+impl AsyncWrite for Partial {
+    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        let n = buf.len().min(2);
+        self.0.extend_from_slice(&buf[..n]);
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+fn sees_only_fresh_bytes() {
+    let mut seen = Vec::new();
+    {
+        let mut reader = Script(vec![
+            Ok(Async::Ready(b"ab")),
+            Ok(Async::Pending),
+            Err(io::Error::new(io::ErrorKind::Other, "boom")),
+            Ok(Async::Ready(b"cde")),
+            Ok(Async::Ready(b"")),
+        ]).inspect(|chunk: &[u8]| seen.push(chunk.to_vec()));
+        let mut buf = [0xff; 8];
+        noop_waker_cx(|cx| {
+            assert_eq!(reader.poll_read(cx, &mut buf).unwrap(), Async::Ready(2));
+            assert_eq!(reader.poll_read(cx, &mut buf).unwrap(), Async::Pending);
+            assert!(reader.poll_read(cx, &mut buf).is_err());
+            assert_eq!(reader.poll_read(cx, &mut buf).unwrap(), Async::Ready(3));
+            assert_eq!(reader.poll_read(cx, &mut buf).unwrap(), Async::Ready(0));
+        });
+    }
+    assert_eq!(seen, vec![b"ab".to_vec(), b"cde".to_vec(), Vec::new()]);
+}
+
+#[test]
+fn sees_whole_stream() {
+    let mut seen = Vec::new();
+    {
+        let mut reader = Cursor::new(b"framed bytes".to_vec())
+            .inspect(|chunk: &[u8]| seen.extend_from_slice(chunk));
+        let mut buf = [0; 5];
+        noop_waker_cx(|cx| {
+            while reader.poll_read(cx, &mut buf).unwrap() != Async::Ready(0) {}
+        });
+    }
+    assert_eq!(seen, b"framed bytes");
+}
+
+#[test]
+fn sees_accepted_writes() {
+    let mut seen = Vec::new();
+    {
+        let mut writer = Partial(Vec::new())
+            .inspect_write(|chunk: &[u8]| seen.push(chunk.to_vec()));
+        noop_waker_cx(|cx| {
+            assert_eq!(writer.poll_write(cx, b"hello").unwrap(), Async::Ready(2));
+            assert_eq!(writer.poll_write(cx, b"llo").unwrap(), Async::Ready(2));
+        });
+        assert_eq!(writer.get_ref().0, b"hell");
+    }
+    assert_eq!(seen, vec![b"he".to_vec(), b"ll".to_vec()]);
+}
+
+#[test]
+fn duplex_objects_inspect_writes() {
+    let mut seen = Vec::new();
+    {
+        let mut writer = Cursor::new(Vec::<u8>::new())
+            .inspect_write(|chunk: &[u8]| seen.extend_from_slice(chunk));
+        block_on(writer.write_all(b"hello")).unwrap();
+    }
+    assert_eq!(seen, b"hello");
+}
@@ -0,0 +1,39 @@
+extern crate futures;
+
+mod support;
+
+use std::io::Cursor;
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+use support::*;
+
+#[test]
+fn records_the_length_of_the_stream() {
+    let data: Vec<u8> = (0..100).collect();
+    let mut reader = Cursor::new(data.clone()).inspect_eof_position();
+    assert_eq!(reader.eof_position(), None);
+    let mut out = Vec::new();
+    block_on(reader.read_to_end(&mut out)).unwrap();
+    assert_eq!(out, data);
+    assert_eq!(reader.eof_position(), Some(100));
+}
+
+#[test]
+fn empty_stream_ends_at_zero() {
+    let mut reader = Cursor::new(Vec::new()).inspect_eof_position();
+    block_on(reader.read_to_end(&mut Vec::new())).unwrap();
+    assert_eq!(reader.eof_position(), Some(0));
+}
+
+#[test]
+fn empty_buffers_are_not_eof() {
+    let mut reader = Cursor::new(vec![1, 2, 3]).inspect_eof_position();
+    noop_waker_cx(|cx| {
+        assert_eq!(reader.poll_read(cx, &mut []).unwrap(), Async::Ready(0));
+    });
+    assert_eq!(reader.eof_position(), None);
+    block_on(reader.read_to_end(&mut Vec::new())).unwrap();
+    assert_eq!(reader.eof_position(), Some(3));
+}
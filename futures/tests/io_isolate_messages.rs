@@ -0,0 +1,49 @@
+extern crate futures;
+
+use std::io;
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+#[derive(Debug, PartialEq)]
+enum Event {
+    Write(Vec<u8>),
+    Flush,
+}
+
+/// A writer recording its calls, accepting up to three bytes per write.
+#[derive(Default)]
+struct Recorder(Vec<Event>);
+
+impl AsyncWrite for Recorder {
+    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        let n = buf.len().min(3);
+        self.0.push(Event::Write(buf[..n].to_vec()));
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        self.0.push(Event::Flush);
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+fn flushes_bracket_each_message() {
+    let mut writer = Recorder::default().isolate_messages();
+    block_on(writer.send_message(b"hello".to_vec())).unwrap();
+    block_on(writer.send_message(b"hi".to_vec())).unwrap();
+    assert_eq!(writer.into_inner().0, vec![
+        Event::Flush,
+        Event::Write(b"hel".to_vec()),
+        Event::Write(b"lo".to_vec()),
+        Event::Flush,
+        Event::Flush,
+        Event::Write(b"hi".to_vec()),
+        Event::Flush,
+    ]);
+}
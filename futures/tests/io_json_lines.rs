@@ -0,0 +1,56 @@
+#![cfg(feature = "json")]
+
+extern crate futures;
+
+use std::collections::BTreeMap;
+use std::io::{self, Cursor};
+
+use futures::executor::{block_on, block_on_stream};
+use futures::io::json_lines_sink;
+use futures::prelude::*;
+
+type Object = BTreeMap<String, u32>;
+
+fn object(pairs: &[(&str, u32)]) -> Object {
+    pairs.iter().map(|&(k, v)| (k.to_string(), v)).collect()
+}
+
+#[test]
+fn decodes_lines() {
+    let data = b"{\"a\": 1}\n\n  \n{\"b\": 2, \"c\": 3}".to_vec();
+    let values = block_on_stream(Cursor::new(data).json_lines::<Object>())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(values, [object(&[("a", 1)]), object(&[("b", 2), ("c", 3)])]);
+}
+
+#[test]
+fn blank_lines_can_be_errors() {
+    let mut lines = Cursor::new(b"{\"a\": 1}\n\n".to_vec()).json_lines::<Object>();
+    lines.skip_blank_lines(false);
+    let mut lines = block_on_stream(lines);
+    assert_eq!(lines.next().unwrap().unwrap(), object(&[("a", 1)]));
+    assert_eq!(lines.next().unwrap().unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn invalid_json_is_invalid_data() {
+    let lines = Cursor::new(b"{\"a\": \"not a number\"}\n".to_vec()).json_lines::<Object>();
+    let err = block_on_stream(lines).next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn round_trips_through_sink() {
+    let items = vec![object(&[("a", 1)]), object(&[("b", 2), ("c", 3)])];
+    let sink = json_lines_sink(Cursor::new(Vec::new()));
+    let sink = block_on(sink.send(items[0].clone())).unwrap();
+    let sink = block_on(sink.send(items[1].clone())).unwrap();
+    let data = sink.into_inner().into_inner();
+    assert_eq!(data.iter().filter(|&&b| b == b'\n').count(), 2);
+
+    let values = block_on_stream(Cursor::new(data).json_lines::<Object>())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(values, items);
+}
@@ -0,0 +1,60 @@
+extern crate futures;
+
+use std::cell::{Cell, RefCell};
+use std::io::Cursor;
+use std::rc::Rc;
+use std::time::Duration;
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+mod support;
+use support::*;
+
+/// A delay which fires once the test says so.
+struct Delay(Rc<Cell<bool>>);
+
+impl Future for Delay {
+    type Item = ();
+    type Error = Never;
+
+    fn poll(&mut self, _: &mut task::Context) -> Poll<(), Never> {
+        Ok(if self.0.get() { Async::Ready(()) } else { Async::Pending })
+    }
+}
+
+#[test]
+fn keepalive_is_written_when_idle() {
+    let timers = Rc::new(RefCell::new(Vec::new()));
+    let created = timers.clone();
+    let timer = move |dur| {
+        assert_eq!(dur, Duration::from_secs(30));
+        let fired = Rc::new(Cell::new(false));
+        created.borrow_mut().push(fired.clone());
+        Delay(fired)
+    };
+    let fire_latest = || timers.borrow().last().unwrap().set(true);
+
+    let mut writer = Cursor::new(Vec::new())
+        .keepalive_write(Duration::from_secs(30), timer, b"PING\n".to_vec());
+    assert_eq!(timers.borrow().len(), 1);
+
+    // Nothing happens before the interval has passed.
+    noop_waker_cx(|cx| assert!(writer.poll_keepalive(cx).unwrap().is_pending()));
+    assert_eq!(writer.keepalives(), 0);
+
+    fire_latest();
+    noop_waker_cx(|cx| assert!(writer.poll_keepalive(cx).unwrap().is_ready()));
+    assert_eq!(writer.keepalives(), 1);
+    assert_eq!(writer.get_ref().get_ref(), b"PING\n");
+
+    // The timer is restarted after the keepalive, and again after a real
+    // write, which makes the old timer irrelevant.
+    assert_eq!(timers.borrow().len(), 2);
+    fire_latest();
+    block_on(writer.write_all(b"data\n")).unwrap();
+    assert_eq!(timers.borrow().len(), 3);
+    noop_waker_cx(|cx| assert!(writer.poll_keepalive(cx).unwrap().is_pending()));
+    assert_eq!(writer.keepalives(), 1);
+    assert_eq!(writer.get_ref().get_ref(), b"PING\ndata\n");
+}
@@ -0,0 +1,67 @@
+extern crate futures;
+
+use std::cell::Cell;
+use std::io;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use futures::io::LatencyWriter;
+use futures::prelude::*;
+
+mod support;
+use support::*;
+
+/// A writer which returns `Pending` the given number of times before
+/// accepting each buffer.
+struct Delayed {
+    delays: Vec<usize>,
+    pending: usize,
+}
+
+impl AsyncWrite for Delayed {
+    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        if self.pending < self.delays[0] {
+            self.pending += 1;
+            return Ok(Async::Pending)
+        }
+        self.delays.remove(0);
+        self.pending = 0;
+        Ok(Async::Ready(buf.len()))
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+fn records_write_latencies() {
+    let base = Instant::now();
+    let elapsed = Rc::new(Cell::new(Duration::from_millis(0)));
+    let clock = elapsed.clone();
+    let inner = Delayed { delays: vec![0, 3, 1], pending: 0 };
+    let mut w = LatencyWriter::new(inner, move || base + clock.get());
+
+    noop_waker_cx(|cx| {
+        for _ in 0..3 {
+            // Each `Pending` costs 10ms on the fake clock.
+            while w.poll_write(cx, b"data").unwrap().is_pending() {
+                elapsed.set(elapsed.get() + Duration::from_millis(10));
+            }
+        }
+    });
+
+    let ms = Duration::from_millis;
+    assert_eq!(w.samples(), [ms(0), ms(30), ms(10)]);
+    assert_eq!(w.percentiles(&[0.0, 50.0, 100.0]), [ms(0), ms(10), ms(30)]);
+}
+
+#[test]
+fn no_samples_without_writes() {
+    let w = LatencyWriter::new(io::Cursor::new(Vec::new()), Instant::now);
+    assert!(w.percentiles(&[50.0]).is_empty());
+}
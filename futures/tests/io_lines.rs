@@ -0,0 +1,55 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::executor::block_on;
+use futures::io::BufReader;
+use futures::prelude::*;
+
+fn lines(data: &[u8]) -> Vec<String> {
+    let stream = Cursor::new(data.to_vec()).lines();
+    block_on(stream.collect()).unwrap()
+}
+
+#[test]
+fn strips_line_endings() {
+    assert_eq!(lines(b"one\ntwo\r\nthree\n"), ["one", "two", "three"]);
+    assert_eq!(lines(b"a\n\nb\n"), ["a", "", "b"]);
+}
+
+#[test]
+fn yields_a_final_line_without_newline() {
+    assert_eq!(lines(b"one\ntwo"), ["one", "two"]);
+    assert!(lines(b"").is_empty());
+}
+
+/// A reader yielding its data two bytes at a time.
+struct Trickle(Vec<u8>);
+
+impl AsyncRead for Trickle {
+    fn poll_read(&mut self, _: &mut task::Context, buf: &mut [u8]) -> Poll<usize, io::Error> {
+        let n = buf.len().min(self.0.len()).min(2);
+        buf[..n].copy_from_slice(&self.0[..n]);
+        self.0.drain(..n);
+        Ok(Async::Ready(n))
+    }
+}
+
+#[test]
+fn lines_span_several_fills() {
+    let reader = BufReader::new(Trickle(b"hello world\r\nbye\n".to_vec()));
+    let lines: Vec<String> = block_on(reader.lines().collect()).unwrap();
+    assert_eq!(lines, ["hello world", "bye"]);
+}
+
+#[test]
+fn invalid_utf8_is_an_error() {
+    let mut stream = Cursor::new(b"ok\n\xff\xfe\nafter\n".to_vec()).lines();
+    let (line, rest) = block_on(stream.next()).ok().unwrap();
+    assert_eq!(line, Some("ok".to_string()));
+    stream = rest;
+    let (err, rest) = block_on(stream.next()).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    let (line, _) = block_on(rest.next()).ok().unwrap();
+    assert_eq!(line, Some("after".to_string()));
+}
@@ -0,0 +1,56 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::executor::{block_on, block_on_stream};
+use futures::io::LogWriter;
+use futures::prelude::*;
+
+fn write_log(events: &[&[u8]]) -> Vec<u8> {
+    let mut ts = 0;
+    let mut w = LogWriter::new(Cursor::new(Vec::new()), || { ts += 10; ts });
+    for event in events {
+        block_on(w.write_all(*event)).unwrap();
+    }
+    block_on(w.flush()).unwrap();
+    w.into_inner().into_inner()
+}
+
+#[test]
+fn round_trips_events() {
+    let log = write_log(&[b"one", b"", b"three"]);
+    let events: Vec<_> = block_on_stream(Cursor::new(log).dechunk_log())
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(events, [
+        (0, 10, b"one".to_vec()),
+        (1, 20, b"three".to_vec()),
+    ]);
+}
+
+#[test]
+fn detects_gaps() {
+    let log = write_log(&[b"a", b"b", b"c"]);
+    // Cut out the middle event.
+    let mut cut = log[..21].to_vec();
+    cut.extend_from_slice(&log[42..]);
+
+    let mut reader = block_on_stream(Cursor::new(cut.clone()).dechunk_log());
+    assert_eq!(reader.next().unwrap().unwrap().0, 0);
+    assert_eq!(reader.next().unwrap().unwrap().0, 2);
+    assert!(reader.next().is_none());
+    assert_eq!(reader.into_inner().gaps(), 1);
+
+    let mut strict = Cursor::new(cut).dechunk_log();
+    strict.set_error_on_gap(true);
+    let mut strict = block_on_stream(strict);
+    assert!(strict.next().unwrap().is_ok());
+    assert_eq!(strict.next().unwrap().unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn truncated_frame_is_an_error() {
+    let log = write_log(&[b"hello"]);
+    let mut reader = block_on_stream(Cursor::new(&log[..log.len() - 1]).dechunk_log());
+    assert_eq!(reader.next().unwrap().unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+}
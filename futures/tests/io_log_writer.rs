@@ -0,0 +1,59 @@
+extern crate futures;
+
+use std::cell::Cell;
+use std::io;
+
+use futures::executor::block_on;
+use futures::io::LogWriter;
+use futures::prelude::*;
+
+/// A writer which accepts at most three bytes per call.
+struct Slow(Vec<u8>);
+
+impl AsyncWrite for Slow {
+    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        let n = buf.len().min(3);
+        self.0.extend_from_slice(&buf[..n]);
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+fn be(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0, |n, &b| (n << 8) | b as u64)
+}
+
+#[test]
+fn frames_events() {
+    let clock = Cell::new(1000);
+    let now = || {
+        clock.set(clock.get() + 5);
+        clock.get()
+    };
+    let mut w = LogWriter::with_sequence(Slow(Vec::new()), 7, now);
+    block_on(w.write_all(b"hello")).unwrap();
+    block_on(w.write_all(b"world!")).unwrap();
+    block_on(w.flush()).unwrap();
+    assert_eq!(w.next_sequence(), 9);
+
+    let out = w.into_inner().0;
+    assert_eq!(out.len(), 20 + 5 + 20 + 6);
+
+    let (first, second) = out.split_at(25);
+    assert_eq!(be(&first[..8]), 7);
+    assert_eq!(be(&first[8..16]), 1005);
+    assert_eq!(be(&first[16..20]), 5);
+    assert_eq!(&first[20..], b"hello");
+
+    assert_eq!(be(&second[..8]), 8);
+    assert_eq!(be(&second[8..16]), 1010);
+    assert_eq!(be(&second[16..20]), 6);
+    assert_eq!(&second[20..], b"world!");
+}
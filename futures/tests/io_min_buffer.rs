@@ -0,0 +1,22 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+#[test]
+fn small_buffer_is_rejected() {
+    let mut reader = Cursor::new(b"hello".to_vec()).require_min_buffer(4);
+    let err = block_on(reader.read(&mut [0; 3])).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn large_enough_buffer_is_read_into() {
+    let mut reader = Cursor::new(b"hello".to_vec()).require_min_buffer(4);
+    let mut buf = [0; 4];
+    let n = block_on(reader.read(&mut buf)).unwrap();
+    assert_eq!(&buf[..n], b"hell");
+    assert_eq!(reader.min(), 4);
+}
@@ -0,0 +1,98 @@
+extern crate futures;
+
+use std::cell::Cell;
+use std::io;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use futures::future;
+use futures::prelude::*;
+
+mod support;
+use support::*;
+
+/// A reader producing one byte per read, advancing a fake clock each time.
+struct Ticking {
+    left: usize,
+    elapsed: Rc<Cell<Duration>>,
+    per_read: Duration,
+}
+
+impl AsyncRead for Ticking {
+    fn poll_read(&mut self, _: &mut task::Context, buf: &mut [u8]) -> Poll<usize, io::Error> {
+        self.elapsed.set(self.elapsed.get() + self.per_read);
+        if self.left == 0 {
+            return Ok(Async::Ready(0))
+        }
+        self.left -= 1;
+        buf[0] = b'x';
+        Ok(Async::Ready(1))
+    }
+}
+
+fn drain(per_read: Duration) -> Result<usize, io::Error> {
+    let base = Instant::now();
+    let elapsed = Rc::new(Cell::new(Duration::from_millis(0)));
+    let clock = elapsed.clone();
+    let reader = Ticking { left: 40, elapsed, per_read };
+    let mut reader = reader.min_throughput(10, Duration::from_secs(1), move || base + clock.get(),
+                                           |_| future::empty());
+
+    let mut total = 0;
+    noop_waker_cx(|cx| {
+        let mut buf = [0; 8];
+        loop {
+            match reader.poll_read(cx, &mut buf)? {
+                Async::Ready(0) => return Ok(total),
+                Async::Ready(n) => total += n,
+                Async::Pending => unreachable!(),
+            }
+        }
+    })
+}
+
+#[test]
+fn fast_enough_reader_passes() {
+    // 20 bytes per second
+    assert_eq!(drain(Duration::from_millis(50)).unwrap(), 40);
+}
+
+#[test]
+fn slow_reader_times_out() {
+    // 2 bytes per second
+    let err = drain(Duration::from_millis(500)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+}
+
+struct Stalled;
+
+impl AsyncRead for Stalled {
+    fn poll_read(&mut self, _: &mut task::Context, _: &mut [u8]) -> Poll<usize, io::Error> {
+        Ok(Async::Pending)
+    }
+}
+
+#[test]
+fn stalled_reader_times_out() {
+    let base = Instant::now();
+    let armed = Rc::new(Cell::new(None));
+    let fired = Rc::new(Cell::new(false));
+    let (armed2, fired2) = (armed.clone(), fired.clone());
+    let mut reader = Stalled.min_throughput(10, Duration::from_secs(1), move || base,
+                                            move |dur| {
+        armed2.set(Some(dur));
+        let fired = fired2.clone();
+        future::poll_fn(move |_| {
+            Ok(if fired.get() { Async::Ready(()) } else { Async::Pending })
+        })
+    });
+
+    noop_waker_cx(|cx| {
+        let mut buf = [0; 8];
+        assert!(reader.poll_read(cx, &mut buf).unwrap().is_pending());
+        assert_eq!(armed.get(), Some(Duration::from_secs(1)));
+        fired.set(true);
+        let err = reader.poll_read(cx, &mut buf).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+    });
+}
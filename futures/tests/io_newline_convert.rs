@@ -0,0 +1,66 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+fn convert(chunks: &[&[u8]], to_crlf: bool) -> Vec<u8> {
+    let mut writer = Cursor::new(Vec::new()).newline_convert_write(to_crlf);
+    for chunk in chunks {
+        block_on(writer.write_all(chunk)).unwrap();
+    }
+    block_on(writer.close()).unwrap();
+    writer.into_inner().into_inner()
+}
+
+#[test]
+fn lf_to_crlf() {
+    assert_eq!(convert(&[b"a\nb\n"], true), b"a\r\nb\r\n");
+    assert_eq!(convert(&[b"a\n", b"b", b"\n"], true), b"a\r\nb\r\n");
+}
+
+#[test]
+fn existing_crlf_is_kept_across_chunks() {
+    assert_eq!(convert(&[b"a\r", b"\nb\n"], true), b"a\r\nb\r\n");
+    assert_eq!(convert(&[b"a\r", b"b"], true), b"a\rb");
+}
+
+#[test]
+fn crlf_to_lf() {
+    assert_eq!(convert(&[b"a\r\nb\r\n"], false), b"a\nb\n");
+    assert_eq!(convert(&[b"a\r", b"\nb\r", b"\n"], false), b"a\nb\n");
+    assert_eq!(convert(&[b"a\r", b"\r", b"\n"], false), b"a\r\n");
+    assert_eq!(convert(&[b"a\rb\n"], false), b"a\rb\n");
+}
+
+#[test]
+fn trailing_cr_is_written_on_close() {
+    assert_eq!(convert(&[b"a\r"], false), b"a\r");
+}
+
+/// A writer accepting one byte per call.
+struct OneByte(Vec<u8>);
+
+impl AsyncWrite for OneByte {
+    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        self.0.push(buf[0]);
+        Ok(Async::Ready(1))
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+fn reports_input_bytes_consumed() {
+    let mut writer = OneByte(Vec::new()).newline_convert_write(true);
+    block_on(writer.write_all(b"a\nb\n")).unwrap();
+    block_on(writer.flush()).unwrap();
+    assert_eq!(writer.get_ref().0, b"a\r\nb\r\n");
+}
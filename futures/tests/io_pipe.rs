@@ -0,0 +1,68 @@
+extern crate futures;
+
+use std::io;
+use std::thread;
+
+use futures::executor::block_on;
+use futures::io::pipe;
+use futures::prelude::*;
+
+mod support;
+
+use support::*;
+
+#[test]
+fn reader_sees_written_bytes_then_eof() {
+    let (mut reader, mut writer) = pipe();
+    block_on(writer.write_all(b"hello")).unwrap();
+    drop(writer);
+    let mut data = Vec::new();
+    block_on(reader.read_to_end(&mut data)).unwrap();
+    assert_eq!(data, b"hello");
+}
+
+#[test]
+fn transfers_more_than_the_buffer() {
+    let (mut reader, writer) = pipe();
+    let data: Vec<u8> = (0..100_000).map(|i| i as u8).collect();
+    let sent = data.clone();
+    let t = thread::spawn(move || {
+        let mut writer = writer;
+        block_on(writer.write_all(&sent)).unwrap();
+        block_on(writer.close()).unwrap();
+    });
+    let mut out = Vec::new();
+    block_on(reader.read_to_end(&mut out)).unwrap();
+    assert_eq!(out, data);
+    t.join().unwrap();
+}
+
+#[test]
+fn pends_while_full_or_empty() {
+    let (mut reader, mut writer) = pipe();
+    let mut buf = [0; 16];
+    noop_waker_cx(|cx| {
+        assert_eq!(reader.poll_read(cx, &mut buf).unwrap(), Async::Pending);
+
+        let data = vec![7; 10_000];
+        let n = match writer.poll_write(cx, &data).unwrap() {
+            Async::Ready(n) => n,
+            Async::Pending => panic!("an empty pipe pended"),
+        };
+        assert!(n < data.len());
+        assert_eq!(writer.poll_write(cx, &data[n..]).unwrap(), Async::Pending);
+
+        assert_eq!(reader.poll_read(cx, &mut buf).unwrap(), Async::Ready(16));
+        assert_eq!(writer.poll_write(cx, &data[n..]).unwrap(), Async::Ready(16));
+    });
+}
+
+#[test]
+fn dropped_reader_breaks_the_pipe() {
+    let (reader, mut writer) = pipe();
+    drop(reader);
+    noop_waker_cx(|cx| {
+        let err = writer.poll_write(cx, b"data").err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::BrokenPipe);
+    });
+}
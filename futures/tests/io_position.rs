@@ -0,0 +1,55 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::executor::block_on;
+use futures::io::{PositionReader, PositionWriter};
+use futures::prelude::*;
+
+#[test]
+fn reader_counts_bytes_read() {
+    let mut reader = PositionReader::new(Cursor::new(b"0123456789".to_vec()));
+    assert_eq!(reader.position(), 0);
+    block_on(reader.read_exact(&mut [0; 3])).unwrap();
+    assert_eq!(reader.position(), 3);
+    let mut buf = [0; 4];
+    let n = block_on(reader.read(&mut buf)).unwrap();
+    assert_eq!(&buf[..n], b"3456");
+    assert_eq!(reader.position(), 7);
+
+    let mut rest = Vec::new();
+    block_on(reader.read_to_end(&mut rest)).unwrap();
+    assert_eq!(rest, b"789");
+    assert_eq!(reader.position(), 10);
+}
+
+/// A writer which accepts at most three bytes per write.
+struct Short(Vec<u8>);
+
+impl AsyncWrite for Short {
+    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        let n = buf.len().min(3);
+        self.0.extend_from_slice(&buf[..n]);
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+fn writer_counts_accepted_bytes() {
+    let mut writer = PositionWriter::new(Short(Vec::new()));
+    let n = block_on(futures::future::poll_fn(|cx| writer.poll_write(cx, b"abcdef"))).unwrap();
+    assert_eq!(n, 3);
+    assert_eq!(writer.position(), 3);
+
+    block_on(writer.write_all(b"ghijklm")).unwrap();
+    assert_eq!(writer.position(), 10);
+    assert_eq!(writer.into_inner().0, b"abcghijklm");
+}
@@ -0,0 +1,33 @@
+extern crate futures;
+
+use std::io::Cursor;
+
+use futures::executor::block_on;
+use futures::io::{Quota, QuotaReader};
+use futures::prelude::*;
+
+#[test]
+fn readers_share_the_quota() {
+    let quota = Quota::new(150);
+    let mut first = QuotaReader::new(Cursor::new(vec![1; 100]), quota.clone());
+    let mut second = QuotaReader::new(Cursor::new(vec![2; 100]), quota.clone());
+
+    let mut a = Vec::new();
+    block_on(first.read_to_end(&mut a)).unwrap();
+    let mut b = Vec::new();
+    block_on(second.read_to_end(&mut b)).unwrap();
+    assert_eq!(a, vec![1; 100]);
+    assert_eq!(b, vec![2; 50]);
+    assert_eq!(quota.remaining(), 0);
+    assert_eq!(second.get_ref().position(), 50);
+}
+
+#[test]
+fn short_reads_only_take_what_was_read() {
+    let quota = Quota::new(100);
+    let mut reader = QuotaReader::new(Cursor::new(vec![0; 30]), quota.clone());
+    let mut data = Vec::new();
+    block_on(reader.read_to_end(&mut data)).unwrap();
+    assert_eq!(data.len(), 30);
+    assert_eq!(quota.remaining(), 70);
+}
@@ -0,0 +1,33 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+#[test]
+fn reads_into_a_borrowed_buffer() {
+    let mut reader = Cursor::new(b"hello world".to_vec());
+    let mut buf = [0; 5];
+    let n = block_on(reader.read(&mut buf)).unwrap();
+    assert_eq!(n, 5);
+    assert_eq!(&buf, b"hello");
+
+    let n = block_on(reader.read(&mut buf)).unwrap();
+    assert_eq!(&buf[..n], b" worl");
+}
+
+/// A reader which is never ready.
+struct Stalled;
+
+impl AsyncRead for Stalled {
+    fn poll_read(&mut self, _: &mut task::Context, _: &mut [u8]) -> Poll<usize, io::Error> {
+        Ok(Async::Pending)
+    }
+}
+
+#[test]
+fn empty_buffer_completes_immediately() {
+    let n = block_on(Stalled.read(&mut [])).unwrap();
+    assert_eq!(n, 0);
+}
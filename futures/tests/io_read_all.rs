@@ -0,0 +1,26 @@
+extern crate futures;
+
+use std::io::{Cursor, Seek, SeekFrom};
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+#[test]
+fn reclaims_the_reader() {
+    let reader = Cursor::new(b"body".to_vec());
+    let (mut reader, data) = block_on(reader.read_all()).unwrap();
+    assert_eq!(data, b"body");
+
+    reader.seek(SeekFrom::Start(1)).unwrap();
+    let (_, data) = block_on(reader.read_all()).unwrap();
+    assert_eq!(data, b"ody");
+}
+
+#[test]
+fn future_is_static() {
+    fn assert_static<T: 'static>(_: &T) {}
+
+    let fut = Cursor::new(vec![1, 2, 3]).read_all();
+    assert_static(&fut);
+    assert_eq!(block_on(fut).unwrap().1, [1, 2, 3]);
+}
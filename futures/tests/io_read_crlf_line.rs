@@ -0,0 +1,54 @@
+extern crate futures;
+
+use std::io;
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+/// A reader which yields its data in fixed chunks.
+struct Chunks(Vec<&'static [u8]>);
+
+impl AsyncRead for Chunks {
+    fn poll_read(&mut self, _: &mut task::Context, buf: &mut [u8]) -> Poll<usize, io::Error> {
+        if self.0.is_empty() {
+            return Ok(Async::Ready(0))
+        }
+        let n = self.0[0].len().min(buf.len());
+        buf[..n].copy_from_slice(&self.0[0][..n]);
+        self.0[0] = &self.0[0][n..];
+        if self.0[0].is_empty() {
+            self.0.remove(0);
+        }
+        Ok(Async::Ready(n))
+    }
+}
+
+#[test]
+fn reads_crlf_lines() {
+    let mut reader = Chunks(vec![b"Host: a\r", b"\nAccept: */*\r\n\r\n"]);
+    let mut line = Vec::new();
+    assert_eq!(block_on(reader.read_crlf_line(&mut line)).unwrap(), 9);
+    assert_eq!(line, b"Host: a");
+
+    line.clear();
+    assert_eq!(block_on(reader.read_crlf_line(&mut line)).unwrap(), 13);
+    assert_eq!(line, b"Accept: */*");
+
+    line.clear();
+    assert_eq!(block_on(reader.read_crlf_line(&mut line)).unwrap(), 2);
+    assert!(line.is_empty());
+    assert_eq!(block_on(reader.read_crlf_line(&mut line)).unwrap(), 0);
+}
+
+#[test]
+fn rejects_bare_terminators() {
+    let mut line = Vec::new();
+    let err = block_on(Chunks(vec![b"Host: a\n"]).read_crlf_line(&mut line)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    let err = block_on(Chunks(vec![b"a\rb\r\n"]).read_crlf_line(&mut line)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+    let err = block_on(Chunks(vec![b"partial"]).read_crlf_line(&mut line)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
@@ -0,0 +1,48 @@
+extern crate futures;
+
+use std::io;
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+/// A reader yielding two bytes per read, returning `Pending` before each.
+struct Trickle {
+    data: Vec<u8>,
+    ready: bool,
+}
+
+impl Trickle {
+    fn new(data: &[u8]) -> Trickle {
+        Trickle { data: data.to_vec(), ready: false }
+    }
+}
+
+impl AsyncRead for Trickle {
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8]) -> Poll<usize, io::Error> {
+        if !self.ready {
+            self.ready = true;
+            cx.waker().wake();
+            return Ok(Async::Pending)
+        }
+        self.ready = false;
+        let n = buf.len().min(self.data.len()).min(2);
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data.drain(..n);
+        Ok(Async::Ready(n))
+    }
+}
+
+#[test]
+fn fills_the_buffer_across_partial_and_pending_reads() {
+    let mut reader = Trickle::new(b"hello world");
+    let mut buf = [0; 7];
+    block_on(reader.read_exact(&mut buf)).unwrap();
+    assert_eq!(&buf, b"hello w");
+    assert_eq!(reader.data, b"orld");
+}
+
+#[test]
+fn early_eof_is_an_error() {
+    let err = block_on(Trickle::new(b"abc").read_exact(&mut [0; 4])).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
@@ -0,0 +1,21 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+#[test]
+fn reads_fixed_size_records() {
+    let mut reader = Cursor::new(b"aaaabbbbccccdd".to_vec());
+    let records = block_on(reader.read_n_records(3, 4)).unwrap();
+    assert_eq!(records, [b"aaaa", b"bbbb", b"cccc"]);
+    assert_eq!(reader.position(), 12);
+}
+
+#[test]
+fn short_stream_is_an_error() {
+    let mut reader = Cursor::new(b"aaaabb".to_vec());
+    let err = block_on(reader.read_n_records(2, 4)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
@@ -0,0 +1,41 @@
+extern crate futures;
+
+use std::io;
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+/// A reader yielding its data five bytes at a time, returning `Pending`
+/// before each read, which checks that the buffers it is handed have been
+/// zeroed as its default `initializer` requires.
+struct Trickle {
+    data: Vec<u8>,
+    ready: bool,
+}
+
+impl AsyncRead for Trickle {
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8]) -> Poll<usize, io::Error> {
+        assert!(buf.iter().all(|&b| b == 0));
+        if !self.ready {
+            self.ready = true;
+            cx.waker().wake();
+            return Ok(Async::Pending)
+        }
+        self.ready = false;
+        let n = buf.len().min(self.data.len()).min(5);
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data.drain(..n);
+        Ok(Async::Ready(n))
+    }
+}
+
+#[test]
+fn pending_mid_stream_keeps_the_bytes_read() {
+    let data: Vec<u8> = (1..201).collect();
+    let mut reader = Trickle { data: data.clone(), ready: false };
+    let mut out = b"prefix".to_vec();
+    let n = block_on(reader.read_to_end(&mut out)).unwrap();
+    assert_eq!(n, data.len());
+    assert_eq!(&out[..6], b"prefix");
+    assert_eq!(&out[6..], &data[..]);
+}
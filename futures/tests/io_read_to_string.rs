@@ -0,0 +1,24 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+#[test]
+fn appends_valid_utf8() {
+    let mut reader = Cursor::new("grüße".as_bytes().to_vec());
+    let mut s = String::from("hallo, ");
+    let n = block_on(reader.read_to_string(&mut s)).unwrap();
+    assert_eq!(n, 7);
+    assert_eq!(s, "hallo, grüße");
+}
+
+#[test]
+fn invalid_utf8_leaves_the_string_untouched() {
+    let mut reader = Cursor::new(b"ok \xff\xfe".to_vec());
+    let mut s = String::from("before");
+    let err = block_on(reader.read_to_string(&mut s)).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(s, "before");
+}
@@ -0,0 +1,41 @@
+extern crate futures;
+
+use std::io::Cursor;
+
+use futures::executor::block_on;
+use futures::io::BufReader;
+use futures::prelude::*;
+
+#[test]
+fn reads_up_to_and_including_the_delimiter() {
+    let mut reader = Cursor::new(b"key:value;next".to_vec());
+    let mut buf = Vec::new();
+    assert_eq!(block_on(reader.read_until(b':', &mut buf)).unwrap(), 4);
+    assert_eq!(buf, b"key:");
+    assert_eq!(reader.position(), 4);
+
+    assert_eq!(block_on(reader.read_until(b';', &mut buf)).unwrap(), 6);
+    assert_eq!(buf, b"key:value;");
+}
+
+#[test]
+fn eof_before_the_delimiter_is_not_an_error() {
+    let mut reader = Cursor::new(b"no delimiter".to_vec());
+    let mut buf = Vec::new();
+    assert_eq!(block_on(reader.read_until(b'\n', &mut buf)).unwrap(), 12);
+    assert_eq!(buf, b"no delimiter");
+    assert_eq!(block_on(reader.read_until(b'\n', &mut buf)).unwrap(), 0);
+    assert_eq!(buf, b"no delimiter");
+}
+
+#[test]
+fn delimiter_across_buffer_fills() {
+    let mut reader = BufReader::with_capacity(3, Cursor::new(b"abcdefgh|ij".to_vec()));
+    let mut buf = Vec::new();
+    assert_eq!(block_on(reader.read_until(b'|', &mut buf)).unwrap(), 9);
+    assert_eq!(buf, b"abcdefgh|");
+
+    let mut rest = Vec::new();
+    assert_eq!(block_on(reader.read_until(b'|', &mut rest)).unwrap(), 2);
+    assert_eq!(rest, b"ij");
+}
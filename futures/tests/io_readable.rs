@@ -0,0 +1,42 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::channel::mpsc;
+use futures::io::ReorderReader;
+use futures::prelude::*;
+
+mod support;
+use support::*;
+
+#[test]
+fn readable_waits_for_data() {
+    let (tx, rx) = mpsc::unbounded();
+    let rx = rx.map_err(|e| -> io::Error { e.never_into() });
+    let mut reader = ReorderReader::new(rx);
+
+    noop_waker_cx(|cx| {
+        assert!(reader.readable().poll(cx).unwrap().is_pending());
+
+        // A segment which isn't next in order doesn't make it readable.
+        tx.unbounded_send((2, b"cd".to_vec())).unwrap();
+        assert!(reader.readable().poll(cx).unwrap().is_pending());
+
+        tx.unbounded_send((0, b"ab".to_vec())).unwrap();
+        assert!(reader.readable().poll(cx).unwrap().is_ready());
+
+        // Nothing was consumed.
+        let mut buf = [0; 4];
+        assert_eq!(reader.poll_read(cx, &mut buf).unwrap(), Async::Ready(2));
+        assert_eq!(&buf[..2], b"ab");
+    });
+}
+
+#[test]
+fn in_memory_readers_are_always_readable() {
+    let mut cursor = Cursor::new(vec![1, 2, 3]);
+    noop_waker_cx(|cx| {
+        assert!(cursor.readable().poll(cx).unwrap().is_ready());
+    });
+    assert_eq!(cursor.position(), 0);
+}
@@ -0,0 +1,59 @@
+extern crate futures;
+
+use std::io;
+
+use futures::channel::mpsc;
+use futures::executor::block_on;
+use futures::io::ReorderReader;
+use futures::prelude::*;
+use futures::stream::iter_ok;
+
+mod support;
+use support::*;
+
+#[test]
+fn reassembles_in_offset_order() {
+    let segments = vec![
+        (3, b"def".to_vec()),
+        (0, b"abc".to_vec()),
+        (1, b"bcd".to_vec()),
+        (6, b"g".to_vec()),
+        (3, b"de".to_vec()),
+    ];
+    let mut reader = ReorderReader::new(iter_ok::<_, io::Error>(segments));
+    let mut data = Vec::new();
+    block_on(reader.read_to_end(&mut data)).unwrap();
+    assert_eq!(data, b"abcdefg");
+}
+
+#[test]
+fn pending_until_next_segment_arrives() {
+    let (tx, rx) = mpsc::unbounded();
+    let rx = rx.map_err(|e| -> io::Error { e.never_into() });
+    let mut reader = ReorderReader::new(rx);
+    let mut buf = [0; 8];
+
+    tx.unbounded_send((2, b"cd".to_vec())).unwrap();
+    noop_waker_cx(|cx| {
+        assert!(reader.poll_read(cx, &mut buf).unwrap().is_pending());
+    });
+
+    tx.unbounded_send((0, b"ab".to_vec())).unwrap();
+    drop(tx);
+    noop_waker_cx(|cx| {
+        assert_eq!(reader.poll_read(cx, &mut buf).unwrap(), Async::Ready(2));
+        assert_eq!(&buf[..2], b"ab");
+        assert_eq!(reader.poll_read(cx, &mut buf).unwrap(), Async::Ready(2));
+        assert_eq!(&buf[..2], b"cd");
+        assert_eq!(reader.poll_read(cx, &mut buf).unwrap(), Async::Ready(0));
+    });
+    assert_eq!(reader.position(), 4);
+}
+
+#[test]
+fn gap_at_end_is_an_error() {
+    let segments = vec![(2, b"cd".to_vec())];
+    let mut reader = ReorderReader::new(iter_ok::<_, io::Error>(segments));
+    let err = block_on(reader.read_to_end(&mut Vec::new())).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
@@ -0,0 +1,36 @@
+extern crate futures;
+
+use std::io::Cursor;
+
+use futures::executor::block_on;
+use futures::io::Replayable;
+use futures::prelude::*;
+
+#[test]
+fn replay_yields_the_same_bytes() {
+    let data: Vec<u8> = (0..20_000).map(|i| (i % 251) as u8).collect();
+    let mut first = Replayable::new(Cursor::new(data.clone()));
+    let mut a = Vec::new();
+    block_on(first.read_to_end(&mut a)).unwrap();
+    assert_eq!(a, data);
+    assert_eq!(first.cached(), data.len());
+
+    let mut b = Vec::new();
+    block_on(first.replay().read_to_end(&mut b)).unwrap();
+    assert_eq!(b, data);
+}
+
+#[test]
+fn replay_before_the_first_pass_finishes() {
+    let mut first = Replayable::new(Cursor::new(b"hello world".to_vec()));
+    block_on(first.read_exact(&mut [0; 5])).unwrap();
+    let mut replay = first.replay();
+
+    let mut b = Vec::new();
+    block_on(replay.read_to_end(&mut b)).unwrap();
+    assert_eq!(b, b"hello world");
+
+    let mut rest = Vec::new();
+    block_on(first.read_to_end(&mut rest)).unwrap();
+    assert_eq!(rest, b" world");
+}
@@ -0,0 +1,35 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::executor::block_on_stream;
+use futures::prelude::*;
+
+/// Decodes frames of a status byte, a length byte and a payload, where a
+/// non-zero status marks an error frame.
+fn decode(buf: &mut Vec<u8>) -> Option<Result<String, u8>> {
+    if buf.len() < 2 || buf.len() < 2 + buf[1] as usize {
+        return None
+    }
+    let frame = buf.drain(..2 + buf[1] as usize).collect::<Vec<_>>();
+    Some(match frame[0] {
+        0 => Ok(String::from_utf8(frame[2..].to_vec()).unwrap()),
+        status => Err(status),
+    })
+}
+
+#[test]
+fn yields_app_errors_as_items() {
+    let data = b"\x00\x02ok\x07\x00\x00\x04more".to_vec();
+    let frames = Cursor::new(data).result_frames(decode);
+    let frames = block_on_stream(frames).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(frames, [Ok("ok".to_string()), Err(7), Ok("more".to_string())]);
+}
+
+#[test]
+fn truncated_frame_is_an_error() {
+    let frames = Cursor::new(b"\x00\x05abc".to_vec()).result_frames(decode);
+    let mut frames = block_on_stream(frames);
+    let err = frames.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
@@ -0,0 +1,90 @@
+extern crate futures;
+
+use std::cell::RefCell;
+use std::io;
+use std::rc::Rc;
+use std::time::Duration;
+
+use futures::executor::block_on;
+use futures::io::RetryPolicy;
+use futures::prelude::*;
+
+/// A reader failing `failures` times with the given error kind before
+/// yielding its data.
+struct Flaky {
+    failures: usize,
+    kind: io::ErrorKind,
+    data: &'static [u8],
+}
+
+impl AsyncRead for Flaky {
+    fn poll_read(&mut self, _: &mut task::Context, buf: &mut [u8]) -> Poll<usize, io::Error> {
+        if self.failures > 0 {
+            self.failures -= 1;
+            return Err(io::Error::new(self.kind, "flaky"))
+        }
+        let n = buf.len().min(self.data.len());
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data = &self.data[n..];
+        Ok(Async::Ready(n))
+    }
+}
+
+/// A delay which is pending once, waking the task, before completing.
+struct Delay(bool);
+
+impl Future for Delay {
+    type Item = ();
+    type Error = Never;
+
+    fn poll(&mut self, cx: &mut task::Context) -> Poll<(), Never> {
+        if self.0 {
+            return Ok(Async::Ready(()))
+        }
+        self.0 = true;
+        cx.waker().wake();
+        Ok(Async::Pending)
+    }
+}
+
+#[test]
+fn retries_transient_errors() {
+    let delays = Rc::new(RefCell::new(Vec::new()));
+    let delays2 = delays.clone();
+    let reader = Flaky { failures: 2, kind: io::ErrorKind::ConnectionReset, data: b"payload" };
+    let mut reader = reader.retry_read(RetryPolicy::new(3, Duration::from_millis(10)), move |d| {
+        delays2.borrow_mut().push(d);
+        Delay(false)
+    });
+
+    let mut data = Vec::new();
+    block_on(reader.read_to_end(&mut data)).unwrap();
+    assert_eq!(data, b"payload");
+    assert_eq!(*delays.borrow(), [Duration::from_millis(10), Duration::from_millis(20)]);
+}
+
+#[test]
+fn exhausted_retries_propagate_the_error() {
+    let reader = Flaky { failures: 3, kind: io::ErrorKind::TimedOut, data: b"" };
+    let mut reader = reader.retry_read(RetryPolicy::new(2, Duration::from_millis(1)),
+                                   |_| Delay(false));
+    let err = block_on(reader.read_to_end(&mut Vec::new())).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+}
+
+#[test]
+fn other_errors_are_not_retried() {
+    let reader = Flaky { failures: 1, kind: io::ErrorKind::InvalidData, data: b"" };
+    let mut reader = reader.retry_read(RetryPolicy::new(2, Duration::from_millis(1)),
+                                   |_| -> Delay { panic!("should not retry") });
+    let err = block_on(reader.read_to_end(&mut Vec::new())).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn backoff_doubles_and_saturates() {
+    let policy = RetryPolicy::new(1000, Duration::from_millis(10));
+    assert_eq!(policy.backoff(1), Duration::from_millis(10));
+    assert_eq!(policy.backoff(3), Duration::from_millis(40));
+    assert_eq!(policy.backoff(1000), Duration::new(u64::max_value(), 999_999_999));
+}
@@ -0,0 +1,59 @@
+extern crate futures;
+
+use std::cell::RefCell;
+use std::io;
+use std::rc::Rc;
+
+use futures::executor::block_on;
+use futures::io::RotatingWriter;
+use futures::prelude::*;
+
+/// A writer recording its contents and whether it was closed.
+struct Log {
+    data: Rc<RefCell<Vec<u8>>>,
+    closed: Rc<RefCell<bool>>,
+}
+
+impl AsyncWrite for Log {
+    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        assert!(!*self.closed.borrow(), "write after close");
+        self.data.borrow_mut().extend_from_slice(buf);
+        Ok(Async::Ready(buf.len()))
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        *self.closed.borrow_mut() = true;
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+fn rotates_at_limit() {
+    let files = Rc::new(RefCell::new(Vec::new()));
+    let files2 = files.clone();
+    let mut w = RotatingWriter::new(4, move || {
+        let log = Log {
+            data: Rc::new(RefCell::new(Vec::new())),
+            closed: Rc::new(RefCell::new(false)),
+        };
+        files2.borrow_mut().push((log.data.clone(), log.closed.clone()));
+        log
+    });
+
+    block_on(w.write_all(b"abc")).unwrap();
+    assert_eq!(files.borrow().len(), 1);
+
+    // This write crosses two rotation boundaries.
+    block_on(w.write_all(b"defghij")).unwrap();
+    block_on(w.close()).unwrap();
+
+    let files = files.borrow();
+    let contents: Vec<Vec<u8>> = files.iter().map(|f| f.0.borrow().clone()).collect();
+    assert_eq!(contents, [b"abcd".to_vec(), b"efgh".to_vec(), b"ij".to_vec()]);
+    assert!(files.iter().all(|f| *f.1.borrow()));
+    assert_eq!(w.written(), 2);
+}
@@ -0,0 +1,58 @@
+extern crate futures;
+
+use std::io::{self, Cursor, SeekFrom};
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+mod support;
+
+use support::*;
+
+#[test]
+fn cursor_seeks_to_record_offsets() {
+    let mut cursor = Cursor::new(b"0123456789".to_vec());
+    noop_waker_cx(|cx| {
+        assert_eq!(cursor.poll_seek(cx, SeekFrom::Start(4)).unwrap(), Async::Ready(4));
+        assert_eq!(cursor.poll_seek(cx, SeekFrom::Current(2)).unwrap(), Async::Ready(6));
+        assert_eq!(cursor.poll_seek(cx, SeekFrom::End(-3)).unwrap(), Async::Ready(7));
+    });
+    let mut data = Vec::new();
+    block_on(cursor.read_to_end(&mut data)).unwrap();
+    assert_eq!(data, b"789");
+}
+
+#[test]
+fn seeking_past_the_end_reads_eof() {
+    let mut cursor = Cursor::new(b"abc".to_vec());
+    noop_waker_cx(|cx| {
+        assert_eq!(cursor.poll_seek(cx, SeekFrom::Start(10)).unwrap(), Async::Ready(10));
+    });
+    let mut data = Vec::new();
+    block_on(cursor.read_to_end(&mut data)).unwrap();
+    assert!(data.is_empty());
+}
+
+#[test]
+fn seeking_before_the_start_is_an_error() {
+    let mut cursor = Cursor::new(b"abc".to_vec());
+    noop_waker_cx(|cx| {
+        let err = cursor.poll_seek(cx, SeekFrom::Current(-1)).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    });
+}
+
+#[test]
+fn boxed_and_borrowed_seekers() {
+    let mut cursor = Cursor::new(vec![0; 8]);
+    noop_waker_cx(|cx| {
+        {
+            let mut borrowed = &mut cursor;
+            let res = AsyncSeek::poll_seek(&mut borrowed, cx, SeekFrom::Start(3));
+            assert_eq!(res.unwrap(), Async::Ready(3));
+        }
+        let mut boxed = Box::new(cursor);
+        let res = AsyncSeek::poll_seek(&mut boxed, cx, SeekFrom::Current(1));
+        assert_eq!(res.unwrap(), Async::Ready(4));
+    });
+}
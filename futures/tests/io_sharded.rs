@@ -0,0 +1,38 @@
+extern crate futures;
+
+use std::io::Cursor;
+
+use futures::executor::block_on;
+use futures::future::poll_fn;
+use futures::io::sharded_write;
+
+fn key_sum(key: &[u8]) -> u64 {
+    key.iter().map(|&b| b as u64).sum()
+}
+
+#[test]
+fn routes_payloads_by_key() {
+    let backends = vec![Cursor::new(Vec::new()); 3];
+    let mut writer = sharded_write(backends, key_sum);
+
+    let sends: &[(&[u8], &[u8])] = &[
+        (b"a", b"1"), (b"b", b"22"), (b"c", b"333"), (b"a", b"4"), (b"d", b"5"),
+    ];
+    for &(key, payload) in sends {
+        block_on(writer.send(key, payload)).unwrap();
+    }
+    block_on(poll_fn(|cx| writer.poll_close(cx))).unwrap();
+
+    let mut expected = vec![Vec::new(); 3];
+    for &(key, payload) in sends {
+        expected[(key_sum(key) % 3) as usize].extend_from_slice(payload);
+    }
+    let backends = writer.into_inner()
+        .into_iter()
+        .map(|c| c.into_inner())
+        .collect::<Vec<_>>();
+    assert_eq!(backends, expected);
+    assert_eq!(backends.iter().map(|b| b.len()).sum::<usize>(), 8);
+    // "a" and "d" share a shard.
+    assert_eq!(backends[1], b"145");
+}
@@ -0,0 +1,35 @@
+extern crate futures;
+
+use std::io::Cursor;
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+#[test]
+fn preserves_byte_multiset() {
+    let data: Vec<u8> = (0..200).collect();
+    let mut reader = Cursor::new(data.clone()).shuffle_reads(42);
+    let mut out = Vec::new();
+    block_on(reader.read_to_end(&mut out)).unwrap();
+
+    assert_eq!(out.len(), data.len());
+    assert_ne!(out, data);
+
+    out.sort();
+    assert_eq!(out, data);
+}
+
+#[test]
+fn shuffling_is_local() {
+    let data: Vec<u8> = (0..64).collect();
+    let mut reader = Cursor::new(data.clone()).shuffle_reads(7);
+    let mut out = Vec::new();
+    block_on(reader.read_to_end(&mut out)).unwrap();
+
+    // Bytes never move out of their window of 16.
+    for (chunk, original) in out.chunks(16).zip(data.chunks(16)) {
+        let mut chunk = chunk.to_vec();
+        chunk.sort();
+        assert_eq!(chunk, original);
+    }
+}
@@ -0,0 +1,30 @@
+extern crate futures;
+
+use std::cell::RefCell;
+use std::io::Cursor;
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+#[test]
+fn sniffer_sees_the_prefix_and_nothing_is_lost() {
+    let sniffed = RefCell::new(Vec::new());
+    let mut reader = Cursor::new(b"%PDF-1.7 rest of the document".to_vec())
+        .sniff_prefix(5, |prefix| sniffed.borrow_mut().extend_from_slice(prefix));
+    let mut out = Vec::new();
+    block_on(reader.read_to_end(&mut out)).unwrap();
+    assert!(reader.is_sniffed());
+    assert_eq!(&*sniffed.borrow(), b"%PDF-");
+    assert_eq!(out, b"%PDF-1.7 rest of the document");
+}
+
+#[test]
+fn short_stream_is_sniffed_at_eof() {
+    let sniffed = RefCell::new(None);
+    let mut reader = Cursor::new(b"ab".to_vec())
+        .sniff_prefix(8, |prefix| *sniffed.borrow_mut() = Some(prefix.to_vec()));
+    let mut out = Vec::new();
+    block_on(reader.read_to_end(&mut out)).unwrap();
+    assert_eq!(sniffed.into_inner().unwrap(), b"ab");
+    assert_eq!(out, b"ab");
+}
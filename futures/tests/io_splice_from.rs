@@ -0,0 +1,84 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::executor::block_on;
+use futures::io::IoVec;
+use futures::prelude::*;
+
+#[test]
+fn splices_a_bounded_number_of_bytes() {
+    let mut reader = Cursor::new(b"hello world".to_vec());
+    let mut writer = Cursor::new(Vec::new());
+    let n = block_on(writer.splice_from(&mut reader, Some(5))).unwrap();
+    assert_eq!(n, 5);
+    assert_eq!(writer.get_ref(), b"hello");
+    assert_eq!(reader.position(), 5);
+}
+
+#[test]
+fn splices_until_eof() {
+    let mut reader = Cursor::new(b"hello world".to_vec());
+    let mut writer = Cursor::new(Vec::new());
+    let n = block_on(writer.splice_from(&mut reader, None)).unwrap();
+    assert_eq!(n, 11);
+    assert_eq!(writer.get_ref(), b"hello world");
+
+    let mut reader = Cursor::new(b"short".to_vec());
+    let mut writer = Cursor::new(Vec::new());
+    let n = block_on(writer.splice_from(&mut reader, Some(100))).unwrap();
+    assert_eq!(n, 5);
+    assert_eq!(writer.get_ref(), b"short");
+}
+
+/// A writer which accepts at most 1000 bytes per call and counts vectored
+/// writes of more than one buffer.
+struct Chunked {
+    data: Vec<u8>,
+    vectored: usize,
+}
+
+impl AsyncWrite for Chunked {
+    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        let n = buf.len().min(1000);
+        self.data.extend_from_slice(&buf[..n]);
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_vectored_write(&mut self, _: &mut task::Context, vec: &[&IoVec])
+        -> Poll<usize, io::Error>
+    {
+        if vec.len() > 1 {
+            self.vectored += 1;
+        }
+        let mut total = 0;
+        for buf in vec {
+            let n = buf.len().min(1000 - total);
+            self.data.extend_from_slice(&buf[..n]);
+            total += n;
+            if n < buf.len() {
+                break
+            }
+        }
+        Ok(Async::Ready(total))
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+fn wrapped_buffer_is_written_vectored() {
+    let input: Vec<u8> = (0..50_000).map(|i| (i % 251) as u8).collect();
+    let mut reader = Cursor::new(input.clone());
+    let mut writer = Chunked { data: Vec::new(), vectored: 0 };
+    let n = block_on(writer.splice_from(&mut reader, Some(40_000))).unwrap();
+    assert_eq!(n, 40_000);
+    assert_eq!(writer.data, &input[..40_000]);
+    assert!(writer.vectored > 0);
+}
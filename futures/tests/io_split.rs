@@ -0,0 +1,112 @@
+extern crate futures;
+
+use std::error::Error;
+use std::io::{self, Cursor};
+
+use futures::executor::block_on;
+use futures::io::{reunite, ReuniteError};
+use futures::prelude::*;
+
+/// A full-duplex object reading from one buffer and writing to another.
+struct Duplex {
+    input: Cursor<Vec<u8>>,
+    output: Vec<u8>,
+}
+
+impl Duplex {
+    fn new(input: &[u8]) -> Duplex {
+        Duplex { input: Cursor::new(input.to_vec()), output: Vec::new() }
+    }
+}
+
+impl AsyncRead for Duplex {
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8]) -> Poll<usize, io::Error> {
+        self.input.poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for Duplex {
+    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        self.output.extend_from_slice(buf);
+        Ok(Async::Ready(buf.len()))
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+fn halves_are_used_independently_and_reunited() {
+    let (mut read, mut write) = Duplex::new(b"request").split();
+    block_on(write.write_all(b"response")).unwrap();
+    let mut data = Vec::new();
+    block_on(read.read_to_end(&mut data)).unwrap();
+    assert_eq!(data, b"request");
+
+    let duplex = reunite(read, write).unwrap();
+    assert_eq!(duplex.output, b"response");
+}
+
+#[test]
+fn reunite_methods_on_either_half() {
+    let (read, write) = Duplex::new(b"").split();
+    assert!(read.reunite(write).is_ok());
+    let (read, write) = Duplex::new(b"").split();
+    assert!(write.reunite(read).is_ok());
+}
+
+#[test]
+fn mismatched_halves_are_handed_back() {
+    let (read1, write1) = Duplex::new(b"one").split();
+    let (read2, write2) = Duplex::new(b"two").split();
+    let err = reunite(read1, write2).err().unwrap();
+    let writer = reunite(read2, err.1).unwrap();
+    assert_eq!(writer.input.into_inner(), b"two");
+    let reader = reunite(err.0, write1).unwrap();
+    assert_eq!(reader.input.into_inner(), b"one");
+}
+
+#[test]
+fn handed_back_halves_stay_usable() {
+    let (read1, write1) = Duplex::new(b"one").split();
+    let (read2, write2) = Duplex::new(b"two").split();
+    let err = write2.reunite(read1).err().unwrap();
+    assert!(err.to_string().contains("don't form a pair"));
+    let ReuniteError(mut read1, mut write2) = err;
+
+    let mut data = Vec::new();
+    block_on(read1.read_to_end(&mut data)).unwrap();
+    assert_eq!(data, b"one");
+    block_on(write2.write_all(b"written")).unwrap();
+
+    assert_eq!(reunite(read1, write1).unwrap().output, b"");
+    assert_eq!(reunite(read2, write2).unwrap().output, b"written");
+}
+
+#[test]
+fn reunite_error_is_an_error() {
+    let (read1, _write1) = Duplex::new(b"one").split();
+    let (_read2, write2) = Duplex::new(b"two").split();
+    let err = reunite(read1, write2).err().unwrap();
+    assert_eq!(format!("{:?}", err), "ReuniteError(\"...\")");
+    fn assert_error<E: Error>(_: &E) {}
+    assert_error(&err);
+}
+
+#[test]
+fn dropping_one_half_keeps_the_other_usable() {
+    let (mut read, write) = Duplex::new(b"still readable").split();
+    drop(write);
+    let mut data = Vec::new();
+    block_on(read.read_to_end(&mut data)).unwrap();
+    assert_eq!(data, b"still readable");
+
+    let (read, mut write) = Duplex::new(b"").split();
+    drop(read);
+    block_on(write.write_all(b"still writable")).unwrap();
+}
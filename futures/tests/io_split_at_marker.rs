@@ -0,0 +1,43 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+#[test]
+fn splits_header_and_body() {
+    let request = b"GET / HTTP/1.1\r\nHost: example.com\r\n\r\nbody \r\n\r\n bytes".to_vec();
+    let (header, mut body) = block_on(Cursor::new(request).split_at_marker(b"\r\n\r\n")).unwrap();
+    assert_eq!(header, &b"GET / HTTP/1.1\r\nHost: example.com"[..]);
+    let mut rest = Vec::new();
+    block_on(body.read_to_end(&mut rest)).unwrap();
+    assert_eq!(rest, &b"body \r\n\r\n bytes"[..]);
+}
+
+/// A reader yielding three bytes per read.
+struct Chunked(Cursor<Vec<u8>>);
+
+impl AsyncRead for Chunked {
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8]) -> Poll<usize, io::Error> {
+        let n = buf.len().min(3);
+        self.0.poll_read(cx, &mut buf[..n])
+    }
+}
+
+#[test]
+fn marker_spanning_reads() {
+    let reader = Chunked(Cursor::new(b"ab::cd".to_vec()));
+    let (header, mut body) = block_on(reader.split_at_marker(b"::")).unwrap();
+    assert_eq!(header, b"ab");
+    let mut rest = Vec::new();
+    block_on(body.read_to_end(&mut rest)).unwrap();
+    assert_eq!(rest, b"cd");
+}
+
+#[test]
+fn missing_marker_is_an_error() {
+    let err = block_on(Cursor::new(b"no marker".to_vec()).split_at_marker(b"\r\n\r\n"))
+        .err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
@@ -0,0 +1,30 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::executor::block_on;
+use futures::io::StructReader;
+
+#[test]
+fn reads_fixed_and_trailing_fields() {
+    let mut reader = Cursor::new(b"\x00\x2aHDRrest of it".to_vec());
+    let mut schema = StructReader::new(&[2, 3]);
+    schema.trailing(true);
+    let fields = block_on(schema.read(&mut reader)).unwrap();
+    assert_eq!(fields, [&b"\x00\x2a"[..], b"HDR", b"rest of it"]);
+}
+
+#[test]
+fn fixed_fields_leave_the_rest_unread() {
+    let mut reader = Cursor::new(b"abcdef".to_vec());
+    let fields = block_on(StructReader::new(&[1, 2]).read(&mut reader)).unwrap();
+    assert_eq!(fields, [&b"a"[..], b"bc"]);
+    assert_eq!(reader.position(), 3);
+}
+
+#[test]
+fn short_stream_is_an_error() {
+    let mut reader = Cursor::new(b"abc".to_vec());
+    let err = block_on(StructReader::new(&[2, 2]).read(&mut reader)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
@@ -0,0 +1,57 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+mod support;
+
+use support::*;
+
+#[test]
+fn reads_at_most_the_limit() {
+    let mut reader = Cursor::new(b"frame|rest".to_vec()).take(5);
+    let mut data = Vec::new();
+    block_on(reader.read_to_end(&mut data)).unwrap();
+    assert_eq!(data, b"frame");
+    assert_eq!(reader.limit(), 0);
+
+    reader.set_limit(100);
+    let mut data = Vec::new();
+    block_on(reader.read_to_end(&mut data)).unwrap();
+    assert_eq!(data, b"|rest");
+    assert_eq!(reader.limit(), 95);
+    assert_eq!(reader.into_inner().position(), 10);
+}
+
+/// A reader which panics when read from.
+struct Untouchable;
+
+impl AsyncRead for Untouchable {
+    fn poll_read(&mut self, _: &mut task::Context, _: &mut [u8]) -> Poll<usize, io::Error> {
+        panic!("the underlying reader was read from")
+    }
+}
+
+#[test]
+fn zero_limit_is_eof_without_reading() {
+    let mut reader = Untouchable.take(0);
+    let mut buf = [0; 8];
+    noop_waker_cx(|cx| {
+        assert_eq!(reader.poll_read(cx, &mut buf).unwrap(), Async::Ready(0));
+    });
+}
+
+#[test]
+fn buffered_take() {
+    let mut reader = Cursor::new(b"abcdef".to_vec()).take(4);
+    noop_waker_cx(|cx| {
+        assert_eq!(reader.poll_fill_buf(cx).unwrap(), Async::Ready(&b"abcd"[..]));
+        reader.consume(3);
+        assert_eq!(reader.poll_fill_buf(cx).unwrap(), Async::Ready(&b"d"[..]));
+        reader.consume(1);
+        assert_eq!(reader.poll_fill_buf(cx).unwrap(), Async::Ready(&b""[..]));
+    });
+    assert_eq!(reader.into_inner().position(), 4);
+}
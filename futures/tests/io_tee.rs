@@ -0,0 +1,57 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+/// A writer accepting up to three bytes per write, returning `Pending`
+/// before each of them.
+#[derive(Default)]
+struct Slow {
+    data: Vec<u8>,
+    ready: bool,
+}
+
+impl AsyncWrite for Slow {
+    fn poll_write(&mut self, cx: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        if !self.ready {
+            self.ready = true;
+            cx.waker().wake();
+            return Ok(Async::Pending)
+        }
+        self.ready = false;
+        let n = buf.len().min(3);
+        self.data.extend_from_slice(&buf[..n]);
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+fn mirrors_everything_read() {
+    let mut reader = Cursor::new(b"the protocol bytes".to_vec()).tee(Cursor::new(Vec::new()));
+    let mut data = Vec::new();
+    block_on(reader.read_to_end(&mut data)).unwrap();
+    assert_eq!(data, b"the protocol bytes");
+    assert_eq!(reader.into_inner().1.into_inner(), b"the protocol bytes");
+}
+
+#[test]
+fn slow_mirrors_lose_nothing() {
+    let input: Vec<u8> = (0..200).collect();
+    let mut reader = Cursor::new(input.clone()).tee(Slow::default());
+    let mut data = Vec::new();
+    block_on(reader.read_to_end(&mut data)).unwrap();
+    assert_eq!(data, input);
+    let (cursor, mirror) = reader.into_inner();
+    assert_eq!(cursor.position(), 200);
+    assert_eq!(mirror.data, input);
+}
@@ -0,0 +1,50 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+#[test]
+fn both_writers_receive_everything() {
+    let mut writer = Cursor::new(Vec::new()).tee_to(Cursor::new(Vec::new()));
+    block_on(writer.write_all(b"hello world")).unwrap();
+    block_on(writer.close()).unwrap();
+    let (primary, capture) = writer.into_inner();
+    assert_eq!(primary.get_ref(), b"hello world");
+    assert_eq!(capture.get_ref(), b"hello world");
+}
+
+/// A writer accepting at most `max` bytes per call.
+struct Short {
+    data: Vec<u8>,
+    max: usize,
+}
+
+impl AsyncWrite for Short {
+    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        let n = buf.len().min(self.max);
+        self.data.extend_from_slice(&buf[..n]);
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+fn writers_accepting_different_amounts() {
+    let data: Vec<u8> = (0..100).collect();
+    let mut writer = Short { data: Vec::new(), max: 7 }
+        .tee_to(Short { data: Vec::new(), max: 3 });
+    block_on(writer.write_all(&data)).unwrap();
+    block_on(writer.flush()).unwrap();
+    let (primary, capture) = writer.into_inner();
+    assert_eq!(primary.data, data);
+    assert_eq!(capture.data, data);
+}
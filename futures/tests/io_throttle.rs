@@ -0,0 +1,52 @@
+extern crate futures;
+
+use std::io::Cursor;
+
+use futures::executor::block_on;
+use futures::io::IoVec;
+use futures::prelude::*;
+
+mod support;
+use support::*;
+
+#[test]
+fn caps_each_read() {
+    let mut reader = Cursor::new(b"0123456789".to_vec()).throttle(4);
+    assert_eq!(reader.limit(), 4);
+    let mut buf = [0; 8];
+    noop_waker_cx(|cx| {
+        assert_eq!(reader.poll_read(cx, &mut buf).unwrap(), Async::Ready(4));
+        assert_eq!(&buf[..4], b"0123");
+        assert_eq!(reader.poll_read(cx, &mut buf[..2]).unwrap(), Async::Ready(2));
+        assert_eq!(&buf[..2], b"45");
+
+        reader.set_limit(1);
+        assert_eq!(reader.poll_read(cx, &mut buf).unwrap(), Async::Ready(1));
+        assert_eq!(buf[0], b'6');
+    });
+
+    let mut data = Vec::new();
+    block_on(reader.read_to_end(&mut data)).unwrap();
+    assert_eq!(data, b"789");
+}
+
+#[test]
+fn caps_vectored_reads() {
+    let mut reader = Cursor::new(b"0123456789".to_vec()).throttle(3);
+    let mut a = [0; 2];
+    let mut b = [0; 4];
+    noop_waker_cx(|cx| {
+        let n = {
+            let mut vec: [&mut IoVec; 2] = [(&mut a[..]).into(), (&mut b[..]).into()];
+            reader.poll_vectored_read(cx, &mut vec).unwrap()
+        };
+        assert_eq!(n, Async::Ready(2));
+    });
+    assert_eq!(&a, b"01");
+}
+
+#[test]
+#[should_panic]
+fn rejects_zero() {
+    Cursor::new(Vec::new()).throttle(0);
+}
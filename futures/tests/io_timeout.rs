@@ -0,0 +1,90 @@
+extern crate futures;
+
+use std::cell::{Cell, RefCell};
+use std::io;
+use std::rc::Rc;
+use std::time::Duration;
+
+use futures::future::{ok, FutureResult};
+use futures::io::pipe;
+use futures::prelude::*;
+
+mod support;
+use support::*;
+
+/// A delay which fires once the test says so.
+struct Delay(Rc<Cell<bool>>);
+
+impl Future for Delay {
+    type Item = ();
+    type Error = Never;
+
+    fn poll(&mut self, _: &mut task::Context) -> Poll<(), Never> {
+        Ok(if self.0.get() { Async::Ready(()) } else { Async::Pending })
+    }
+}
+
+fn ready(_: Duration) -> FutureResult<(), Never> {
+    ok(())
+}
+
+#[test]
+fn ready_delay_times_out_idle_read() {
+    let (reader, _writer) = pipe();
+    let mut reader = reader.timeout(Duration::from_secs(5), ready);
+    let mut buf = [0; 8];
+    noop_waker_cx(|cx| {
+        let err = reader.poll_read(cx, &mut buf).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+    });
+}
+
+#[test]
+fn available_bytes_win_over_delay() {
+    let (reader, mut writer) = pipe();
+    let mut reader = reader.timeout(Duration::from_secs(5), ready);
+    let mut buf = [0; 8];
+    noop_waker_cx(|cx| {
+        assert_eq!(writer.poll_write(cx, b"data").unwrap(), Async::Ready(4));
+        assert_eq!(reader.poll_read(cx, &mut buf).unwrap(), Async::Ready(4));
+    });
+    assert_eq!(&buf[..4], b"data");
+}
+
+#[test]
+fn timer_restarts_after_each_read() {
+    let timers = Rc::new(RefCell::new(Vec::new()));
+    let created = timers.clone();
+    let timer = move |dur| {
+        assert_eq!(dur, Duration::from_secs(5));
+        let fired = Rc::new(Cell::new(false));
+        created.borrow_mut().push(fired.clone());
+        Delay(fired)
+    };
+
+    let (reader, mut writer) = pipe();
+    let mut reader = reader.timeout(Duration::from_secs(5), timer);
+    let mut buf = [0; 8];
+    noop_waker_cx(|cx| {
+        // Reads which don't have to wait don't start a timer.
+        assert_eq!(writer.poll_write(cx, b"a").unwrap(), Async::Ready(1));
+        assert_eq!(reader.poll_read(cx, &mut buf).unwrap(), Async::Ready(1));
+        assert_eq!(timers.borrow().len(), 0);
+
+        assert_eq!(reader.poll_read(cx, &mut buf).unwrap(), Async::Pending);
+        assert_eq!(reader.poll_read(cx, &mut buf).unwrap(), Async::Pending);
+        assert_eq!(timers.borrow().len(), 1);
+
+        assert_eq!(writer.poll_write(cx, b"b").unwrap(), Async::Ready(1));
+        assert_eq!(reader.poll_read(cx, &mut buf).unwrap(), Async::Ready(1));
+
+        // The fired timer belonged to the previous idle period.
+        timers.borrow()[0].set(true);
+        assert_eq!(reader.poll_read(cx, &mut buf).unwrap(), Async::Pending);
+        assert_eq!(timers.borrow().len(), 2);
+
+        timers.borrow()[1].set(true);
+        let err = reader.poll_read(cx, &mut buf).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+    });
+}
@@ -0,0 +1,46 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+use std::thread;
+use std::time::Duration;
+
+use futures::executor::block_on;
+use futures::io::CancellationToken;
+use futures::prelude::*;
+
+/// A reader which never becomes ready and never wakes its task.
+struct Stalled;
+
+impl AsyncRead for Stalled {
+    fn poll_read(&mut self, _: &mut task::Context, _: &mut [u8]) -> Poll<usize, io::Error> {
+        Ok(Async::Pending)
+    }
+}
+
+#[test]
+fn cancel_interrupts_a_pending_read() {
+    let token = CancellationToken::new();
+    let mut reader = Stalled.until_cancelled(token.clone());
+
+    let canceller = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        token.cancel();
+    });
+    let err = block_on(reader.read(&mut [0; 8])).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::Interrupted);
+    canceller.join().unwrap();
+}
+
+#[test]
+fn reads_pass_through_until_cancelled() {
+    let token = CancellationToken::new();
+    let mut reader = Cursor::new(b"hello world".to_vec()).until_cancelled(token.clone());
+    let mut buf = [0; 5];
+    block_on(reader.read_exact(&mut buf)).unwrap();
+    assert_eq!(&buf, b"hello");
+
+    token.cancel();
+    assert!(reader.token().is_cancelled());
+    let err = block_on(reader.read_exact(&mut buf)).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::Interrupted);
+}
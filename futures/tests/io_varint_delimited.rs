@@ -0,0 +1,39 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::executor::block_on;
+use futures::io::varint_delimited_sink;
+use futures::prelude::*;
+use futures::stream;
+
+#[test]
+fn round_trip() {
+    let big: Vec<u8> = (0..300).map(|i| i as u8).collect();
+    let messages = vec![b"hello".to_vec(), big.clone(), Vec::new()];
+
+    let sink = varint_delimited_sink(Cursor::new(Vec::new()));
+    let (sink, _) = block_on(sink.send_all(stream::iter_ok::<_, io::Error>(messages.clone()))).unwrap();
+    let written = sink.into_inner().into_inner();
+    // 300 takes two bytes as a varint.
+    assert_eq!(&written[..6], b"\x05hello");
+    assert_eq!(&written[6..8], b"\xac\x02");
+
+    let decoded: Vec<Vec<u8>> =
+        block_on(Cursor::new(written).varint_delimited().collect()).unwrap();
+    assert_eq!(decoded, messages);
+}
+
+#[test]
+fn truncated_message_is_an_error() {
+    let reader = Cursor::new(b"\x05hel".to_vec()).varint_delimited();
+    let err = block_on(reader.collect::<Vec<_>>()).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn overlong_length_is_an_error() {
+    let reader = Cursor::new(vec![0xff; 11]).varint_delimited();
+    let err = block_on(reader.collect::<Vec<_>>()).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
@@ -0,0 +1,128 @@
+extern crate futures;
+
+use std::io;
+
+use futures::io::IoVec;
+use futures::prelude::*;
+
+mod support;
+
+use support::*;
+
+/// A reader with the default `poll_vectored_read`, which yields at most
+/// `max` bytes per read and pends once it has run out of data.
+struct Chunked {
+    data: Vec<u8>,
+    max: usize,
+    reads: usize,
+}
+
+impl AsyncRead for Chunked {
+    fn poll_read(&mut self, _: &mut task::Context, buf: &mut [u8]) -> Poll<usize, io::Error> {
+        self.reads += 1;
+        if self.data.is_empty() {
+            return Ok(Async::Pending)
+        }
+        let n = buf.len().min(self.data.len()).min(self.max);
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data.drain(..n);
+        Ok(Async::Ready(n))
+    }
+}
+
+fn chunked(data: &[u8], max: usize) -> Chunked {
+    Chunked { data: data.to_vec(), max, reads: 0 }
+}
+
+fn read_vectored(reader: &mut Chunked, lens: &[usize]) -> (Async<usize>, Vec<Vec<u8>>) {
+    let mut bufs: Vec<Vec<u8>> = lens.iter().map(|&len| vec![0; len]).collect();
+    let res = noop_waker_cx(|cx| {
+        let mut iovecs: Vec<&mut IoVec> = bufs.iter_mut()
+            .filter_map(|buf| IoVec::from_bytes_mut(buf))
+            .collect();
+        reader.poll_vectored_read(cx, &mut iovecs).unwrap()
+    });
+    (res, bufs)
+}
+
+#[test]
+fn fills_several_buffers() {
+    let mut reader = chunked(b"abcdefgh", 100);
+    let (res, bufs) = read_vectored(&mut reader, &[3, 2, 10]);
+    assert_eq!(res, Async::Ready(8));
+    assert_eq!(bufs[0], b"abc");
+    assert_eq!(bufs[1], b"de");
+    assert_eq!(&bufs[2][..3], b"fgh");
+    assert_eq!(reader.reads, 3);
+}
+
+#[test]
+fn stops_at_a_short_read() {
+    let mut reader = chunked(b"abcdefgh", 4);
+    let (res, bufs) = read_vectored(&mut reader, &[2, 5, 5]);
+    assert_eq!(res, Async::Ready(6));
+    assert_eq!(bufs[0], b"ab");
+    assert_eq!(&bufs[1][..4], b"cdef");
+    assert_eq!(reader.reads, 2);
+}
+
+#[test]
+fn pending_first_read_is_pending() {
+    let mut reader = chunked(b"", 100);
+    let (res, _) = read_vectored(&mut reader, &[2, 2]);
+    assert_eq!(res, Async::Pending);
+}
+
+#[test]
+fn pending_later_read_returns_what_was_read() {
+    let mut reader = chunked(b"ab", 100);
+    let (res, bufs) = read_vectored(&mut reader, &[2, 2]);
+    assert_eq!(res, Async::Ready(2));
+    assert_eq!(bufs[0], b"ab");
+    assert_eq!(reader.reads, 2);
+}
+
+#[test]
+fn empty_buffers_are_skipped() {
+    let mut reader = chunked(b"abc", 100);
+    let (res, bufs) = read_vectored(&mut reader, &[0, 3]);
+    assert_eq!(res, Async::Ready(3));
+    assert_eq!(bufs[1], b"abc");
+    assert_eq!(reader.reads, 1);
+}
+
+/// A reader which yields `data` and then fails every read.
+struct Broken {
+    data: &'static [u8],
+}
+
+impl AsyncRead for Broken {
+    fn poll_read(&mut self, _: &mut task::Context, buf: &mut [u8]) -> Poll<usize, io::Error> {
+        if self.data.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::ConnectionReset, "reset"))
+        }
+        let n = buf.len().min(self.data.len());
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data = &self.data[n..];
+        Ok(Async::Ready(n))
+    }
+}
+
+#[test]
+fn error_after_some_bytes_is_left_for_the_next_call() {
+    let mut reader = Broken { data: b"ab" };
+    let mut bufs = [[0; 2], [0; 2]];
+    noop_waker_cx(|cx| {
+        {
+            let (first, second) = bufs.split_at_mut(1);
+            let mut iovecs: Vec<&mut IoVec> = vec![(&mut first[0][..]).into(),
+                                                   (&mut second[0][..]).into()];
+            assert_eq!(reader.poll_vectored_read(cx, &mut iovecs).unwrap(), Async::Ready(2));
+        }
+        assert_eq!(bufs[0], *b"ab");
+
+        let mut iovecs: Vec<&mut IoVec> = vec![(&mut bufs[1][..]).into()];
+        let err = reader.poll_vectored_read(cx, &mut iovecs).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::ConnectionReset);
+    });
+}
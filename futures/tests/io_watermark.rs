@@ -0,0 +1,57 @@
+extern crate futures;
+
+use std::cell::RefCell;
+use std::io;
+use std::rc::Rc;
+
+use futures::executor::block_on;
+use futures::io::{Watermark, WatermarkWriter};
+use futures::prelude::*;
+
+/// A writer which accepts at most one byte per call.
+struct Trickle(Vec<u8>);
+
+impl AsyncWrite for Trickle {
+    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        match buf.first() {
+            Some(&b) => {
+                self.0.push(b);
+                Ok(Async::Ready(1))
+            }
+            None => Ok(Async::Ready(0)),
+        }
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+fn reports_high_and_low_water() {
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let events2 = events.clone();
+    let mut w = WatermarkWriter::new(Trickle(Vec::new()), 2, 4, move |e| {
+        events2.borrow_mut().push(e)
+    });
+
+    block_on(w.write_all(b"abcd")).unwrap();
+    assert_eq!(*events.borrow(), [Watermark::High]);
+    assert_eq!(w.buffered(), 4);
+
+    // The next write has to drain the buffer down to the low-water mark
+    // before anything new is accepted.
+    block_on(w.write_all(b"ef")).unwrap();
+    assert_eq!(*events.borrow(), [Watermark::High, Watermark::Low, Watermark::High]);
+    assert_eq!(w.get_ref().0, b"ab");
+
+    block_on(w.flush()).unwrap();
+    assert_eq!(*events.borrow(),
+               [Watermark::High, Watermark::Low, Watermark::High, Watermark::Low]);
+    assert_eq!(w.buffered(), 0);
+    assert_eq!(w.into_inner().0, b"abcdef");
+}
@@ -0,0 +1,69 @@
+extern crate futures;
+
+use std::io::{self, SeekFrom};
+
+use futures::executor::block_on;
+use futures::io::Window;
+use futures::prelude::*;
+
+mod support;
+use support::*;
+
+#[test]
+fn reads_only_the_window() {
+    let mut window = Window::new(b"header|body|trailer".to_vec());
+    window.set_start(7).set_end(11);
+    assert_eq!(window.as_ref(), b"body");
+
+    let mut data = Vec::new();
+    block_on(window.read_to_end(&mut data)).unwrap();
+    assert_eq!(data, b"body");
+    assert_eq!(window.position(), 4);
+    assert_eq!(window.into_inner().len(), 19);
+}
+
+#[test]
+fn seeks_within_the_window() {
+    let mut window = Window::new(b"0123456789".to_vec());
+    window.set_start(2).set_end(8);
+    let mut buf = [0; 3];
+    noop_waker_cx(|cx| {
+        assert_eq!(window.poll_seek(cx, SeekFrom::End(-2)).unwrap(), Async::Ready(4));
+        assert_eq!(window.poll_read(cx, &mut buf).unwrap(), Async::Ready(2));
+        assert_eq!(&buf[..2], b"67");
+        assert_eq!(window.poll_read(cx, &mut buf).unwrap(), Async::Ready(0));
+
+        assert_eq!(window.poll_seek(cx, SeekFrom::Start(1)).unwrap(), Async::Ready(1));
+        assert_eq!(window.poll_seek(cx, SeekFrom::Current(1)).unwrap(), Async::Ready(2));
+        assert_eq!(window.poll_read(cx, &mut buf).unwrap(), Async::Ready(3));
+        assert_eq!(&buf, b"456");
+
+        // Past the end of the window reads nothing, even though the buffer
+        // goes on.
+        assert_eq!(window.poll_seek(cx, SeekFrom::Start(100)).unwrap(), Async::Ready(100));
+        assert_eq!(window.poll_read(cx, &mut buf).unwrap(), Async::Ready(0));
+
+        let err = window.poll_seek(cx, SeekFrom::Current(-101)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(window.position(), 100);
+    });
+}
+
+#[test]
+fn position_is_relative_to_the_window() {
+    let mut window = Window::new(b"0123456789".to_vec());
+    let mut buf = [0; 2];
+    noop_waker_cx(|cx| {
+        assert_eq!(window.poll_read(cx, &mut buf).unwrap(), Async::Ready(2));
+        window.set_start(5);
+        assert_eq!(window.poll_read(cx, &mut buf).unwrap(), Async::Ready(2));
+        assert_eq!(&buf, b"78");
+    });
+}
+
+#[test]
+#[should_panic]
+fn start_after_end_is_rejected() {
+    let mut window = Window::new(b"0123456789".to_vec());
+    window.set_end(4).set_start(5);
+}
@@ -0,0 +1,106 @@
+extern crate futures;
+
+use std::io;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use futures::executor::block_on;
+use futures::io::Semaphore;
+use futures::prelude::*;
+use futures::task::{LocalMap, Wake, Waker};
+
+mod support;
+use support::*;
+
+/// A slow reader which records the highest number of reads in progress at
+/// once across all readers sharing `active`.
+struct Tracked {
+    active: Arc<AtomicUsize>,
+    max: Arc<AtomicUsize>,
+    left: usize,
+}
+
+impl AsyncRead for Tracked {
+    fn poll_read(&mut self, _: &mut task::Context, buf: &mut [u8]) -> Poll<usize, io::Error> {
+        let now = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max.fetch_max(now, Ordering::SeqCst);
+        thread::sleep(Duration::from_millis(2));
+        self.active.fetch_sub(1, Ordering::SeqCst);
+
+        let n = buf.len().min(self.left).min(1);
+        self.left -= n;
+        Ok(Async::Ready(n))
+    }
+}
+
+#[test]
+fn single_permit_serializes_reads() {
+    let semaphore = Semaphore::new(1);
+    let active = Arc::new(AtomicUsize::new(0));
+    let max = Arc::new(AtomicUsize::new(0));
+
+    let threads = (0..2).map(|_| {
+        let mut reader = Tracked { active: active.clone(), max: max.clone(), left: 10 }
+            .with_permit(semaphore.clone());
+        thread::spawn(move || block_on(reader.read_to_end(&mut Vec::new())).unwrap())
+    }).collect::<Vec<_>>();
+    for t in threads {
+        assert_eq!(t.join().unwrap(), 10);
+    }
+
+    assert_eq!(max.load(Ordering::SeqCst), 1);
+    assert_eq!(semaphore.available(), 1);
+}
+
+#[test]
+fn pending_acquire_is_woken_on_release() {
+    let semaphore = Semaphore::new(1);
+    let permit = block_on(futures::future::poll_fn(|cx| {
+        Ok::<_, ()>(semaphore.poll_acquire(cx))
+    })).unwrap();
+    assert_eq!(semaphore.available(), 0);
+
+    let other = semaphore.clone();
+    let t = thread::spawn(move || {
+        let mut reader = io::Cursor::new(b"data".to_vec()).with_permit(other);
+        let mut data = Vec::new();
+        block_on(reader.read_to_end(&mut data)).unwrap();
+        data
+    });
+    thread::sleep(Duration::from_millis(20));
+    drop(permit);
+    assert_eq!(t.join().unwrap(), b"data");
+}
+
+/// A waker counting how often it is woken.
+struct CountWakes(AtomicUsize);
+
+impl Wake for CountWakes {
+    fn wake(arc_self: &Arc<Self>) {
+        arc_self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn waiting_handle_keeps_one_waker() {
+    let semaphore = Semaphore::new(1);
+    let permit = block_on(futures::future::poll_fn(|cx| {
+        Ok::<_, ()>(semaphore.poll_acquire(cx))
+    })).unwrap();
+
+    let wakes = Arc::new(CountWakes(AtomicUsize::new(0)));
+    let waker = Waker::from(wakes.clone());
+    let mut map = LocalMap::new();
+    let mut exec = PanicExec;
+    let mut cx = task::Context::new(&mut map, &waker, &mut exec);
+
+    let waiting = semaphore.clone();
+    for _ in 0..100 {
+        assert!(waiting.poll_acquire(&mut cx).is_pending());
+    }
+    drop(permit);
+    assert_eq!(wakes.0.load(Ordering::SeqCst), 1);
+    assert!(waiting.poll_acquire(&mut cx).is_ready());
+}
@@ -0,0 +1,64 @@
+extern crate futures;
+
+use std::cell::Cell;
+use std::io;
+use std::rc::Rc;
+
+use futures::io::WatermarkWriter;
+use futures::prelude::*;
+
+mod support;
+use support::*;
+
+/// A bounded ring buffer which only accepts data while it has free space;
+/// the consumer drains it by resetting `used`.
+struct Ring {
+    cap: usize,
+    used: Rc<Cell<usize>>,
+}
+
+impl AsyncWrite for Ring {
+    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        let n = buf.len().min(self.cap - self.used.get());
+        if n == 0 {
+            return Ok(Async::Pending)
+        }
+        self.used.set(self.used.get() + n);
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+fn writable_waits_for_capacity() {
+    let used = Rc::new(Cell::new(0));
+    let ring = Ring { cap: 4, used: used.clone() };
+    let mut w = WatermarkWriter::new(ring, 0, 4, |_| ());
+
+    noop_waker_cx(|cx| {
+        assert!(w.writable().poll(cx).unwrap().is_ready());
+
+        // Fill the writer's buffer; waiting for writability drains it into
+        // the ring buffer, which still has room.
+        assert_eq!(w.poll_write(cx, b"abcd").unwrap(), Async::Ready(4));
+        assert!(w.writable().poll(cx).unwrap().is_ready());
+        assert_eq!(used.get(), 4);
+
+        // Now both buffers are full.
+        assert_eq!(w.poll_write(cx, b"efgh").unwrap(), Async::Ready(4));
+        assert!(w.writable().poll(cx).unwrap().is_pending());
+        assert!(w.poll_write(cx, b"ijkl").unwrap().is_pending());
+
+        // Once the consumer drains the ring buffer we can write again.
+        used.set(0);
+        assert!(w.writable().poll(cx).unwrap().is_ready());
+        assert_eq!(w.poll_write(cx, b"ijkl").unwrap(), Async::Ready(4));
+    });
+}
@@ -0,0 +1,55 @@
+extern crate futures;
+
+use std::io;
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+/// A writer accepting at most `max` bytes per call, returning `Pending`
+/// before each write, which panics if it's flushed.
+struct Short {
+    data: Vec<u8>,
+    max: usize,
+    ready: bool,
+}
+
+impl Short {
+    fn new(max: usize) -> Short {
+        Short { data: Vec::new(), max, ready: false }
+    }
+}
+
+impl AsyncWrite for Short {
+    fn poll_write(&mut self, cx: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        if !self.ready {
+            self.ready = true;
+            cx.waker().wake();
+            return Ok(Async::Pending)
+        }
+        self.ready = false;
+        let n = buf.len().min(self.max);
+        self.data.extend_from_slice(&buf[..n]);
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        panic!("write_all must not flush")
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+fn writes_everything_across_partial_writes() {
+    let mut writer = Short::new(3);
+    block_on(writer.write_all(&b"hello world"[..])).unwrap();
+    assert_eq!(writer.data, b"hello world");
+}
+
+#[test]
+fn zero_write_is_an_error() {
+    let err = block_on(Short::new(0).write_all(b"hello")).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::WriteZero);
+}
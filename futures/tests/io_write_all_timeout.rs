@@ -0,0 +1,79 @@
+extern crate futures;
+
+use std::cell::Cell;
+use std::io;
+use std::rc::Rc;
+use std::time::Duration;
+
+use futures::prelude::*;
+
+mod support;
+use support::*;
+
+/// A writer accepting a single byte every other call.
+struct Slow {
+    data: Vec<u8>,
+    ready: bool,
+}
+
+impl AsyncWrite for Slow {
+    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        self.ready = !self.ready;
+        if !self.ready {
+            return Ok(Async::Pending)
+        }
+        self.data.push(buf[0]);
+        Ok(Async::Ready(1))
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+/// A delay which fires once the test says so.
+struct Delay(Rc<Cell<bool>>);
+
+impl Future for Delay {
+    type Item = ();
+    type Error = Never;
+
+    fn poll(&mut self, _: &mut task::Context) -> Poll<(), Never> {
+        Ok(if self.0.get() { Async::Ready(()) } else { Async::Pending })
+    }
+}
+
+#[test]
+fn times_out_mid_write() {
+    let fired = Rc::new(Cell::new(false));
+    let mut w = Slow { data: Vec::new(), ready: false };
+    {
+        let timer = |dur| {
+            assert_eq!(dur, Duration::from_secs(5));
+            Delay(fired.clone())
+        };
+        let mut fut = w.write_all_timeout(b"hello", Duration::from_secs(5), timer);
+        noop_waker_cx(|cx| {
+            assert!(fut.poll(cx).unwrap().is_pending());
+            fired.set(true);
+            assert_eq!(fut.poll(cx).unwrap_err().kind(), io::ErrorKind::TimedOut);
+        });
+        assert_eq!(fut.remaining(), b"llo");
+    }
+    assert_eq!(w.data, b"he");
+}
+
+#[test]
+fn completes_before_deadline() {
+    let fired = Rc::new(Cell::new(false));
+    let mut w = Slow { data: Vec::new(), ready: false };
+    noop_waker_cx(|cx| {
+        let mut fut = w.write_all_timeout(b"hi", Duration::from_secs(1), |_| Delay(fired.clone()));
+        while fut.poll(cx).unwrap().is_pending() {}
+    });
+    assert_eq!(w.data, b"hi");
+}
@@ -0,0 +1,79 @@
+extern crate futures;
+
+use std::cell::RefCell;
+use std::io;
+use std::rc::Rc;
+use std::time::Duration;
+
+use futures::executor::block_on;
+use futures::prelude::*;
+
+/// A writer reporting zero-length writes `zeros` times before accepting
+/// data.
+struct Hiccup {
+    zeros: usize,
+    data: Vec<u8>,
+}
+
+impl AsyncWrite for Hiccup {
+    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        if self.zeros > 0 {
+            self.zeros -= 1;
+            return Ok(Async::Ready(0))
+        }
+        self.data.extend_from_slice(buf);
+        Ok(Async::Ready(buf.len()))
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+/// A delay which is pending once, waking the task, before completing.
+struct Delay(bool);
+
+impl Future for Delay {
+    type Item = ();
+    type Error = Never;
+
+    fn poll(&mut self, cx: &mut task::Context) -> Poll<(), Never> {
+        if self.0 {
+            return Ok(Async::Ready(()))
+        }
+        self.0 = true;
+        cx.waker().wake();
+        Ok(Async::Pending)
+    }
+}
+
+#[test]
+fn completes_where_write_all_fails() {
+    let mut writer = Hiccup { zeros: 1, data: Vec::new() };
+    let err = block_on(writer.write_all(b"data")).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::WriteZero);
+
+    let delays = Rc::new(RefCell::new(Vec::new()));
+    let delays2 = delays.clone();
+    let mut writer = Hiccup { zeros: 1, data: Vec::new() };
+    block_on(writer.write_all_tolerant(b"data", 3, Duration::from_millis(5), move |d| {
+        delays2.borrow_mut().push(d);
+        Delay(false)
+    })).unwrap();
+    assert_eq!(writer.data, b"data");
+    assert_eq!(*delays.borrow(), vec![Duration::from_millis(5)]);
+}
+
+#[test]
+fn errors_once_retries_are_exhausted() {
+    let mut writer = Hiccup { zeros: 3, data: Vec::new() };
+    let err = block_on(writer.write_all_tolerant(b"data", 2, Duration::from_millis(5), |_| {
+        Delay(false)
+    })).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::WriteZero);
+    assert!(writer.data.is_empty());
+}
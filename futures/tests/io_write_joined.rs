@@ -0,0 +1,62 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::executor::block_on;
+use futures::prelude::*;
+use futures::io::IoVec;
+
+/// A writer which accepts at most two bytes per call, recording whether the
+/// vectored path was used.
+struct Slow {
+    data: Vec<u8>,
+    vectored: usize,
+}
+
+impl AsyncWrite for Slow {
+    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        let n = buf.len().min(2);
+        self.data.extend_from_slice(&buf[..n]);
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_vectored_write(&mut self, cx: &mut task::Context, vec: &[&IoVec])
+        -> Poll<usize, io::Error>
+    {
+        self.vectored += 1;
+        match vec.first() {
+            Some(buf) => self.poll_write(cx, buf),
+            None => Ok(Async::Ready(0)),
+        }
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+fn joins_fields() {
+    let mut w = Cursor::new(Vec::new());
+    block_on(w.write_joined(&[b"foo", b"bar", b"baz"], b",")).unwrap();
+    assert_eq!(w.into_inner(), b"foo,bar,baz");
+}
+
+#[test]
+fn joins_across_partial_writes() {
+    let mut w = Slow { data: Vec::new(), vectored: 0 };
+    block_on(w.write_joined(&[b"foo", b"", b"bazz"], b", ")).unwrap();
+    assert_eq!(w.data, b"foo, , bazz");
+    assert!(w.vectored > 0);
+}
+
+#[test]
+fn empty_parts_write_nothing() {
+    let mut w = Cursor::new(Vec::new());
+    block_on(w.write_joined(&[], b",")).unwrap();
+    assert!(w.into_inner().is_empty());
+}
@@ -0,0 +1,71 @@
+extern crate futures;
+
+use std::io::{self, Cursor};
+
+use futures::executor::block_on;
+use futures::io::{XorStreamReader, XorStreamWriter};
+use futures::prelude::*;
+
+const KEY: &[u8] = b"key";
+
+fn keystream(pos: u64) -> u8 {
+    KEY[(pos % KEY.len() as u64) as usize]
+}
+
+/// A reader which returns at most two bytes per read.
+struct Short<R>(R);
+
+impl<R: AsyncRead> AsyncRead for Short<R> {
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8]) -> Poll<usize, io::Error> {
+        let len = buf.len().min(2);
+        self.0.poll_read(cx, &mut buf[..len])
+    }
+}
+
+#[test]
+fn decrypts_with_repeating_key() {
+    let plain = b"attack at dawn";
+    let cipher = plain.iter().enumerate()
+        .map(|(i, &b)| b ^ keystream(i as u64))
+        .collect::<Vec<_>>();
+
+    let mut reader = XorStreamReader::new(Short(Cursor::new(cipher)), keystream);
+    let mut data = Vec::new();
+    block_on(reader.read_to_end(&mut data)).unwrap();
+    assert_eq!(data, plain);
+    assert_eq!(reader.position(), plain.len() as u64);
+}
+
+/// A writer which accepts at most three bytes per write.
+struct ShortWrite(Vec<u8>);
+
+impl AsyncWrite for ShortWrite {
+    fn poll_write(&mut self, _: &mut task::Context, buf: &[u8]) -> Poll<usize, io::Error> {
+        let n = buf.len().min(3);
+        self.0.extend_from_slice(&buf[..n]);
+        Ok(Async::Ready(n))
+    }
+
+    fn poll_flush(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+
+    fn poll_close(&mut self, _: &mut task::Context) -> Poll<(), io::Error> {
+        Ok(Async::Ready(()))
+    }
+}
+
+#[test]
+fn round_trips_through_writer_and_reader() {
+    let plain = b"the quick brown fox";
+    let mut writer = XorStreamWriter::new(ShortWrite(Vec::new()), keystream);
+    block_on(writer.write_all(plain)).unwrap();
+    assert_eq!(writer.position(), plain.len() as u64);
+    let cipher = writer.into_inner().0;
+    assert!(cipher != plain);
+
+    let mut reader = XorStreamReader::new(Cursor::new(cipher), keystream);
+    let mut data = Vec::new();
+    block_on(reader.read_to_end(&mut data)).unwrap();
+    assert_eq!(data, plain);
+}
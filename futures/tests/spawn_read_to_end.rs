@@ -0,0 +1,59 @@
+extern crate futures;
+
+use std::io;
+
+use futures::channel::mpsc;
+use futures::executor::{block_on, spawn_read_to_end, ThreadPool};
+use futures::prelude::*;
+use futures::stream;
+
+/// The reading end of a pipe made of a channel of chunks.
+struct PipeReader {
+    rx: mpsc::Receiver<Vec<u8>>,
+    chunk: Vec<u8>,
+}
+
+impl AsyncRead for PipeReader {
+    fn poll_read(&mut self, cx: &mut task::Context, buf: &mut [u8]) -> Poll<usize, io::Error> {
+        while self.chunk.is_empty() {
+            match self.rx.poll_next(cx).unwrap() {
+                Async::Ready(Some(chunk)) => self.chunk = chunk,
+                Async::Ready(None) => return Ok(Async::Ready(0)),
+                Async::Pending => return Ok(Async::Pending),
+            }
+        }
+        let n = buf.len().min(self.chunk.len());
+        buf[..n].copy_from_slice(&self.chunk[..n]);
+        self.chunk.drain(..n);
+        Ok(Async::Ready(n))
+    }
+}
+
+#[test]
+fn drains_pipe_on_background_task() {
+    let mut pool = ThreadPool::new().unwrap();
+    let (tx, rx) = mpsc::channel(1);
+
+    let handle = spawn_read_to_end(&mut pool, PipeReader { rx, chunk: Vec::new() }).unwrap();
+    let chunks = (0..50u8).map(|i| vec![i; 100]).collect::<Vec<_>>();
+    let expected = chunks.concat();
+    let writer = tx.send_all(stream::iter_ok(chunks)).map(|_| ()).map_err(|_| panic!());
+    pool.spawn(Box::new(writer)).unwrap();
+
+    assert_eq!(block_on(handle).unwrap(), expected);
+}
+
+#[test]
+fn read_errors_reach_the_handle() {
+    struct Failing;
+
+    impl AsyncRead for Failing {
+        fn poll_read(&mut self, _: &mut task::Context, _: &mut [u8]) -> Poll<usize, io::Error> {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "gone"))
+        }
+    }
+
+    let mut pool = ThreadPool::new().unwrap();
+    let handle = spawn_read_to_end(&mut pool, Failing).unwrap();
+    assert_eq!(block_on(handle).unwrap_err().kind(), io::ErrorKind::BrokenPipe);
+}